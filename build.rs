@@ -1,14 +1,16 @@
-#[cfg(feature="bind_c")]
 use std::env;
+use std::path::{Path, PathBuf};
 
-#[cfg(feature="bind_c")]
-use std::path::PathBuf;
+mod codegen;
 
 fn main() {
     // Rerun on file changes
     println!("cargo:rerun-if-changed=./witx/*.witx");
+    println!("cargo:rerun-if-changed=witx");
+    println!("cargo:rerun-if-changed=codegen");
     println!("cargo:rerun-if-changed=lib/*");
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=WASME_UPDATE_HEADERS");
 
     // Setup WASI root
     // https://github.com/bytecodealliance/wasmtime/issues/3519
@@ -18,6 +20,14 @@ fn main() {
     // Export spec dir for other projects
     println!("cargo:ROOT={}", dir);
 
+    // Generate C headers from the witx specifications
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    #[allow(unused_variables)]
+    let inc = generate_headers(Path::new(&dir), &out_path);
+
+    // Export generated header dir for other projects
+    println!("cargo:INCLUDE={}", out_path.join("inc").display());
+
     // Generate C bindings if enabled
     #[cfg(feature="bind_c")]
     {
@@ -25,15 +35,23 @@ fn main() {
         let mut builder = bindgen::Builder::default()
             .use_core()
             .ctypes_prefix("::cty")
-            .header("inc/wasm_embedded/i2c.h")
-            .header("inc/wasm_embedded/spi.h")
-            .header("inc/wasm_embedded/uart.h")
-            .header("inc/wasm_embedded/gpio.h")
+            .header(inc.join("i2c.h").to_string_lossy())
+            .header(inc.join("spi.h").to_string_lossy())
+            .header(inc.join("uart.h").to_string_lossy())
+            .header(inc.join("gpio.h").to_string_lossy())
+            .header(inc.join("adc.h").to_string_lossy())
+            .header(inc.join("pwm.h").to_string_lossy())
+            .header(inc.join("time.h").to_string_lossy())
+            .header(inc.join("device.h").to_string_lossy())
             .allowlist_type("wasme.*")
             .allowlist_type("i2c.*")
             .allowlist_type("spi.*")
             .allowlist_type("uart.*")
-            .allowlist_type("gpio.*");
+            .allowlist_type("gpio.*")
+            .allowlist_type("adc.*")
+            .allowlist_type("pwm.*")
+            .allowlist_type("time_.*")
+            .allowlist_type("device.*");
 
         // Patches to help bindgen with cross compiling
         // See: https://github.com/rust-lang/rust-bindgen/issues/1229#issuecomment-366522257
@@ -67,9 +85,44 @@ fn main() {
         let bindings = builder.generate().expect("Unable to generate bindings");
 
         // Write the bindings to the $OUT_DIR/bindings.rs file.
-        let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
         bindings
             .write_to_file(out_path.join("bindings.rs"))
             .expect("Couldn't write bindings!");
     }
 }
+
+/// Generate C headers (and wasm3 / WAMR host bindings) into `$OUT_DIR/inc/wasm_embedded`,
+/// returning the header directory.
+///
+/// The committed headers in `inc/wasm_embedded` are checked against the generated output,
+/// set `WASME_UPDATE_HEADERS=1` to update these on build.
+fn generate_headers(root: &Path, out_path: &Path) -> PathBuf {
+    let spec = codegen::Spec::load(&root.join("witx")).expect("Failed to load witx");
+    let mut headers = codegen::c::generate(&spec);
+    headers.extend(codegen::wasm3::generate(&spec));
+    headers.extend(codegen::wamr::generate(&spec));
+
+    let gen_dir = out_path.join("inc/wasm_embedded");
+    let inc_dir = root.join("inc/wasm_embedded");
+    let update = env::var("WASME_UPDATE_HEADERS").is_ok();
+
+    for (name, contents) in headers {
+        let gen_file = gen_dir.join(&name);
+        std::fs::create_dir_all(gen_file.parent().unwrap()).expect("Failed to create header dir");
+        std::fs::write(&gen_file, &contents).expect("Failed to write header");
+
+        let inc_file = inc_dir.join(&name);
+        if update {
+            std::fs::create_dir_all(inc_file.parent().unwrap()).expect("Failed to create header dir");
+            std::fs::write(&inc_file, &contents).expect("Failed to update header");
+        } else if std::fs::read_to_string(&inc_file).ok().as_deref() != Some(contents.as_str()) {
+            println!(
+                "cargo:warning=inc/wasm_embedded/{} is out of date with the witx specifications, \
+                rebuild with WASME_UPDATE_HEADERS=1 to regenerate",
+                name
+            );
+        }
+    }
+
+    gen_dir
+}
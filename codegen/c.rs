@@ -0,0 +1,267 @@
+//! C driver header generation.
+//!
+//! Emits a header per witx module containing the module types, a function typedef per
+//! interface function (taking a platform `ctx` followed by the lowered parameters),
+//! and a `<module>_drv_t` driver object collecting these functions.
+
+use std::fmt::Write;
+use std::rc::Rc;
+
+use witx::{BuiltinType, NamedType, RecordKind, Type, TypeRef};
+
+use super::{buffer_elem, builtin, type_name, Func, ModuleSpec, Param, Ret, Spec, COMMON_PREFIX};
+
+/// Generate headers for the provided specification, returning `(file name, contents)` pairs
+pub fn generate(spec: &Spec) -> Vec<(String, String)> {
+    let mut headers = vec![("common.h".to_string(), common(spec))];
+
+    for m in &spec.modules {
+        headers.push((format!("{}.h", m.name), module(spec, m)));
+    }
+
+    headers
+}
+
+/// Generate the common header, containing error codes
+fn common(spec: &Spec) -> String {
+    let mut s = String::new();
+
+    preamble(&mut s, "common definitions", "common", &[]);
+
+    writeln!(s, "/// Functions return zero (or a non-negative handle or count where").unwrap();
+    writeln!(s, "/// documented) on success, or a negated wasme_errno_e on failure").unwrap();
+    writeln!(s).unwrap();
+
+    for t in spec.common.typenames() {
+        named_type(&mut s, COMMON_PREFIX, "common", &t);
+    }
+
+    postamble(&mut s);
+
+    s
+}
+
+/// Generate the header for a module
+fn module(spec: &Spec, m: &ModuleSpec) -> String {
+    let mut s = String::new();
+    let title = format!("{} platform interface", m.name.to_uppercase());
+
+    preamble(&mut s, &title, &m.name, &["\"common.h\""]);
+
+    for t in m.typenames(&spec.common) {
+        named_type(&mut s, &m.name, &m.name, &t);
+    }
+
+    let funcs: Vec<_> = m.funcs().map(|f| Func::new(&m.name, &f)).collect();
+
+    for f in &funcs {
+        func(&mut s, &m.name, f);
+    }
+
+    writeln!(s, "/// C platform {} driver object", m.name.to_uppercase()).unwrap();
+    writeln!(s, "typedef struct {{").unwrap();
+    for f in &funcs {
+        writeln!(s, "    {}_{}_f *{};", m.name, f.name, f.name).unwrap();
+    }
+    writeln!(s, "}} {}_drv_t;", m.name).unwrap();
+    writeln!(s).unwrap();
+
+    postamble(&mut s);
+
+    s
+}
+
+fn preamble(s: &mut String, title: &str, name: &str, includes: &[&str]) {
+    let guard = format!("WASME_{}_DRIVER_H", name.to_uppercase());
+
+    writeln!(s, "/// Embedded WASM abstract C {}", title).unwrap();
+    writeln!(s, "///").unwrap();
+    writeln!(s, "/// Generated from {}.witx by build.rs, do not edit", name).unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "#ifndef {}", guard).unwrap();
+    writeln!(s, "#define {}", guard).unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "#include <stdint.h>").unwrap();
+    for i in includes {
+        writeln!(s, "#include {}", i).unwrap();
+    }
+    writeln!(s).unwrap();
+    writeln!(s, "#ifdef __cplusplus").unwrap();
+    writeln!(s, "extern \"C\"").unwrap();
+    writeln!(s, "{{").unwrap();
+    writeln!(s, "#endif").unwrap();
+    writeln!(s).unwrap();
+}
+
+fn postamble(s: &mut String) {
+    writeln!(s, "#ifdef __cplusplus").unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s, "#endif").unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "#endif").unwrap();
+}
+
+/// Write doc comment lines with the provided indent
+fn docs(s: &mut String, indent: &str, docs: &str) {
+    for l in docs.lines() {
+        match l.trim_end() {
+            "" => writeln!(s, "{}///", indent).unwrap(),
+            l => writeln!(s, "{}/// {}", indent, l).unwrap(),
+        }
+    }
+}
+
+/// Emit enums, flags and records, aliases and buffers are resolved at use
+fn named_type(s: &mut String, prefix: &str, file: &str, t: &Rc<NamedType>) {
+    let name = match prefix {
+        COMMON_PREFIX => format!("{}_{}", COMMON_PREFIX, t.name.as_str()),
+        _ => type_name(prefix, &TypeRef::Name(t.clone())),
+    };
+    let see = format!("see `${}` in {}.witx", t.name.as_str(), file);
+
+    match &**t.type_() {
+        Type::Variant(v) if v.is_enum() => {
+            type_docs(s, &t.docs, &see);
+            writeln!(s, "typedef enum {{").unwrap();
+            for (i, c) in v.cases.iter().enumerate() {
+                docs(s, "    ", &c.docs);
+                let c = c.name.as_str().to_uppercase();
+                writeln!(s, "    {}_{} = {},", name.to_uppercase(), c, i).unwrap();
+            }
+            writeln!(s, "}} {}_e;", name).unwrap();
+        }
+        Type::Record(r) if matches!(r.kind, RecordKind::Bitflags(_)) => {
+            type_docs(s, &t.docs, &see);
+            writeln!(s, "typedef enum {{").unwrap();
+            for (i, m) in r.members.iter().enumerate() {
+                docs(s, "    ", &m.docs);
+                let m = m.name.as_str().to_uppercase();
+                writeln!(s, "    {}_{} = (1 << {}),", name.to_uppercase(), m, i).unwrap();
+            }
+            writeln!(s, "}} {}_e;", name).unwrap();
+        }
+        Type::Record(r) if buffer_elem(r).is_none() => {
+            type_docs(s, &t.docs, &see);
+            writeln!(s, "typedef struct {{").unwrap();
+            for m in &r.members {
+                let mut d = m.docs.trim_end().to_string();
+                if let Some(k) = kind_name(prefix, &m.tref) {
+                    d = format!("{}, {}_e", d, k);
+                }
+                docs(s, "    ", &d);
+                let ty = c_type(prefix, &m.tref);
+                match ty.ends_with('*') {
+                    true => writeln!(s, "    {}{};", ty, m.name.as_str()).unwrap(),
+                    false => writeln!(s, "    {} {};", ty, m.name.as_str()).unwrap(),
+                }
+            }
+            writeln!(s, "}} {}_t;", name).unwrap();
+        }
+        _ => return,
+    }
+
+    writeln!(s).unwrap();
+}
+
+fn type_docs(s: &mut String, d: &str, see: &str) {
+    match d.trim_end() {
+        "" => writeln!(s, "/// {}", capitalise(see)).unwrap(),
+        d => {
+            docs(s, "", d);
+            writeln!(s, "///").unwrap();
+            writeln!(s, "/// {}", capitalise(see)).unwrap();
+        }
+    }
+}
+
+fn capitalise(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        Some(f) => f.to_uppercase().chain(c).collect(),
+        None => String::new(),
+    }
+}
+
+/// Fetch the enum or flags name for a named scalar type
+fn kind_name(prefix: &str, tref: &TypeRef) -> Option<String> {
+    match (tref, &**tref.type_()) {
+        (TypeRef::Name(_), Type::Variant(_)) => Some(type_name(prefix, tref)),
+        (TypeRef::Name(_), Type::Record(r)) if r.bitflags_repr().is_some() => {
+            Some(type_name(prefix, tref))
+        }
+        _ => None,
+    }
+}
+
+/// C type for record fields, buffer elements and output values, in the context of a module
+pub fn c_type(module: &str, tref: &TypeRef) -> String {
+    match &**tref.type_() {
+        Type::Pointer(t) | Type::ConstPointer(t) => format!("{} *", c_type(module, t)),
+        Type::Record(r) if r.bitflags_repr().is_none() => format!("{}_t", type_name(module, tref)),
+        _ => c_builtin(builtin(tref)).to_string(),
+    }
+}
+
+pub fn c_builtin(b: BuiltinType) -> &'static str {
+    match b {
+        BuiltinType::U8 { .. } => "uint8_t",
+        BuiltinType::U16 => "uint16_t",
+        BuiltinType::U32 { .. } | BuiltinType::Char => "uint32_t",
+        BuiltinType::U64 => "uint64_t",
+        BuiltinType::S8 => "int8_t",
+        BuiltinType::S16 => "int16_t",
+        BuiltinType::S32 => "int32_t",
+        BuiltinType::S64 => "int64_t",
+        BuiltinType::F32 => "float",
+        BuiltinType::F64 => "double",
+    }
+}
+
+/// Emit the typedef for a function
+fn func(s: &mut String, module: &str, f: &Func) {
+    let mut args = vec!["const void *ctx".to_string()];
+    let mut d = String::new();
+
+    if !f.docs.trim().is_empty() {
+        writeln!(d, "{}", f.docs.trim_end()).unwrap();
+        writeln!(d).unwrap();
+    }
+
+    writeln!(d, "`{}.{}`, see {}.witx", module, f.name, module).unwrap();
+
+    for p in &f.params {
+        match p {
+            Param::Value { name, ty, .. } => args.push(format!("{} {}", c_builtin(*ty), name)),
+            Param::Buffer { name, elem, .. } => {
+                args.push(format!("{} *{}", c_type(module, elem), name));
+                args.push(format!("uint32_t {}_len", name));
+            }
+            Param::Record { name, ty, .. } => args.push(format!("const {}_t *{}", ty, name)),
+        }
+
+        let mut line = match p.docs().trim() {
+            "" => format!("- {}", p.name()),
+            docs => format!("- {}: {}", p.name(), docs),
+        };
+        if let Param::Value { kind: Some(k), .. } = p {
+            line = format!("{} ({}_e)", line, k);
+        }
+        writeln!(d, "{}", line).unwrap();
+    }
+
+    match &f.ret {
+        Ret::None => (),
+        Ret::Value { docs } => match docs.trim() {
+            "" => writeln!(d, "Returns a non-negative value or error").unwrap(),
+            r => writeln!(d, "{}", r).unwrap(),
+        },
+        Ret::Out { name, tref, docs } => {
+            args.push(format!("{} *{}", c_type(module, tref), name));
+            writeln!(d, "- {}: {}", name, docs.trim()).unwrap();
+        }
+    }
+
+    docs(s, "", &d);
+    writeln!(s, "typedef int32_t {}_{}_f({});", module, f.name, args.join(", ")).unwrap();
+    writeln!(s).unwrap();
+}
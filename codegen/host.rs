@@ -0,0 +1,340 @@
+//! Common host binding generation for C wasm runtimes.
+//!
+//! Runtimes differ in how host functions receive arguments and access guest memory,
+//! while lowering, validation and translation of guest arguments and dispatch to the C
+//! `<module>_drv_t` driver objects is shared (see [`super::wasm3`] and [`super::wamr`]).
+
+use std::fmt::Write;
+
+use witx::{BuiltinType, Layout, RecordDatatype, Type, TypeRef};
+
+use super::c::{c_builtin, c_type};
+use super::{Func, Param, Ret};
+
+/// Runtime specific C fragments
+pub struct Runtime {
+    /// Runtime name used in header titles
+    pub name: &'static str,
+    /// Prefix for generated functions and helpers (`wasme_wasm3` etc.)
+    pub prefix: &'static str,
+    /// System headers included by each generated header
+    pub includes: &'static [&'static str],
+    /// Runtime API header (`"wasm3.h"` etc.)
+    pub header: &'static str,
+    /// Translate a guest offset expression to a native pointer
+    pub ptr: fn(&str) -> String,
+    /// Return a value from a host function
+    pub ret: fn(&str) -> String,
+}
+
+impl Runtime {
+    /// Emit the header comment, include guard and includes for a generated header,
+    /// with `includes` following the runtime header
+    pub fn preamble(
+        &self,
+        s: &mut String,
+        title: &str,
+        source: &str,
+        name: &str,
+        includes: &[&str],
+    ) {
+        let guard = format!("{}_{}_H", self.prefix.to_uppercase(), name.to_uppercase());
+
+        writeln!(s, "/// Embedded WASM {} {}", self.name, title).unwrap();
+        writeln!(s, "///").unwrap();
+        match source.ends_with(".witx") {
+            true => writeln!(s, "/// Generated from {} by build.rs, do not edit", source).unwrap(),
+            false => writeln!(s, "/// Generated by {}, do not edit", source).unwrap(),
+        }
+        writeln!(s).unwrap();
+        writeln!(s, "#ifndef {}", guard).unwrap();
+        writeln!(s, "#define {}", guard).unwrap();
+        writeln!(s).unwrap();
+
+        for i in self.includes {
+            writeln!(s, "#include {}", i).unwrap();
+        }
+        writeln!(s).unwrap();
+        writeln!(s, "#include {}", self.header).unwrap();
+        for i in includes {
+            writeln!(s, "#include {}", i).unwrap();
+        }
+        writeln!(s).unwrap();
+        writeln!(s, "#ifdef __cplusplus").unwrap();
+        writeln!(s, "extern \"C\"").unwrap();
+        writeln!(s, "{{").unwrap();
+        writeln!(s, "#endif").unwrap();
+        writeln!(s).unwrap();
+    }
+
+    /// Memory check macro, trapping if the guest region is out of bounds
+    fn check_mem(&self, offset: &str, len: &str) -> String {
+        format!("{}_CHECK_MEM({}, {});", self.prefix.to_uppercase(), offset, len)
+    }
+
+    /// Maximum number of records in a list argument
+    fn max_ops(&self) -> String {
+        format!("{}_MAX_OPS", self.prefix.to_uppercase())
+    }
+}
+
+/// Close the `extern "C"` block and include guard opened by [Runtime::preamble]
+pub fn postamble(s: &mut String) {
+    writeln!(s, "#ifdef __cplusplus").unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s, "#endif").unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "#endif").unwrap();
+}
+
+/// Lowered wasm argument for a host function
+pub struct Arg {
+    /// C type of the argument
+    pub ty: &'static str,
+    /// Argument name
+    pub name: String,
+    /// Signature character (`i`, `I`, `f` or `F`)
+    pub sig: char,
+}
+
+/// Lower function parameters (and the trailing out pointer) to wasm arguments
+pub fn args(f: &Func) -> Vec<Arg> {
+    let mut args = vec![];
+    let arg = |ty, name: String, sig| Arg { ty, name, sig };
+
+    for p in &f.params {
+        match p {
+            Param::Value { name, ty, .. } => {
+                let (t, c) = wasm_arg(*ty);
+                args.push(arg(t, name.clone(), c));
+            }
+            Param::Buffer { name, indirect: true, .. } => {
+                args.push(arg("uint32_t", format!("{}_ref", name), 'i'));
+            }
+            Param::Buffer { name, indirect: false, .. } => {
+                args.push(arg("uint32_t", format!("{}_off", name), 'i'));
+                args.push(arg("uint32_t", format!("{}_len", name), 'i'));
+            }
+            Param::Record { name, .. } => {
+                args.push(arg("uint32_t", format!("{}_off", name), 'i'));
+            }
+        }
+    }
+    if !matches!(f.ret, Ret::None) {
+        args.push(arg("uint32_t", "out_off".to_string(), 'i'));
+    }
+
+    args
+}
+
+/// Check whether a function accesses guest memory
+pub fn uses_memory(f: &Func) -> bool {
+    let scalar = f.params.iter().all(|p| matches!(p, Param::Value { .. }));
+    !(scalar && matches!(f.ret, Ret::None))
+}
+
+/// Emit the body of a host function, following the fetch of raw arguments and `binding`.
+///
+/// This looks up the driver function, validates and translates arguments from guest memory,
+/// dispatches to the driver then writes outputs and returns the resulting errno.
+pub fn body(s: &mut String, rt: &Runtime, module: &str, f: &Func) {
+    let ret = rt.ret;
+    let ptr = rt.ptr;
+    let mut args = vec!["binding->ctx".to_string()];
+
+    writeln!(s, "    const {m}_drv_t *drv = (const {m}_drv_t *)binding->drv;", m = module).unwrap();
+    writeln!(s, "    if (drv->{} == NULL) {{", f.name).unwrap();
+    writeln!(s, "        {}", ret("WASME_ERRNO_UNSUPPORTED")).unwrap();
+    writeln!(s, "    }}").unwrap();
+    writeln!(s).unwrap();
+
+    // Validate and translate arguments
+    for p in &f.params {
+        match p {
+            Param::Value { name, ty, .. } => {
+                let c = c_builtin(*ty);
+                match c == wasm_arg(*ty).0 {
+                    true => args.push(name.clone()),
+                    false => args.push(format!("({}){}", c, name)),
+                }
+            }
+            Param::Buffer { name, elem, indirect, .. } => {
+                if *indirect {
+                    // `{ptr, len}` records are two 32-bit words in guest memory
+                    let r = format!("{}_ref", name);
+                    writeln!(s, "    {}", rt.check_mem(&r, "8")).unwrap();
+                    writeln!(s, "    uint32_t {n}_off, {n}_len;", n = name).unwrap();
+                    writeln!(s, "    memcpy(&{}_off, {}, 4);", name, ptr(&r)).unwrap();
+                    let len_ptr = ptr(&format!("{} + 4", r));
+                    writeln!(s, "    memcpy(&{}_len, {}, 4);", name, len_ptr).unwrap();
+                }
+
+                let (ty, size) = (c_type(module, elem), elem.mem_size());
+                let off = format!("{}_off", name);
+                let len = format!("(uint64_t){}_len * {}", name, size);
+                match record(elem) {
+                    Some(r) => {
+                        writeln!(s, "    if ({}_len > {}) {{", name, rt.max_ops()).unwrap();
+                        writeln!(s, "        {}", ret("WASME_ERRNO_INVALID_ARG")).unwrap();
+                        writeln!(s, "    }}").unwrap();
+                        writeln!(s, "    {}", rt.check_mem(&off, &len)).unwrap();
+                        writeln!(s, "    {} {}[{}];", ty, name, rt.max_ops()).unwrap();
+                        writeln!(s, "    for (uint32_t i = 0; i < {}_len; i++) {{", name).unwrap();
+                        writeln!(
+                            s,
+                            "        const uint8_t *src = (const uint8_t *){};",
+                            ptr(&format!("{} + i * {}", off, size))
+                        )
+                        .unwrap();
+                        copy_in(s, rt, "        ", module, &format!("{}[i]", name), "src", r);
+                        writeln!(s, "    }}").unwrap();
+                    }
+                    None => {
+                        writeln!(s, "    {}", rt.check_mem(&off, &len)).unwrap();
+                        writeln!(s, "    {t} *{n} = ({t} *){p};", t = ty, n = name, p = ptr(&off))
+                            .unwrap();
+                    }
+                }
+                writeln!(s).unwrap();
+
+                args.push(name.clone());
+                args.push(format!("{}_len", name));
+            }
+            Param::Record { name, ty, tref, .. } => {
+                let r = record(tref).unwrap();
+                let off = format!("{}_off", name);
+                writeln!(s, "    {}", rt.check_mem(&off, &tref.mem_size().to_string())).unwrap();
+                writeln!(s, "    const uint8_t *{}_src = (const uint8_t *){};", name, ptr(&off))
+                    .unwrap();
+                writeln!(s, "    {}_t {};", ty, name).unwrap();
+                copy_in(s, rt, "    ", module, name, &format!("{}_src", name), r);
+                writeln!(s).unwrap();
+
+                args.push(format!("&{}", name));
+            }
+        }
+    }
+
+    match &f.ret {
+        Ret::None => (),
+        Ret::Value { .. } => {
+            writeln!(s, "    {}", rt.check_mem("out_off", "4")).unwrap();
+            writeln!(s).unwrap();
+        }
+        Ret::Out { tref, .. } => {
+            writeln!(s, "    {}", rt.check_mem("out_off", &tref.mem_size().to_string())).unwrap();
+            writeln!(s, "    {} out;", c_type(module, tref)).unwrap();
+            writeln!(s).unwrap();
+            args.push("&out".to_string());
+        }
+    }
+
+    // Dispatch to the driver and map results
+    writeln!(s, "    int32_t res = drv->{}({});", f.name, args.join(", ")).unwrap();
+    writeln!(s, "    if (res < 0) {{").unwrap();
+    writeln!(s, "        {}", ret("(uint32_t)-res")).unwrap();
+    writeln!(s, "    }}").unwrap();
+    writeln!(s).unwrap();
+
+    match &f.ret {
+        Ret::None => (),
+        Ret::Value { .. } => {
+            writeln!(s, "    memcpy({}, &res, 4);", ptr("out_off")).unwrap();
+        }
+        Ret::Out { tref, .. } => match record(tref) {
+            Some(r) => {
+                writeln!(s, "    uint8_t *dst = (uint8_t *){};", ptr("out_off")).unwrap();
+                copy_out(s, "    ", "dst", "out", r);
+            }
+            None => {
+                writeln!(s, "    memcpy({}, &out, {});", ptr("out_off"), tref.mem_size()).unwrap();
+            }
+        },
+    }
+
+    writeln!(s, "    {}", ret("WASME_ERRNO_OK")).unwrap();
+}
+
+/// C type and signature character for a scalar argument
+fn wasm_arg(b: BuiltinType) -> (&'static str, char) {
+    match b {
+        BuiltinType::U64 => ("uint64_t", 'I'),
+        BuiltinType::S64 => ("int64_t", 'I'),
+        BuiltinType::F32 => ("float", 'f'),
+        BuiltinType::F64 => ("double", 'F'),
+        BuiltinType::S8 | BuiltinType::S16 | BuiltinType::S32 => ("int32_t", 'i'),
+        _ => ("uint32_t", 'i'),
+    }
+}
+
+/// Fetch the record definition for record types
+fn record(tref: &TypeRef) -> Option<&RecordDatatype> {
+    match &**tref.type_() {
+        Type::Record(r) if r.bitflags_repr().is_none() => Some(r),
+        _ => None,
+    }
+}
+
+/// Copy a record from guest memory at `src` into the C struct `dst`,
+/// translating and validating pointer members against their sibling `len`
+fn copy_in(
+    s: &mut String,
+    rt: &Runtime,
+    indent: &str,
+    module: &str,
+    dst: &str,
+    src: &str,
+    r: &RecordDatatype,
+) {
+    let layout = r.member_layout();
+
+    for l in layout.iter().filter(|l| !is_pointer(&l.member.tref)) {
+        let (name, size) = (l.member.name.as_str(), l.member.tref.mem_size());
+        writeln!(s, "{}memcpy(&{}.{}, {} + {}, {});", indent, dst, name, src, l.offset, size)
+            .unwrap();
+    }
+
+    for l in layout.iter().filter(|l| is_pointer(&l.member.tref)) {
+        let name = l.member.name.as_str();
+        let elem = match &**l.member.tref.type_() {
+            Type::Pointer(t) | Type::ConstPointer(t) => t.clone(),
+            _ => unreachable!(),
+        };
+        if !r.members.iter().any(|m| m.name.as_str() == "len") {
+            panic!("pointer member {} requires a len member", name);
+        }
+
+        let off = format!("{}_off", name);
+        let len = format!("(uint64_t){}.len * {}", dst, elem.mem_size());
+        writeln!(s, "{}uint32_t {};", indent, off).unwrap();
+        writeln!(s, "{}memcpy(&{}, {} + {}, 4);", indent, off, src, l.offset).unwrap();
+        writeln!(s, "{}{}", indent, rt.check_mem(&off, &len)).unwrap();
+        writeln!(
+            s,
+            "{}{}.{} = ({} *){};",
+            indent,
+            dst,
+            name,
+            c_type(module, &elem),
+            (rt.ptr)(&off)
+        )
+        .unwrap();
+    }
+}
+
+/// Copy the C struct `src` out to guest memory at `dst`
+fn copy_out(s: &mut String, indent: &str, dst: &str, src: &str, r: &RecordDatatype) {
+    for l in r.member_layout() {
+        if is_pointer(&l.member.tref) {
+            panic!("pointer member {} cannot be returned", l.member.name.as_str());
+        }
+
+        let (name, size) = (l.member.name.as_str(), l.member.tref.mem_size());
+        writeln!(s, "{}memcpy({} + {}, &{}.{}, {});", indent, dst, l.offset, src, name, size)
+            .unwrap();
+    }
+}
+
+fn is_pointer(tref: &TypeRef) -> bool {
+    matches!(&**tref.type_(), Type::Pointer(_) | Type::ConstPointer(_))
+}
@@ -0,0 +1,235 @@
+//! Code generation from the witx specifications, used by `build.rs`.
+//!
+//! Each `witx/<module>.witx` file is loaded independently (alongside `common.witx`)
+//! so definitions can be attributed to the module that declares them.
+
+use std::path::Path;
+use std::rc::Rc;
+
+use witx::{BuiltinType, Document, NamedType, RecordKind, Type, TypeRef};
+
+pub mod c;
+pub mod host;
+pub mod wamr;
+pub mod wasm3;
+
+/// Prefix used for types declared in `common.witx`
+pub const COMMON_PREFIX: &str = "wasme";
+
+/// Loaded witx specification
+pub struct Spec {
+    /// Definitions from `common.witx`
+    pub common: Document,
+    /// Per-module definitions, in alphabetical order
+    pub modules: Vec<ModuleSpec>,
+}
+
+/// A single witx module
+pub struct ModuleSpec {
+    /// Module name (and witx file stem)
+    pub name: String,
+    /// Document containing the module and its dependencies
+    pub doc: Document,
+}
+
+impl Spec {
+    /// Load all specifications from the provided witx directory
+    pub fn load(dir: &Path) -> Result<Self, witx::WitxError> {
+        let common = witx::load(&[dir.join("common.witx")])?;
+
+        let mut files: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| witx::WitxError::Io(dir.to_path_buf(), e))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().map(|e| e == "witx").unwrap_or(false))
+            .filter(|p| !p.ends_with("common.witx"))
+            .collect();
+        files.sort();
+
+        let mut modules = Vec::with_capacity(files.len());
+        for f in files {
+            let name = f.file_stem().unwrap().to_string_lossy().to_string();
+            let doc = witx::load(&[&f])?;
+            modules.push(ModuleSpec { name, doc });
+        }
+
+        Ok(Self { common, modules })
+    }
+}
+
+impl ModuleSpec {
+    /// Types declared in this module (excluding those from `common.witx`)
+    pub fn typenames<'a>(
+        &'a self,
+        common: &'a Document,
+    ) -> impl Iterator<Item = Rc<NamedType>> + 'a {
+        self.doc.typenames().filter(|t| common.typename(&t.name).is_none())
+    }
+
+    /// Functions exported by this module
+    pub fn funcs(&self) -> impl Iterator<Item = Rc<witx::InterfaceFunc>> + '_ {
+        self.doc.modules().flat_map(|m| m.funcs().collect::<Vec<_>>())
+    }
+}
+
+/// Lowered function parameter
+pub enum Param {
+    /// Scalar value, with the name of the enum or flags type if applicable
+    Value { name: String, ty: BuiltinType, kind: Option<String>, docs: String },
+    /// Buffer or list, passed as an element pointer and length.
+    /// `indirect` buffers are `{ptr, len}` records, passed to wasm by reference
+    Buffer { name: String, elem: TypeRef, indirect: bool, docs: String },
+    /// Record, passed by pointer
+    Record { name: String, ty: String, tref: TypeRef, docs: String },
+}
+
+/// Lowered function result
+pub enum Ret {
+    /// No value beyond the result code
+    None,
+    /// Non-negative value (handle or count) returned in place of the result code
+    Value { docs: String },
+    /// Value written to an output pointer
+    Out { name: String, tref: TypeRef, docs: String },
+}
+
+/// Lowered function signature
+pub struct Func {
+    pub name: String,
+    pub docs: String,
+    pub params: Vec<Param>,
+    pub ret: Ret,
+}
+
+impl Func {
+    /// Lower a witx function
+    pub fn new(module: &str, f: &witx::InterfaceFunc) -> Self {
+        let params = f.params.iter().map(|p| Param::new(module, p)).collect();
+
+        let ret = match f.results.first() {
+            Some(r) => Ret::new(module, r),
+            None => Ret::None,
+        };
+
+        Self { name: f.name.as_str().to_string(), docs: f.docs.clone(), params, ret }
+    }
+}
+
+impl Param {
+    fn new(module: &str, p: &witx::InterfaceFuncParam) -> Self {
+        let name = p.name.as_str().to_string();
+        let docs = p.docs.clone();
+
+        match &**p.tref.type_() {
+            Type::List(elem) => Param::Buffer { name, elem: elem.clone(), indirect: false, docs },
+            Type::Record(r) if r.bitflags_repr().is_none() => match buffer_elem(r) {
+                Some(elem) => Param::Buffer { name, elem, indirect: true, docs },
+                None => {
+                    let ty = type_name(module, &p.tref);
+                    Param::Record { name, ty, tref: p.tref.clone(), docs }
+                }
+            },
+            _ => {
+                let kind = match (&p.tref, &**p.tref.type_()) {
+                    (TypeRef::Name(nt), Type::Variant(_) | Type::Record(_)) => {
+                        Some(type_name(module, &TypeRef::Name(nt.clone())))
+                    }
+                    _ => None,
+                };
+                Param::Value { name, ty: builtin(&p.tref), kind, docs }
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Param::Value { name, .. } => name,
+            Param::Buffer { name, .. } => name,
+            Param::Record { name, .. } => name,
+        }
+    }
+
+    pub fn docs(&self) -> &str {
+        match self {
+            Param::Value { docs, .. } => docs,
+            Param::Buffer { docs, .. } => docs,
+            Param::Record { docs, .. } => docs,
+        }
+    }
+}
+
+impl Ret {
+    fn new(module: &str, r: &witx::InterfaceFuncParam) -> Self {
+        let docs = r.docs.clone();
+
+        let ok = match &**r.tref.type_() {
+            Type::Variant(v) => v.as_expected().and_then(|(ok, _err)| ok.cloned()),
+            _ => Some(r.tref.clone()),
+        };
+
+        match ok {
+            None => Ret::None,
+            // Handles and sizes are returned in place of the (negative) result code
+            Some(TypeRef::Name(nt)) if nt.name.as_str() == "dev" || nt.name.as_str() == "size" => {
+                Ret::Value { docs }
+            }
+            Some(tref) => {
+                // Name outputs by type, stripping prefixes from records and enums
+                let name = match (&tref, &**tref.type_()) {
+                    (TypeRef::Name(nt), Type::Builtin(_)) => nt.name.as_str().to_string(),
+                    (TypeRef::Name(nt), _) => strip_prefix(module, nt.name.as_str()).to_string(),
+                    (TypeRef::Value(_), _) => r.name.as_str().to_string(),
+                };
+                Ret::Out { name, tref, docs }
+            }
+        }
+    }
+}
+
+/// Fetch the element type for `{ptr, len}` buffer records
+pub fn buffer_elem(r: &witx::RecordDatatype) -> Option<TypeRef> {
+    let (ptr, len) = match (&r.kind, r.members.as_slice()) {
+        (RecordKind::Other, [ptr, len]) => (ptr, len),
+        _ => return None,
+    };
+
+    match &**ptr.tref.type_() {
+        Type::Pointer(t) | Type::ConstPointer(t)
+            if ptr.name.as_str() == "ptr" && len.name.as_str() == "len" =>
+        {
+            Some(t.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Resolve the integer representation of a scalar type
+pub fn builtin(tref: &TypeRef) -> BuiltinType {
+    match &**tref.type_() {
+        Type::Builtin(b) => *b,
+        Type::Variant(v) => v.tag_repr.to_builtin(),
+        Type::Record(r) => match r.kind {
+            RecordKind::Bitflags(repr) => repr.to_builtin(),
+            _ => panic!("record is not a scalar type"),
+        },
+        Type::Handle(_) => BuiltinType::S32,
+        t => panic!("{} is not a scalar type", t.kind()),
+    }
+}
+
+/// Strip the module prefix from a type name, `gpio_cfg` in `gpio` becomes `cfg`
+fn strip_prefix<'a>(module: &str, name: &'a str) -> &'a str {
+    name.strip_prefix(module).and_then(|n| n.strip_prefix('_')).unwrap_or(name)
+}
+
+/// Fetch the module-prefixed name for a named type, `$cfg` in `spi` becomes `spi_cfg`
+pub fn type_name(module: &str, tref: &TypeRef) -> String {
+    let name = match tref {
+        TypeRef::Name(nt) => nt.name.as_str(),
+        TypeRef::Value(_) => panic!("anonymous types cannot be named"),
+    };
+
+    match name {
+        "errno" => format!("{}_{}", COMMON_PREFIX, name),
+        _ => format!("{}_{}", module, strip_prefix(module, name)),
+    }
+}
@@ -0,0 +1,179 @@
+//! WAMR host binding generation.
+//!
+//! Emits a header per witx module containing a native function per interface function,
+//! which validates and translates guest arguments before dispatching to the C
+//! `<module>_drv_t` driver object, a `NativeSymbol` template for these functions and a
+//! `wasme_wamr_register_<module>` function to register a caller-owned copy with WAMR.
+
+use std::fmt::Write;
+
+use super::host::{self, Runtime};
+use super::{Func, ModuleSpec, Spec};
+
+/// Generate WAMR headers for the provided specification, returning `(file name, contents)` pairs
+pub fn generate(spec: &Spec) -> Vec<(String, String)> {
+    let mut headers = vec![("wamr/common.h".to_string(), common())];
+
+    for m in &spec.modules {
+        headers.push((format!("wamr/{}.h", m.name), module(m)));
+    }
+
+    headers
+}
+
+/// Generate the common header, containing the driver binding and memory helpers
+fn common() -> String {
+    let mut s = String::new();
+
+    RUNTIME.preamble(&mut s, "host binding helpers", "build.rs", "common", &["\"../common.h\""]);
+
+    s.push_str(
+        r#"/// Maximum number of records in a list argument (such as I2C or SPI operations),
+/// longer lists are rejected with WASME_ERRNO_INVALID_ARG
+#ifndef WASME_WAMR_MAX_OPS
+#define WASME_WAMR_MAX_OPS 16
+#endif
+
+/// Driver binding, attached to each registered native symbol
+typedef struct {
+    /// Driver object for the registered module (`gpio_drv_t` etc.)
+    const void *drv;
+    /// Platform context passed to driver functions
+    const void *ctx;
+} wasme_wamr_binding_t;
+
+/// Raise an exception and return if the guest memory region at `offset` is out of bounds,
+/// for use in native functions
+#define WASME_WAMR_CHECK_MEM(offset, len)                                                     \
+    if ((uint64_t)(offset) + (uint64_t)(len) > UINT32_MAX                                     \
+        || !wasm_runtime_validate_app_addr(inst, (uint32_t)(offset), (uint32_t)(len))) {     \
+        wasm_runtime_set_exception(inst, "out of bounds memory access");                    \
+        return 0;                                                                             \
+    }
+
+/// Populate the caller-owned `symbols` table from `defaults`, attaching `binding` to each
+/// symbol, and register it with WAMR
+static inline bool wasme_wamr_register(const char *module_name, NativeSymbol *symbols,
+                                       const NativeSymbol *defaults, uint32_t len,
+                                       const wasme_wamr_binding_t *binding)
+{
+    for (uint32_t i = 0; i < len; i++) {
+        symbols[i] = defaults[i];
+        symbols[i].attachment = (void *)binding;
+    }
+    return wasm_runtime_register_natives(module_name, symbols, len);
+}
+
+"#,
+    );
+
+    host::postamble(&mut s);
+
+    s
+}
+
+/// Generate the header for a module
+fn module(m: &ModuleSpec) -> String {
+    let mut s = String::new();
+    let title = format!("{} host bindings", m.name.to_uppercase());
+    let source = format!("{}.witx", m.name);
+    let include = format!("\"../{}.h\"", m.name);
+
+    RUNTIME.preamble(&mut s, &title, &source, &m.name, &[&include, "\"common.h\""]);
+
+    let funcs: Vec<_> = m.funcs().map(|f| Func::new(&m.name, &f)).collect();
+
+    let mut sigs = vec![];
+    for f in &funcs {
+        sigs.push(func(&mut s, &m.name, f));
+    }
+
+    let count = format!("WASME_WAMR_{}_SYMBOLS", m.name.to_uppercase());
+    writeln!(s, "/// Number of `{}` native symbols", m.name).unwrap();
+    writeln!(s, "#define {} {}", count, funcs.len()).unwrap();
+    writeln!(s).unwrap();
+
+    writeln!(s, "/// `{}` native symbol template, see `wasme_wamr_register_{}`", m.name, m.name)
+        .unwrap();
+    writeln!(s, "static const NativeSymbol wasme_wamr_{}_symbols[{}] = {{", m.name, count).unwrap();
+    for (f, sig) in funcs.iter().zip(&sigs) {
+        writeln!(
+            s,
+            "    {{ \"{f}\", (void *)wasme_wamr_{m}_{f}, \"{sig}\", NULL }},",
+            m = m.name,
+            f = f.name,
+            sig = sig
+        )
+        .unwrap();
+    }
+    writeln!(s, "}};").unwrap();
+    writeln!(s).unwrap();
+
+    writeln!(s, "/// Register `{}` native symbols with WAMR,", m.name).unwrap();
+    writeln!(s, "/// dispatching to the `{}_drv_t` driver object in `binding`.", m.name).unwrap();
+    writeln!(s, "///").unwrap();
+    s.push_str(
+        "/// `symbols` is populated and registered as the native symbol table, so each\n\
+        /// binding requires its own table. WAMR resolves imports by module name across\n\
+        /// all registered tables when modules are loaded, so only one binding should be\n\
+        /// registered at a time (see `wasm_runtime_unregister_natives`).\n\
+        /// `symbols` and `binding` must remain valid while registered.\n",
+    );
+    let decl = format!("static inline bool wasme_wamr_register_{}(", m.name);
+    writeln!(s, "{}NativeSymbol symbols[{}],", decl, count).unwrap();
+    writeln!(s, "{:w$}const wasme_wamr_binding_t *binding)", "", w = decl.len()).unwrap();
+    writeln!(s, "{{").unwrap();
+    writeln!(
+        s,
+        "    return wasme_wamr_register(\"{m}\", symbols, wasme_wamr_{m}_symbols, {c},\n\
+        \x20                              binding);",
+        m = m.name,
+        c = count
+    )
+    .unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    host::postamble(&mut s);
+
+    s
+}
+
+/// WAMR native function fragments
+const RUNTIME: Runtime = Runtime {
+    name: "WAMR",
+    prefix: "wasme_wamr",
+    includes: &["<stdbool.h>", "<stdint.h>", "<string.h>"],
+    header: "\"wasm_export.h\"",
+    ptr: |off| format!("wasm_runtime_addr_app_to_native(inst, {})", off),
+    ret: |v| format!("return {};", v),
+};
+
+/// Emit the native function for an interface function, returning the WAMR signature
+fn func(s: &mut String, module: &str, f: &Func) -> String {
+    let args = host::args(f);
+
+    let mut params = vec!["wasm_exec_env_t exec_env".to_string()];
+    params.extend(args.iter().map(|a| format!("{} {}", a.ty, a.name)));
+
+    writeln!(s, "/// `{}.{}`, see {}.witx", module, f.name, module).unwrap();
+    writeln!(s, "static uint32_t wasme_wamr_{}_{}({})", module, f.name, params.join(", ")).unwrap();
+    writeln!(s, "{{").unwrap();
+
+    // Functions passing only scalars do not access guest memory
+    if host::uses_memory(f) {
+        writeln!(s, "    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);")
+            .unwrap();
+    }
+    writeln!(
+        s,
+        "    const wasme_wamr_binding_t *binding = \
+        (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);"
+    )
+    .unwrap();
+    host::body(s, &RUNTIME, module, f);
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    format!("({})i", args.iter().map(|a| a.sig).collect::<String>())
+}
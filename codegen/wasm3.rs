@@ -0,0 +1,167 @@
+//! wasm3 host binding generation.
+//!
+//! Emits a header per witx module containing an `m3ApiRawFunction` per interface function,
+//! which validates and translates guest arguments before dispatching to the C
+//! `<module>_drv_t` driver object, and a `wasme_wasm3_link_<module>` function to link
+//! these into a wasm3 module.
+
+use std::fmt::Write;
+
+use super::host::{self, Runtime};
+use super::{Func, ModuleSpec, Spec};
+
+/// Generate wasm3 headers for the provided specification, returning `(file name, contents)` pairs
+pub fn generate(spec: &Spec) -> Vec<(String, String)> {
+    let mut headers = vec![("wasm3/common.h".to_string(), common())];
+
+    for m in &spec.modules {
+        headers.push((format!("wasm3/{}.h", m.name), module(m)));
+    }
+
+    headers
+}
+
+/// Generate the common header, containing the driver binding and memory helpers
+fn common() -> String {
+    let mut s = String::new();
+
+    RUNTIME.preamble(&mut s, "host binding helpers", "build.rs", "common", &["\"../common.h\""]);
+
+    s.push_str(
+        r#"/// Maximum number of records in a list argument (such as I2C or SPI operations),
+/// longer lists are rejected with WASME_ERRNO_INVALID_ARG
+#ifndef WASME_WASM3_MAX_OPS
+#define WASME_WASM3_MAX_OPS 16
+#endif
+
+/// Driver binding, provided as userdata when linking functions
+typedef struct {
+    /// Driver object for the linked module (`gpio_drv_t` etc.)
+    const void *drv;
+    /// Platform context passed to driver functions
+    const void *ctx;
+} wasme_wasm3_binding_t;
+
+/// Trap if the guest memory region at `offset` is out of bounds, for use in raw functions
+#define WASME_WASM3_CHECK_MEM(offset, len)                                    \
+    if ((uint64_t)(offset) + (uint64_t)(len) > m3_GetMemorySize(runtime)) {   \
+        m3ApiTrap(m3Err_trapOutOfBoundsMemoryAccess);                         \
+    }
+
+/// Link a raw function, skipping functions not imported by the module
+static inline M3Result wasme_wasm3_link(IM3Module module, const char *module_name,
+                                        const char *name, const char *sig, M3RawCall call,
+                                        const wasme_wasm3_binding_t *binding)
+{
+    M3Result res = m3_LinkRawFunctionEx(module, module_name, name, sig, call, binding);
+    if (res == m3Err_functionLookupFailed) {
+        return m3Err_none;
+    }
+    return res;
+}
+
+"#,
+    );
+
+    host::postamble(&mut s);
+
+    s
+}
+
+/// Generate the header for a module
+fn module(m: &ModuleSpec) -> String {
+    let mut s = String::new();
+    let title = format!("{} host bindings", m.name.to_uppercase());
+    let source = format!("{}.witx", m.name);
+    let include = format!("\"../{}.h\"", m.name);
+
+    RUNTIME.preamble(&mut s, &title, &source, &m.name, &[&include, "\"common.h\""]);
+
+    let funcs: Vec<_> = m.funcs().map(|f| Func::new(&m.name, &f)).collect();
+
+    let mut sigs = vec![];
+    for f in &funcs {
+        sigs.push(func(&mut s, &m.name, f));
+    }
+
+    writeln!(s, "/// Link `{}` functions into a wasm3 module,", m.name).unwrap();
+    writeln!(s, "/// dispatching to the `{}_drv_t` driver object in `binding`.", m.name).unwrap();
+    writeln!(s, "///").unwrap();
+    writeln!(s, "/// Functions not imported by the module are skipped,").unwrap();
+    writeln!(s, "/// `binding` must remain valid for the lifetime of the runtime.").unwrap();
+    writeln!(
+        s,
+        "static inline M3Result wasme_wasm3_link_{}(IM3Module module, \
+        const wasme_wasm3_binding_t *binding)",
+        m.name
+    )
+    .unwrap();
+    writeln!(s, "{{").unwrap();
+    writeln!(s, "    M3Result res;").unwrap();
+    writeln!(s).unwrap();
+    for (f, sig) in funcs.iter().zip(&sigs) {
+        writeln!(
+            s,
+            "    res = wasme_wasm3_link(module, \"{m}\", \"{f}\", \"{sig}\", \
+            &wasme_wasm3_{m}_{f}, binding);",
+            m = m.name,
+            f = f.name,
+            sig = sig
+        )
+        .unwrap();
+        writeln!(s, "    if (res != m3Err_none) {{").unwrap();
+        writeln!(s, "        return res;").unwrap();
+        writeln!(s, "    }}").unwrap();
+        writeln!(s).unwrap();
+    }
+    writeln!(s, "    return m3Err_none;").unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    host::postamble(&mut s);
+
+    s
+}
+
+/// wasm3 raw function fragments
+const RUNTIME: Runtime = Runtime {
+    name: "wasm3",
+    prefix: "wasme_wasm3",
+    includes: &["<stdint.h>", "<string.h>"],
+    header: "\"wasm3.h\"",
+    ptr: |off| format!("m3ApiOffsetToPtr({})", off),
+    ret: |v| format!("m3ApiReturn({});", v),
+};
+
+/// Emit the raw function for an interface function, returning the wasm3 signature
+fn func(s: &mut String, module: &str, f: &Func) -> String {
+    let args = host::args(f);
+
+    writeln!(s, "/// `{}.{}`, see {}.witx", module, f.name, module).unwrap();
+    writeln!(s, "static m3ApiRawFunction(wasme_wasm3_{}_{})", module, f.name).unwrap();
+    writeln!(s, "{{").unwrap();
+    writeln!(s, "    m3ApiReturnType(uint32_t);").unwrap();
+    for a in &args {
+        writeln!(s, "    m3ApiGetArg({}, {});", a.ty, a.name).unwrap();
+    }
+    writeln!(s).unwrap();
+
+    // Functions passing only scalars do not access guest memory
+    if !host::uses_memory(f) {
+        writeln!(s, "    (void)runtime;").unwrap();
+        writeln!(s, "    (void)_mem;").unwrap();
+        writeln!(s).unwrap();
+    }
+
+    writeln!(
+        s,
+        "    const wasme_wasm3_binding_t *binding = \
+        (const wasme_wasm3_binding_t *)_ctx->userdata;"
+    )
+    .unwrap();
+    host::body(s, &RUNTIME, module, f);
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    format!("i({})", args.iter().map(|a| a.sig).collect::<String>())
+}
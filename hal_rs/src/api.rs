@@ -0,0 +1,159 @@
+//! Raw imports from the runtime, see the `.witx` files for definitions.
+//!
+//! Records are passed by pointer, lists as a pointer and length, and
+//! `expected` results return an errno with values written to a trailing out pointer.
+//!
+//! Function names are shared between modules, so imports are prefixed with their
+//! module and use `link_name` to keep the name defined in the specification.
+//! Imports are namespaced by module on `wasm32`, elsewhere (such as workspace builds on
+//! the host) the shared link names are reported as clashing though they are never linked.
+
+#![cfg_attr(not(target_arch = "wasm32"), allow(clashing_extern_declarations))]
+
+/// Buffer record, see `$rbytes` and `$wbytes` in `common.witx`
+#[repr(C)]
+pub(crate) struct Bytes {
+    pub ptr: *const u8,
+    pub len: u32,
+}
+
+impl Bytes {
+    pub fn new(d: &[u8]) -> Self {
+        Self { ptr: d.as_ptr(), len: d.len() as u32 }
+    }
+
+    pub fn new_mut(d: &mut [u8]) -> Self {
+        Self { ptr: d.as_mut_ptr(), len: d.len() as u32 }
+    }
+}
+
+/// GPIO configuration record, see `$gpio_cfg` in `gpio.witx`
+#[repr(C)]
+pub(crate) struct GpioCfg {
+    pub direction: u32,
+    pub pull: u32,
+    pub drive: u32,
+    pub strength: u32,
+    pub initial: u32,
+}
+
+/// Transaction operation record, see `$i2c_op` in `i2c.witx` and `$op` in `spi.witx`
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct Op {
+    pub kind: u32,
+    pub ptr: *const u8,
+    pub len: u32,
+}
+
+impl Op {
+    pub const EMPTY: Op = Op { kind: 0, ptr: core::ptr::null(), len: 0 };
+}
+
+pub(crate) mod gpio {
+    pub const MODE_INPUT: u32 = 0;
+    pub const MODE_OUTPUT: u32 = 1;
+
+    pub const VALUE_LOW: u32 = 0;
+    pub const VALUE_HIGH: u32 = 1;
+
+    #[link(wasm_import_module = "gpio")]
+    extern "C" {
+        #[link_name = "init"]
+        pub fn gpio_init(port: i32, pin: i32, cfg: *const super::GpioCfg, handle: *mut i32) -> i32;
+        #[link_name = "deinit"]
+        pub fn gpio_deinit(handle: i32) -> i32;
+        #[link_name = "set"]
+        pub fn gpio_set(handle: i32, value: u32) -> i32;
+        #[link_name = "get"]
+        pub fn gpio_get(handle: i32, value: *mut u32) -> i32;
+    }
+}
+
+pub(crate) mod i2c {
+    pub const OP_READ: u32 = 0;
+    pub const OP_WRITE: u32 = 1;
+
+    #[link(wasm_import_module = "i2c")]
+    extern "C" {
+        #[link_name = "init"]
+        pub fn i2c_init(port: u32, baud: u32, sda: i32, scl: i32, handle: *mut i32) -> i32;
+        #[link_name = "deinit"]
+        pub fn i2c_deinit(handle: i32) -> i32;
+        #[link_name = "write"]
+        pub fn i2c_write(handle: i32, addr: u16, data: *const super::Bytes) -> i32;
+        #[link_name = "read"]
+        pub fn i2c_read(handle: i32, addr: u16, buff: *const super::Bytes) -> i32;
+        #[link_name = "write_read"]
+        pub fn i2c_write_read(
+            handle: i32,
+            addr: u16,
+            data: *const super::Bytes,
+            buff: *const super::Bytes,
+        ) -> i32;
+        #[link_name = "transaction"]
+        pub fn i2c_transaction(handle: i32, addr: u16, ops: *const super::Op, len: u32) -> i32;
+    }
+}
+
+pub(crate) mod spi {
+    pub const OP_TRANSFER: u32 = 0;
+    pub const OP_READ: u32 = 1;
+    pub const OP_WRITE: u32 = 2;
+
+    #[link(wasm_import_module = "spi")]
+    extern "C" {
+        #[link_name = "init"]
+        pub fn spi_init(
+            port: u32,
+            baud: u32,
+            mosi: i32,
+            miso: i32,
+            sck: i32,
+            cs: i32,
+            handle: *mut i32,
+        ) -> i32;
+        #[link_name = "deinit"]
+        pub fn spi_deinit(handle: i32) -> i32;
+        #[link_name = "read"]
+        pub fn spi_read(handle: i32, buff: *const super::Bytes) -> i32;
+        #[link_name = "write"]
+        pub fn spi_write(handle: i32, data: *const super::Bytes) -> i32;
+        #[link_name = "transfer"]
+        pub fn spi_transfer(
+            handle: i32,
+            read: *const super::Bytes,
+            write: *const super::Bytes,
+        ) -> i32;
+        #[link_name = "transfer_inplace"]
+        pub fn spi_transfer_inplace(handle: i32, data: *const super::Bytes) -> i32;
+        #[link_name = "exec"]
+        pub fn spi_exec(handle: i32, ops: *const super::Op, len: u32) -> i32;
+    }
+}
+
+pub(crate) mod uart {
+    #[link(wasm_import_module = "uart")]
+    extern "C" {
+        #[link_name = "init"]
+        pub fn uart_init(port: u32, baud: u32, tx: i32, rx: i32, handle: *mut i32) -> i32;
+        #[link_name = "deinit"]
+        pub fn uart_deinit(handle: i32) -> i32;
+        #[link_name = "write"]
+        pub fn uart_write(
+            handle: i32,
+            flags: u32,
+            timeout_ms: u32,
+            data: *const super::Bytes,
+            len: *mut u32,
+        ) -> i32;
+        #[link_name = "read"]
+        pub fn uart_read(
+            handle: i32,
+            flags: u32,
+            timeout_ms: u32,
+            buff: *const super::Bytes,
+            len: *mut u32,
+        ) -> i32;
+    }
+}
@@ -0,0 +1,126 @@
+//! GPIO pins over the `gpio` imports
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin, PinState};
+
+use crate::api::{self, GpioCfg};
+use crate::Error;
+
+/// GPIO pull resistor, see `$pull` in `gpio.witx`
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Pull {
+    #[default]
+    None,
+    Up,
+    Down,
+}
+
+fn init(port: i32, pin: i32, cfg: GpioCfg) -> Result<i32, Error> {
+    let mut handle = 0;
+    Error::check(unsafe { api::gpio::gpio_init(port, pin, &cfg, &mut handle) })?;
+    Ok(handle)
+}
+
+/// GPIO input pin, deinitialised on drop
+#[derive(Debug)]
+pub struct Input {
+    handle: i32,
+}
+
+impl Input {
+    /// Initialise a pin as an input
+    pub fn new(port: i32, pin: i32, pull: Pull) -> Result<Self, Error> {
+        let cfg = GpioCfg {
+            direction: api::gpio::MODE_INPUT,
+            pull: pull as u32,
+            drive: 0,
+            strength: 0,
+            initial: api::gpio::VALUE_LOW,
+        };
+
+        Ok(Self { handle: init(port, pin, cfg)? })
+    }
+
+    /// Fetch the runtime handle for this pin
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+}
+
+impl ErrorType for Input {
+    type Error = Error;
+}
+
+impl InputPin for Input {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let mut value = 0;
+        Error::check(unsafe { api::gpio::gpio_get(self.handle, &mut value) })?;
+        Ok(value == api::gpio::VALUE_HIGH)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|v| !v)
+    }
+}
+
+impl Drop for Input {
+    fn drop(&mut self) {
+        let _ = unsafe { api::gpio::gpio_deinit(self.handle) };
+    }
+}
+
+/// GPIO output pin, deinitialised on drop
+#[derive(Debug)]
+pub struct Output {
+    handle: i32,
+}
+
+impl Output {
+    /// Initialise a pin as a push-pull output with the provided initial state
+    pub fn new(port: i32, pin: i32, initial: PinState) -> Result<Self, Error> {
+        let cfg = GpioCfg {
+            direction: api::gpio::MODE_OUTPUT,
+            pull: Pull::None as u32,
+            drive: 0,
+            strength: 0,
+            initial: value(initial),
+        };
+
+        Ok(Self { handle: init(port, pin, cfg)? })
+    }
+
+    /// Fetch the runtime handle for this pin
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+}
+
+fn value(state: PinState) -> u32 {
+    match state {
+        PinState::Low => api::gpio::VALUE_LOW,
+        PinState::High => api::gpio::VALUE_HIGH,
+    }
+}
+
+impl ErrorType for Output {
+    type Error = Error;
+}
+
+impl OutputPin for Output {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_state(PinState::Low)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_state(PinState::High)
+    }
+
+    fn set_state(&mut self, state: PinState) -> Result<(), Self::Error> {
+        Error::check(unsafe { api::gpio::gpio_set(self.handle, value(state)) })
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        let _ = unsafe { api::gpio::gpio_deinit(self.handle) };
+    }
+}
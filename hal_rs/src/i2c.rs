@@ -0,0 +1,89 @@
+//! I2C bus over the `i2c` imports
+
+use embedded_hal::i2c::{ErrorType, Operation, SevenBitAddress};
+
+use crate::api::{self, Bytes, Op};
+use crate::Error;
+
+/// Maximum number of operations in a single [I2c] transaction
+pub const MAX_I2C_OPS: usize = 16;
+
+/// I2C bus, deinitialised on drop
+#[derive(Debug)]
+pub struct I2c {
+    handle: i32,
+}
+
+impl I2c {
+    /// Initialise an I2C port, pins may be -1 to use platform defaults
+    pub fn new(port: u32, baud: u32, sda: i32, scl: i32) -> Result<Self, Error> {
+        let mut handle = 0;
+        Error::check(unsafe { api::i2c::i2c_init(port, baud, sda, scl, &mut handle) })?;
+        Ok(Self { handle })
+    }
+
+    /// Fetch the runtime handle for this bus
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+}
+
+impl ErrorType for I2c {
+    type Error = Error;
+}
+
+impl embedded_hal::i2c::I2c<SevenBitAddress> for I2c {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        let buff = Bytes::new_mut(read);
+        Error::check(unsafe { api::i2c::i2c_read(self.handle, address as u16, &buff) })
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        let data = Bytes::new(write);
+        Error::check(unsafe { api::i2c::i2c_write(self.handle, address as u16, &data) })
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let data = Bytes::new(write);
+        let buff = Bytes::new_mut(read);
+        Error::check(unsafe { api::i2c::i2c_write_read(self.handle, address as u16, &data, &buff) })
+    }
+
+    /// Execute a transaction of up to [MAX_I2C_OPS] operations,
+    /// returning [Error::InvalidArg] for longer transactions
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if operations.len() > MAX_I2C_OPS {
+            return Err(Error::InvalidArg);
+        }
+
+        let mut ops = [Op::EMPTY; MAX_I2C_OPS];
+        for (o, op) in ops.iter_mut().zip(operations.iter_mut()) {
+            *o = match op {
+                Operation::Read(b) => {
+                    Op { kind: api::i2c::OP_READ, ptr: b.as_mut_ptr(), len: b.len() as u32 }
+                }
+                Operation::Write(d) => {
+                    Op { kind: api::i2c::OP_WRITE, ptr: d.as_ptr(), len: d.len() as u32 }
+                }
+            };
+        }
+
+        let (addr, len) = (address as u16, operations.len() as u32);
+        Error::check(unsafe { api::i2c::i2c_transaction(self.handle, addr, ops.as_ptr(), len) })
+    }
+}
+
+impl Drop for I2c {
+    fn drop(&mut self) {
+        let _ = unsafe { api::i2c::i2c_deinit(self.handle) };
+    }
+}
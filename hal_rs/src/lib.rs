@@ -0,0 +1,149 @@
+//! Embedded WASM guest HAL.
+//!
+//! Implements the embedded-hal 1.0 and embedded-io traits for guest applications
+//! (`wasm32-unknown-unknown` or `wasm32-wasi`) over the imports defined in the
+//! specification `.witx` files, so existing drivers can be used unmodified within a runtime.
+//!
+//! Peripherals are initialised on construction and deinitialised on drop.
+//!
+//! ```ignore
+//! use embedded_hal::digital::OutputPin;
+//! use wasm_embedded_hal::{Output, PinState};
+//!
+//! let mut led = Output::new(0, 25, PinState::Low).unwrap();
+//! led.set_high().unwrap();
+//! ```
+//!
+//! https://github.com/embedded-wasm/spec
+
+#![no_std]
+
+mod api;
+
+mod gpio;
+pub use gpio::{Input, Output, Pull};
+
+mod i2c;
+pub use i2c::{I2c, MAX_I2C_OPS};
+
+mod spi;
+pub use spi::{Spi, MAX_SPI_OPS};
+
+mod uart;
+pub use uart::{Uart, UartFlags};
+
+pub use embedded_hal::digital::PinState;
+
+/// Guest HAL errors, see `$errno` in `common.witx`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Error {
+    InvalidArg,
+    Failed,
+    NoDevice,
+    Unexpected,
+    Unsupported,
+    Timeout,
+    /// No acknowledgement received (I2C address or data NACK)
+    NoAcknowledge,
+    /// Bus arbitration lost
+    ArbitrationLoss,
+    /// Bus error, such as a misplaced start or stop condition
+    Bus,
+    /// Receive data overrun
+    Overrun,
+    /// SPI mode fault
+    ModeFault,
+    /// Frame format error (SPI frame or UART framing)
+    FrameFormat,
+    /// SPI chip select fault
+    ChipSelectFault,
+    /// UART parity error
+    Parity,
+    /// UART noise detected
+    Noise,
+    /// Errno not known to this version of the specification
+    Unknown(i32),
+}
+
+impl Error {
+    /// Convert a result code returned by an import to a result
+    pub(crate) fn check(errno: i32) -> Result<(), Error> {
+        let e = match errno {
+            0 => return Ok(()),
+            1 => Error::InvalidArg,
+            2 => Error::Failed,
+            3 => Error::NoDevice,
+            4 => Error::Unexpected,
+            5 => Error::Unsupported,
+            6 => Error::Timeout,
+            7 => Error::NoAcknowledge,
+            8 => Error::ArbitrationLoss,
+            9 => Error::Bus,
+            10 => Error::Overrun,
+            11 => Error::ModeFault,
+            12 => Error::FrameFormat,
+            13 => Error::ChipSelectFault,
+            14 => Error::Parity,
+            15 => Error::Noise,
+            _ => Error::Unknown(errno),
+        };
+        Err(e)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Unknown(errno) => write!(f, "unknown errno {}", errno),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl embedded_hal::digital::Error for Error {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+impl embedded_hal::i2c::Error for Error {
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+        match self {
+            Error::NoAcknowledge => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            Error::ArbitrationLoss => ErrorKind::ArbitrationLoss,
+            Error::Bus => ErrorKind::Bus,
+            Error::Overrun => ErrorKind::Overrun,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl embedded_hal::spi::Error for Error {
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        use embedded_hal::spi::ErrorKind;
+
+        match self {
+            Error::Overrun => ErrorKind::Overrun,
+            Error::ModeFault => ErrorKind::ModeFault,
+            Error::FrameFormat => ErrorKind::FrameFormat,
+            Error::ChipSelectFault => ErrorKind::ChipSelectFault,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        use embedded_io::ErrorKind;
+
+        match self {
+            Error::Timeout => ErrorKind::TimedOut,
+            Error::Unsupported => ErrorKind::Unsupported,
+            Error::InvalidArg => ErrorKind::InvalidInput,
+            Error::NoDevice => ErrorKind::NotFound,
+            _ => ErrorKind::Other,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! SPI bus and device over the `spi` imports
+
+use embedded_hal::spi::{ErrorType, Operation, SpiBus, SpiDevice};
+
+use crate::api::{self, Bytes, Op};
+use crate::Error;
+
+/// Maximum number of operations in a single [SpiDevice] transaction
+pub const MAX_SPI_OPS: usize = 16;
+
+/// SPI port, deinitialised on drop.
+///
+/// Implements [SpiBus] for direct bus use (with `cs` set to -1), and [SpiDevice] with
+/// chip select managed by the runtime for the duration of each transaction.
+#[derive(Debug)]
+pub struct Spi {
+    handle: i32,
+}
+
+impl Spi {
+    /// Initialise an SPI port, pins may be -1 to use platform defaults
+    /// (or no chip select in the case of `cs`)
+    pub fn new(
+        port: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<Self, Error> {
+        let mut handle = 0;
+        Error::check(unsafe { api::spi::spi_init(port, baud, mosi, miso, sck, cs, &mut handle) })?;
+        Ok(Self { handle })
+    }
+
+    /// Fetch the runtime handle for this port
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+}
+
+impl ErrorType for Spi {
+    type Error = Error;
+}
+
+impl SpiBus for Spi {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        let buff = Bytes::new_mut(words);
+        Error::check(unsafe { api::spi::spi_read(self.handle, &buff) })
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let data = Bytes::new(words);
+        Error::check(unsafe { api::spi::spi_write(self.handle, &data) })
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let buff = Bytes::new_mut(read);
+        let data = Bytes::new(write);
+        Error::check(unsafe { api::spi::spi_transfer(self.handle, &buff, &data) })
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        let data = Bytes::new_mut(words);
+        Error::check(unsafe { api::spi::spi_transfer_inplace(self.handle, &data) })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl SpiDevice for Spi {
+    /// Execute a transaction of up to [MAX_SPI_OPS] operations.
+    ///
+    /// Transfers with separate read and write buffers and delays cannot be represented
+    /// in `spi.exec` and return [Error::Unsupported], longer transactions [Error::InvalidArg]
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        if operations.len() > MAX_SPI_OPS {
+            return Err(Error::InvalidArg);
+        }
+
+        let mut ops = [Op::EMPTY; MAX_SPI_OPS];
+        for (o, op) in ops.iter_mut().zip(operations.iter_mut()) {
+            *o = match op {
+                Operation::Read(b) => {
+                    Op { kind: api::spi::OP_READ, ptr: b.as_mut_ptr(), len: b.len() as u32 }
+                }
+                Operation::Write(d) => {
+                    Op { kind: api::spi::OP_WRITE, ptr: d.as_ptr(), len: d.len() as u32 }
+                }
+                Operation::TransferInPlace(b) => {
+                    Op { kind: api::spi::OP_TRANSFER, ptr: b.as_mut_ptr(), len: b.len() as u32 }
+                }
+                Operation::Transfer(..) | Operation::DelayNs(_) => {
+                    return Err(Error::Unsupported)
+                }
+            };
+        }
+
+        let len = operations.len() as u32;
+        Error::check(unsafe { api::spi::spi_exec(self.handle, ops.as_ptr(), len) })
+    }
+}
+
+impl Drop for Spi {
+    fn drop(&mut self) {
+        let _ = unsafe { api::spi::spi_deinit(self.handle) };
+    }
+}
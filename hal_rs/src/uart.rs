@@ -0,0 +1,113 @@
+//! UART over the `uart` imports
+
+use embedded_io::{ErrorType, Read, Write};
+
+use crate::api::{self, Bytes};
+use crate::Error;
+
+/// UART read / write operation flags, see `$uart_flags` in `uart.witx`
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct UartFlags(pub u32);
+
+impl UartFlags {
+    /// Block until the full buffer has been transferred
+    pub const BLOCKING: UartFlags = UartFlags(0);
+    /// Return immediately with the data that could be transferred, which may be none
+    pub const NON_BLOCKING: UartFlags = UartFlags(1 << 0);
+    /// Return once the buffer is complete or the provided timeout has elapsed
+    pub const TIMEOUT: UartFlags = UartFlags(1 << 1);
+    /// Return reads once data has been received and the line goes idle
+    pub const READ_UNTIL_IDLE: UartFlags = UartFlags(1 << 2);
+}
+
+impl core::ops::BitOr for UartFlags {
+    type Output = UartFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        UartFlags(self.0 | rhs.0)
+    }
+}
+
+/// UART port, deinitialised on drop.
+///
+/// [Read] blocks until data is received then returns once the line goes idle,
+/// [Write] blocks until the full buffer has been written,
+/// see [Uart::read_with] and [Uart::write_with] for other modes.
+#[derive(Debug)]
+pub struct Uart {
+    handle: i32,
+}
+
+impl Uart {
+    /// Initialise a UART port, pins may be -1 to use platform defaults
+    pub fn new(port: u32, baud: u32, tx: i32, rx: i32) -> Result<Self, Error> {
+        let mut handle = 0;
+        Error::check(unsafe { api::uart::uart_init(port, baud, tx, rx, &mut handle) })?;
+        Ok(Self { handle })
+    }
+
+    /// Fetch the runtime handle for this port
+    pub fn handle(&self) -> i32 {
+        self.handle
+    }
+
+    /// Read into the provided buffer with the specified flags, returning the number of bytes read
+    pub fn read_with(
+        &mut self,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        let b = Bytes::new_mut(buff);
+        let mut n = 0;
+        Error::check(unsafe {
+            api::uart::uart_read(self.handle, flags.0, timeout_ms, &b, &mut n)
+        })?;
+        Ok(n as usize)
+    }
+
+    /// Write the provided data with the specified flags, returning the number of bytes written
+    pub fn write_with(
+        &mut self,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        let d = Bytes::new(data);
+        let mut n = 0;
+        Error::check(unsafe {
+            api::uart::uart_write(self.handle, flags.0, timeout_ms, &d, &mut n)
+        })?;
+        Ok(n as usize)
+    }
+}
+
+impl ErrorType for Uart {
+    type Error = Error;
+}
+
+impl Read for Uart {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // Return what has been received rather than waiting to fill the buffer
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        self.read_with(UartFlags::READ_UNTIL_IDLE, 0, buf)
+    }
+}
+
+impl Write for Uart {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.write_with(UartFlags::BLOCKING, 0, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Drop for Uart {
+    fn drop(&mut self) {
+        let _ = unsafe { api::uart::uart_deinit(self.handle) };
+    }
+}
@@ -0,0 +1,66 @@
+/// Embedded WASM abstract C ADC platform interface
+///
+/// Generated from adc.witx by build.rs, do not edit
+
+#ifndef WASME_ADC_DRIVER_H
+#define WASME_ADC_DRIVER_H
+
+#include <stdint.h>
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// ADC voltage reference
+///
+/// See `$adc_reference` in adc.witx
+typedef enum {
+    /// Platform default reference
+    ADC_REFERENCE_DEFAULT = 0,
+    /// Internal reference
+    ADC_REFERENCE_INTERNAL = 1,
+    /// External reference pin
+    ADC_REFERENCE_EXTERNAL = 2,
+    /// Supply voltage
+    ADC_REFERENCE_SUPPLY = 3,
+} adc_reference_e;
+
+/// `adc.init`, see adc.witx
+/// - port: ADC device to init
+/// - channel: ADC channel
+/// - pin: ADC pin, -1 for channel default
+/// - resolution: Sample resolution in bits
+/// - reference: Voltage reference (adc_reference_e)
+/// Returns a device handle or error
+typedef int32_t adc_init_f(const void *ctx, uint32_t port, uint32_t channel, int32_t pin, uint32_t resolution, uint32_t reference);
+
+/// `adc.deinit`, see adc.witx
+/// - handle: ADC channel handle to deinit
+typedef int32_t adc_deinit_f(const void *ctx, int32_t handle);
+
+/// `adc.read`, see adc.witx
+/// - handle: ADC channel handle for read
+/// - adc_sample: Returns a single sample or error
+typedef int32_t adc_read_f(const void *ctx, int32_t handle, uint16_t *adc_sample);
+
+/// `adc.read_multi`, see adc.witx
+/// - handle: ADC channel handle for read
+/// - rate_hz: Sample rate in Hz, 0 for as fast as possible
+/// - buff: Buffer to read samples into
+typedef int32_t adc_read_multi_f(const void *ctx, int32_t handle, uint32_t rate_hz, uint16_t *buff, uint32_t buff_len);
+
+/// C platform ADC driver object
+typedef struct {
+    adc_init_f *init;
+    adc_deinit_f *deinit;
+    adc_read_f *read;
+    adc_read_multi_f *read_multi;
+} adc_drv_t;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,53 @@
+/// Embedded WASM abstract C common definitions
+///
+/// Generated from common.witx by build.rs, do not edit
+
+#ifndef WASME_COMMON_DRIVER_H
+#define WASME_COMMON_DRIVER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// Functions return zero (or a non-negative handle or count where
+/// documented) on success, or a negated wasme_errno_e on failure
+
+/// Common error number type
+///
+/// See `$errno` in common.witx
+typedef enum {
+    WASME_ERRNO_OK = 0,
+    WASME_ERRNO_INVALID_ARG = 1,
+    WASME_ERRNO_FAILED = 2,
+    WASME_ERRNO_NO_DEVICE = 3,
+    WASME_ERRNO_UNEXPECTED = 4,
+    WASME_ERRNO_UNSUPPORTED = 5,
+    WASME_ERRNO_TIMEOUT = 6,
+    /// No acknowledgement received (I2C address or data NACK)
+    WASME_ERRNO_NO_ACKNOWLEDGE = 7,
+    /// Bus arbitration lost
+    WASME_ERRNO_ARBITRATION_LOSS = 8,
+    /// Bus error, such as a misplaced start or stop condition
+    WASME_ERRNO_BUS = 9,
+    /// Receive data overrun
+    WASME_ERRNO_OVERRUN = 10,
+    /// SPI mode fault
+    WASME_ERRNO_MODE_FAULT = 11,
+    /// Frame format error (SPI frame or UART framing)
+    WASME_ERRNO_FRAME_FORMAT = 12,
+    /// SPI chip select fault
+    WASME_ERRNO_CHIP_SELECT_FAULT = 13,
+    /// UART parity error
+    WASME_ERRNO_PARITY = 14,
+    /// UART noise detected
+    WASME_ERRNO_NOISE = 15,
+} wasme_errno_e;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
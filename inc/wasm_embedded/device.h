@@ -0,0 +1,48 @@
+/// Embedded WASM abstract C DEVICE platform interface
+///
+/// Generated from device.witx by build.rs, do not edit
+
+#ifndef WASME_DEVICE_DRIVER_H
+#define WASME_DEVICE_DRIVER_H
+
+#include <stdint.h>
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `device.spi`, see device.witx
+/// - spi: SPI array to fill
+/// Returns the number of SPI handles available or error
+typedef int32_t device_spi_f(const void *ctx, int32_t *spi, uint32_t spi_len);
+
+/// `device.i2c`, see device.witx
+/// - i2c: I2C array to fill
+/// Returns the number of I2C handles available or error
+typedef int32_t device_i2c_f(const void *ctx, int32_t *i2c, uint32_t i2c_len);
+
+/// `device.gpio_in`, see device.witx
+/// - gpio_in: GPIO input array to fill
+/// Returns the number of GPIO input handles available or error
+typedef int32_t device_gpio_in_f(const void *ctx, int32_t *gpio_in, uint32_t gpio_in_len);
+
+/// `device.gpio_out`, see device.witx
+/// - gpio_out: GPIO output array to fill
+/// Returns the number of GPIO output handles available or error
+typedef int32_t device_gpio_out_f(const void *ctx, int32_t *gpio_out, uint32_t gpio_out_len);
+
+/// C platform DEVICE driver object
+typedef struct {
+    device_spi_f *spi;
+    device_i2c_f *i2c;
+    device_gpio_in_f *gpio_in;
+    device_gpio_out_f *gpio_out;
+} device_drv_t;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -1,27 +1,128 @@
 /// Embedded WASM abstract C GPIO platform interface
+///
+/// Generated from gpio.witx by build.rs, do not edit
 
 #ifndef WASME_GPIO_DRIVER_H
 #define WASME_GPIO_DRIVER_H
 
 #include <stdint.h>
+#include "common.h"
 
 #ifdef __cplusplus
 extern "C"
 {
 #endif
 
-/// Init, takes device index and config and returns handle (or error)
-typedef int32_t gpio_init_f(const void *ctx, int32_t port, int32_t pin, uint32_t mode);
+/// See `$mode` in gpio.witx
+typedef enum {
+    GPIO_MODE_INPUT = 0,
+    GPIO_MODE_OUTPUT = 1,
+} gpio_mode_e;
 
-/// Deinit, takes device handle and deinitialises
-typedef int32_t gpio_deinit_f(const void *ctx, int32_t handle);
+/// See `$value` in gpio.witx
+typedef enum {
+    GPIO_VALUE_LOW = 0,
+    GPIO_VALUE_HIGH = 1,
+} gpio_value_e;
 
-/// Write, takes device handle, writes data_out to address
-typedef int32_t gpio_set_f(const void *ctx, int32_t handle, uint32_t value);
+/// GPIO pull resistor
+///
+/// See `$pull` in gpio.witx
+typedef enum {
+    GPIO_PULL_NONE = 0,
+    GPIO_PULL_UP = 1,
+    GPIO_PULL_DOWN = 2,
+} gpio_pull_e;
 
-/// Read, takes device handle, reads data_in from address
-typedef int32_t gpio_get_f(const void *ctx, int32_t handle, uint32_t* value);
+/// GPIO output drive mode
+///
+/// See `$drive` in gpio.witx
+typedef enum {
+    GPIO_DRIVE_PUSH_PULL = 0,
+    GPIO_DRIVE_OPEN_DRAIN = 1,
+} gpio_drive_e;
 
+/// GPIO output drive strength, platforms may round to the nearest supported value
+///
+/// See `$drive_strength` in gpio.witx
+typedef enum {
+    GPIO_DRIVE_STRENGTH_DEFAULT = 0,
+    GPIO_DRIVE_STRENGTH_LOW = 1,
+    GPIO_DRIVE_STRENGTH_MEDIUM = 2,
+    GPIO_DRIVE_STRENGTH_HIGH = 3,
+} gpio_drive_strength_e;
+
+/// GPIO pin configuration
+///
+/// See `$gpio_cfg` in gpio.witx
+typedef struct {
+    /// Pin direction, gpio_mode_e
+    uint32_t direction;
+    /// Pull resistor, gpio_pull_e
+    uint32_t pull;
+    /// Output drive mode, ignored for inputs, gpio_drive_e
+    uint32_t drive;
+    /// Output drive strength, ignored for inputs, gpio_drive_strength_e
+    uint32_t strength;
+    /// Initial output level, ignored for inputs, gpio_value_e
+    uint32_t initial;
+} gpio_cfg_t;
+
+/// GPIO interrupt edge
+///
+/// See `$edge` in gpio.witx
+typedef enum {
+    /// No edge, disables interrupts
+    GPIO_EDGE_NONE = 0,
+    /// Rising (low to high) edge
+    GPIO_EDGE_RISING = 1,
+    /// Falling (high to low) edge
+    GPIO_EDGE_FALLING = 2,
+    /// Either edge
+    GPIO_EDGE_BOTH = 3,
+} gpio_edge_e;
+
+/// GPIO edge event
+///
+/// See `$gpio_event` in gpio.witx
+typedef struct {
+    /// GPIO pin handle
+    int32_t handle;
+    /// Edge that occurred, gpio_edge_e
+    uint32_t edge;
+} gpio_event_t;
+
+/// `gpio.init`, see gpio.witx
+/// - port: GPIO Port
+/// - pin: GPIO Pin
+/// - cfg: GPIO configuration
+/// Returns a device handle or error
+typedef int32_t gpio_init_f(const void *ctx, int32_t port, int32_t pin, const gpio_cfg_t *cfg);
+
+/// `gpio.deinit`, see gpio.witx
+/// - dev: GPIO pin handle
+typedef int32_t gpio_deinit_f(const void *ctx, int32_t dev);
+
+/// `gpio.set`, see gpio.witx
+/// - dev: GPIO pin handle
+/// - value: Value to write (gpio_value_e)
+typedef int32_t gpio_set_f(const void *ctx, int32_t dev, uint32_t value);
+
+/// `gpio.get`, see gpio.witx
+/// - dev: GPIO pin handle
+/// - value: Result
+typedef int32_t gpio_get_f(const void *ctx, int32_t dev, uint32_t *value);
+
+/// `gpio.set_interrupt`, see gpio.witx
+/// - dev: GPIO pin handle
+/// - edge: Edge to generate events on (gpio_edge_e)
+typedef int32_t gpio_set_interrupt_f(const void *ctx, int32_t dev, uint32_t edge);
+
+/// `gpio.wait_event`, see gpio.witx
+/// - handles: GPIO pin handles to wait on
+/// - timeout_us: Timeout in microseconds, 0 to poll and 0xffffffff to wait forever
+/// - event: Returns the first pending event or timeout error
+typedef int32_t gpio_wait_event_f(const void *ctx, int32_t *handles, uint32_t handles_len, uint32_t timeout_us, gpio_event_t *event);
 
 /// C platform GPIO driver object
 typedef struct {
@@ -29,6 +130,12 @@ typedef struct {
     gpio_deinit_f *deinit;
     gpio_set_f *set;
     gpio_get_f *get;
+    gpio_set_interrupt_f *set_interrupt;
+    gpio_wait_event_f *wait_event;
 } gpio_drv_t;
 
+#ifdef __cplusplus
+}
+#endif
+
 #endif
@@ -1,31 +1,76 @@
 /// Embedded WASM abstract C I2C platform interface
+///
+/// Generated from i2c.witx by build.rs, do not edit
 
 #ifndef WASME_I2C_DRIVER_H
 #define WASME_I2C_DRIVER_H
 
 #include <stdint.h>
+#include "common.h"
 
 #ifdef __cplusplus
 extern "C"
 {
 #endif
 
-/// Init, takes device index and config and returns handle (or error)
-typedef int32_t i2c_init_f(const void *ctx, uint32_t dev, uint32_t baud, int32_t sda, int32_t scl);
+/// Operation kinds for transactional I2C
+///
+/// See `$i2c_op_kind` in i2c.witx
+typedef enum {
+    /// Read into the buffer
+    I2C_OP_KIND_READ = 0,
+    /// Write out the buffer
+    I2C_OP_KIND_WRITE = 1,
+} i2c_op_kind_e;
 
-/// Deinit, takes device handle and deinitialises
+/// Operation for transactional I2C
+///
+/// See `$i2c_op` in i2c.witx
+typedef struct {
+    /// Kind of operation, i2c_op_kind_e
+    uint32_t kind;
+    /// The address of the operation buffer, read buffers must not overlap
+    uint8_t *ptr;
+    /// The length of the operation buffer
+    uint32_t len;
+} i2c_op_t;
+
+/// `i2c.init`, see i2c.witx
+/// - port: I2C device to init
+/// - baud: Baud rate
+/// - sda
+/// - scl
+/// Returns a device handle or error
+typedef int32_t i2c_init_f(const void *ctx, uint32_t port, uint32_t baud, int32_t sda, int32_t scl);
+
+/// `i2c.deinit`, see i2c.witx
+/// - handle: I2C device handle to deinit
 typedef int32_t i2c_deinit_f(const void *ctx, int32_t handle);
 
-/// Write, takes device handle, writes data_out to address
-typedef int32_t i2c_write_f(const void *ctx, int32_t handle, uint16_t address, uint8_t *data_out, uint32_t length_out);
+/// `i2c.write`, see i2c.witx
+/// - handle: I2C device handle for write
+/// - addr: Peripheral address
+/// - data: Data to write
+typedef int32_t i2c_write_f(const void *ctx, int32_t handle, uint16_t addr, uint8_t *data, uint32_t data_len);
+
+/// `i2c.read`, see i2c.witx
+/// - handle: I2C device handle for transfer
+/// - addr: Peripheral address
+/// - buff: Buffer to read into
+typedef int32_t i2c_read_f(const void *ctx, int32_t handle, uint16_t addr, uint8_t *buff, uint32_t buff_len);
 
-/// Read, takes device handle, reads data_in from address
-typedef int32_t i2c_read_f(const void *ctx, int32_t handle, uint16_t address, uint8_t *data_in, uint32_t length_in);
+/// `i2c.write_read`, see i2c.witx
+/// - handle: I2C device handle for exec
+/// - addr: Peripheral address
+/// - data: Data to write
+/// - buff: Buffer to read into
+typedef int32_t i2c_write_read_f(const void *ctx, int32_t handle, uint16_t addr, uint8_t *data, uint32_t data_len, uint8_t *buff, uint32_t buff_len);
 
-/// WriteRead, takes device handle, writes data_out then reads data_in from address
-typedef int32_t i2c_write_read_f(const void *ctx, int32_t handle, uint16_t address,
-                                uint8_t *data_out, uint32_t length_out,
-                                uint8_t *data_in, uint32_t length_in);
+/// `i2c.transaction`, see i2c.witx
+/// - handle: I2C device handle for transaction
+/// - addr: Peripheral address
+/// - ops: List of operations to execute, with repeated starts between operations
+typedef int32_t i2c_transaction_f(const void *ctx, int32_t handle, uint16_t addr, i2c_op_t *ops, uint32_t ops_len);
 
 /// C platform I2C driver object
 typedef struct {
@@ -34,6 +79,7 @@ typedef struct {
     i2c_write_f *write;
     i2c_read_f *read;
     i2c_write_read_f *write_read;
+    i2c_transaction_f *transaction;
 } i2c_drv_t;
 
 #ifdef __cplusplus
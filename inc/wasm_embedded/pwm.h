@@ -0,0 +1,66 @@
+/// Embedded WASM abstract C PWM platform interface
+///
+/// Generated from pwm.witx by build.rs, do not edit
+
+#ifndef WASME_PWM_DRIVER_H
+#define WASME_PWM_DRIVER_H
+
+#include <stdint.h>
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `pwm.init`, see pwm.witx
+/// - port: PWM timer to init
+/// - channel: PWM timer channel
+/// - pin: PWM output pin, -1 for channel default
+/// - freq_hz: PWM frequency in Hz
+/// Returns a device handle or error
+typedef int32_t pwm_init_f(const void *ctx, uint32_t port, uint32_t channel, int32_t pin, uint32_t freq_hz);
+
+/// `pwm.deinit`, see pwm.witx
+/// - handle: PWM channel handle to deinit
+typedef int32_t pwm_deinit_f(const void *ctx, int32_t handle);
+
+/// `pwm.set_duty`, see pwm.witx
+/// - handle: PWM channel handle
+/// - duty: Duty cycle, from 0 to the maximum duty
+typedef int32_t pwm_set_duty_f(const void *ctx, int32_t handle, uint32_t duty);
+
+/// `pwm.set_frequency`, see pwm.witx
+/// - handle: PWM channel handle
+/// - freq_hz: PWM frequency in Hz, this may change the maximum duty
+typedef int32_t pwm_set_frequency_f(const void *ctx, int32_t handle, uint32_t freq_hz);
+
+/// `pwm.enable`, see pwm.witx
+/// - handle: PWM channel handle
+typedef int32_t pwm_enable_f(const void *ctx, int32_t handle);
+
+/// `pwm.disable`, see pwm.witx
+/// - handle: PWM channel handle
+typedef int32_t pwm_disable_f(const void *ctx, int32_t handle);
+
+/// `pwm.get_max_duty`, see pwm.witx
+/// - handle: PWM channel handle
+/// - pwm_duty: Returns the maximum duty cycle or error
+typedef int32_t pwm_get_max_duty_f(const void *ctx, int32_t handle, uint32_t *pwm_duty);
+
+/// C platform PWM driver object
+typedef struct {
+    pwm_init_f *init;
+    pwm_deinit_f *deinit;
+    pwm_set_duty_f *set_duty;
+    pwm_set_frequency_f *set_frequency;
+    pwm_enable_f *enable;
+    pwm_disable_f *disable;
+    pwm_get_max_duty_f *get_max_duty;
+} pwm_drv_t;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -1,49 +1,104 @@
 /// Embedded WASM abstract C SPI platform interface
+///
+/// Generated from spi.witx by build.rs, do not edit
 
 #ifndef WASME_SPI_DRIVER_H
 #define WASME_SPI_DRIVER_H
 
 #include <stdint.h>
+#include "common.h"
 
 #ifdef __cplusplus
 extern "C"
 {
 #endif
 
-/// SPI Init, takes device index and config and returns handle (or error)
-typedef int32_t spi_init_f(const void *ctx, uint32_t dev, uint32_t baud, int32_t mosi, int32_t miso, int32_t sck, int32_t cs);
+/// Operation kinds for transactional SPI
+///
+/// See `$op_kind` in spi.witx
+typedef enum {
+    /// Transfer (write and read) the buffer in place
+    SPI_OP_KIND_TRANSFER = 0,
+    /// Read into the buffer
+    SPI_OP_KIND_READ = 1,
+    /// Write out the buffer
+    SPI_OP_KIND_WRITE = 2,
+} spi_op_kind_e;
 
-/// SPI Deinit, takes device handle and deinitialises
+/// Operation for transactional SPI
+///
+/// See `$op` in spi.witx
+typedef struct {
+    /// Kind of operation, spi_op_kind_e
+    uint32_t kind;
+    /// The address of the operation buffer, read and transfer buffers must not overlap
+    uint8_t *ptr;
+    /// The length of the operation buffer
+    uint32_t len;
+} spi_op_t;
+
+/// See `$cfg` in spi.witx
+typedef struct {
+    uint32_t freq;
+    int32_t mosi;
+    int32_t miso;
+    int32_t sck;
+    int32_t cs;
+} spi_cfg_t;
+
+/// `spi.init`, see spi.witx
+/// - port: SPI device to init
+/// - baud: Baud rate
+/// - mosi
+/// - miso
+/// - sck
+/// - cs
+/// Returns a device handle or error
+typedef int32_t spi_init_f(const void *ctx, uint32_t port, uint32_t baud, int32_t mosi, int32_t miso, int32_t sck, int32_t cs);
+
+/// `spi.deinit`, see spi.witx
+/// - handle: SPI device to deinit
 typedef int32_t spi_deinit_f(const void *ctx, int32_t handle);
 
-/// SPI Read, takes device handle, reads into data_in
-typedef int32_t spi_read_f(const void *ctx, int32_t handle, uint8_t *data_in, uint32_t length_out);
+/// `spi.read`, see spi.witx
+/// - handle: SPI device for write
+/// - read: Buffer to read in data
+typedef int32_t spi_read_f(const void *ctx, int32_t handle, uint8_t *read, uint32_t read_len);
 
-/// SPI Write, takes device handle, writes data_out
-typedef int32_t spi_write_f(const void *ctx, int32_t handle, uint8_t *data_out, uint32_t length_out);
+/// `spi.write`, see spi.witx
+/// - handle: SPI device for write
+/// - data: Data to write
+typedef int32_t spi_write_f(const void *ctx, int32_t handle, uint8_t *data, uint32_t data_len);
 
-/// SPI Transfer, takes device handle, transfers (reads and writes) data
-typedef int32_t spi_transfer_f(const void *ctx, int32_t handle, uint8_t *data_in, uint8_t *data_out, uint32_t length);
+/// `spi.transfer`, see spi.witx
+/// - handle: SPI device for transfer
+/// - read: Buffer to read in data
+/// - write: Data to write out
+typedef int32_t spi_transfer_f(const void *ctx, int32_t handle, uint8_t *read, uint32_t read_len, uint8_t *write, uint32_t write_len);
 
-/// SPI Transfer, takes device handle, transfers (reads and writes) data
-typedef int32_t spi_transfer_inplace_f(const void *ctx, int32_t handle, uint8_t *data, uint32_t length);
+/// `spi.transfer_inplace`, see spi.witx
+/// - handle: SPI device for transfer
+/// - data: Data to transfer (write and read)
+typedef int32_t spi_transfer_inplace_f(const void *ctx, int32_t handle, uint8_t *data, uint32_t data_len);
 
-/// SPI Exec, takes device handle, writes data_out then reads data_in from address
-// TODO: THIS IS NOT YET IMPLEMENTED / AVAILABLE
-typedef int32_t spi_exec_f(const void *ctx, int32_t handle);
+/// EXPERIMENTAL
+///
+/// `spi.exec`, see spi.witx
+/// - handle: SPI device for exec
+/// - data: List of operations to execute with CS held for the duration
+typedef int32_t spi_exec_f(const void *ctx, int32_t handle, spi_op_t *data, uint32_t data_len);
 
 /// C platform SPI driver object
 typedef struct {
-    spi_init_f              *init;
-    spi_deinit_f            *deinit;
-    spi_read_f              *read;
-    spi_write_f             *write;
-    spi_transfer_f          *transfer;
-    spi_transfer_inplace_f  *transfer_inplace;
-    spi_exec_f              *exec;
+    spi_init_f *init;
+    spi_deinit_f *deinit;
+    spi_read_f *read;
+    spi_write_f *write;
+    spi_transfer_f *transfer;
+    spi_transfer_inplace_f *transfer_inplace;
+    spi_exec_f *exec;
 } spi_drv_t;
 
-
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,61 @@
+/// Embedded WASM abstract C TIME platform interface
+///
+/// Generated from time.witx by build.rs, do not edit
+
+#ifndef WASME_TIME_DRIVER_H
+#define WASME_TIME_DRIVER_H
+
+#include <stdint.h>
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `time.now_us`, see time.witx
+/// - time_us: Returns the monotonic time in microseconds or error
+typedef int32_t time_now_us_f(const void *ctx, uint64_t *time_us);
+
+/// `time.delay_us`, see time.witx
+/// - us: Delay in microseconds
+typedef int32_t time_delay_us_f(const void *ctx, uint32_t us);
+
+/// `time.delay_ms`, see time.witx
+/// - ms: Delay in milliseconds
+typedef int32_t time_delay_ms_f(const void *ctx, uint32_t ms);
+
+/// `time.alarm_start`, see time.witx
+/// - timeout_us: Time until the alarm expires in microseconds
+/// Returns an alarm handle or error
+typedef int32_t time_alarm_start_f(const void *ctx, uint64_t timeout_us);
+
+/// `time.alarm_remaining`, see time.witx
+/// - handle: Alarm handle
+/// - time_us: Returns the time remaining in microseconds (0 once expired) or error
+typedef int32_t time_alarm_remaining_f(const void *ctx, int32_t handle, uint64_t *time_us);
+
+/// `time.alarm_wait`, see time.witx
+/// - handle: Alarm handle to wait for
+typedef int32_t time_alarm_wait_f(const void *ctx, int32_t handle);
+
+/// `time.alarm_cancel`, see time.witx
+/// - handle: Alarm handle to cancel and release, required for expired alarms
+typedef int32_t time_alarm_cancel_f(const void *ctx, int32_t handle);
+
+/// C platform TIME driver object
+typedef struct {
+    time_now_us_f *now_us;
+    time_delay_us_f *delay_us;
+    time_delay_ms_f *delay_ms;
+    time_alarm_start_f *alarm_start;
+    time_alarm_remaining_f *alarm_remaining;
+    time_alarm_wait_f *alarm_wait;
+    time_alarm_cancel_f *alarm_cancel;
+} time_drv_t;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
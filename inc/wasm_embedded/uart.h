@@ -1,31 +1,117 @@
 /// Embedded WASM abstract C UART platform interface
+///
+/// Generated from uart.witx by build.rs, do not edit
 
 #ifndef WASME_UART_DRIVER_H
 #define WASME_UART_DRIVER_H
 
 #include <stdint.h>
+#include "common.h"
 
 #ifdef __cplusplus
 extern "C"
 {
 #endif
 
-/// Init, takes device index and config and returns handle (or error)
-typedef int32_t uart_init_f(const void *ctx, uint32_t dev, uint32_t baud, int32_t sda, int32_t scl);
+/// UART parity
+///
+/// See `$parity` in uart.witx
+typedef enum {
+    UART_PARITY_NONE = 0,
+    UART_PARITY_EVEN = 1,
+    UART_PARITY_ODD = 2,
+} uart_parity_e;
 
-/// Deinit, takes device handle and deinitialises
+/// UART stop bits
+///
+/// See `$stop_bits` in uart.witx
+typedef enum {
+    UART_STOP_BITS_ONE = 0,
+    UART_STOP_BITS_ONE_POINT_FIVE = 1,
+    UART_STOP_BITS_TWO = 2,
+} uart_stop_bits_e;
+
+/// UART flow control
+///
+/// See `$flow_control` in uart.witx
+typedef enum {
+    UART_FLOW_CONTROL_NONE = 0,
+    UART_FLOW_CONTROL_RTS_CTS = 1,
+} uart_flow_control_e;
+
+/// UART read / write operation flags
+///
+/// With no flags set operations block until the full buffer has been transferred
+///
+/// See `$uart_flags` in uart.witx
+typedef enum {
+    /// Return immediately with the data that could be transferred, which may be none
+    UART_FLAGS_NON_BLOCKING = (1 << 0),
+    /// Return once the buffer is complete or the provided timeout has elapsed
+    UART_FLAGS_TIMEOUT = (1 << 1),
+    /// Return reads once data has been received and the line goes idle,
+    /// may be combined with `$timeout` to bound the wait for the first byte
+    UART_FLAGS_READ_UNTIL_IDLE = (1 << 2),
+} uart_flags_e;
+
+/// UART line configuration
+///
+/// See `$uart_cfg` in uart.witx
+typedef struct {
+    /// Baud rate
+    uint32_t baud;
+    /// Data bits per character (5 to 9)
+    uint32_t data_bits;
+    /// Parity, uart_parity_e
+    uint32_t parity;
+    /// Stop bits, uart_stop_bits_e
+    uint32_t stop_bits;
+    /// Flow control, uart_flow_control_e
+    uint32_t flow_control;
+    /// RTS pin for flow control, -1 for platform default
+    int32_t rts;
+    /// CTS pin for flow control, -1 for platform default
+    int32_t cts;
+} uart_cfg_t;
+
+/// `uart.init`, see uart.witx
+/// - port: UART device to init
+/// - baud: Baud rate
+/// - tx
+/// - rx
+/// Returns a device handle or error
+typedef int32_t uart_init_f(const void *ctx, uint32_t port, uint32_t baud, int32_t tx, int32_t rx);
+
+/// `uart.deinit`, see uart.witx
+/// - handle: UART device handle to deinit
 typedef int32_t uart_deinit_f(const void *ctx, int32_t handle);
 
-/// Write, takes device handle, writes data_out to address
-typedef int32_t uart_write_f(const void *ctx, int32_t handle, uint32_t flags, uint8_t *data_out, uint32_t length_out);
+/// `uart.configure`, see uart.witx
+/// - handle: UART device handle to configure
+/// - cfg: Line configuration
+typedef int32_t uart_configure_f(const void *ctx, int32_t handle, const uart_cfg_t *cfg);
+
+/// `uart.write`, see uart.witx
+/// - handle: UART device handle for write
+/// - flags: Write operation flags (uart_flags_e)
+/// - timeout_ms: Timeout in milliseconds, used with the `$timeout` flag
+/// - data: Data to write
+/// Returns the number of bytes written or error
+typedef int32_t uart_write_f(const void *ctx, int32_t handle, uint32_t flags, uint32_t timeout_ms, uint8_t *data, uint32_t data_len);
 
-/// Read, takes device handle, reads data_in from address
-typedef int32_t uart_read_f(const void *ctx, int32_t handle, uint32_t flags, uint8_t *data_in, uint32_t length_in);
+/// `uart.read`, see uart.witx
+/// - handle: UART device handle for transfer
+/// - flags: Read operation flags (uart_flags_e)
+/// - timeout_ms: Timeout in milliseconds, used with the `$timeout` flag
+/// - buff: Buffer to read into
+/// Returns the number of bytes read or error
+typedef int32_t uart_read_f(const void *ctx, int32_t handle, uint32_t flags, uint32_t timeout_ms, uint8_t *buff, uint32_t buff_len);
 
 /// C platform UART driver object
 typedef struct {
     uart_init_f *init;
     uart_deinit_f *deinit;
+    uart_configure_f *configure;
     uart_write_f *write;
     uart_read_f *read;
 } uart_drv_t;
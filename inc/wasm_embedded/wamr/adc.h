@@ -0,0 +1,132 @@
+/// Embedded WASM WAMR ADC host bindings
+///
+/// Generated from adc.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_ADC_H
+#define WASME_WAMR_ADC_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../adc.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `adc.init`, see adc.witx
+static uint32_t wasme_wamr_adc_init(wasm_exec_env_t exec_env, uint32_t port, uint32_t channel, int32_t pin, uint32_t resolution, uint32_t reference, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, channel, pin, resolution, reference);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `adc.deinit`, see adc.witx
+static uint32_t wasme_wamr_adc_deinit(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `adc.read`, see adc.witx
+static uint32_t wasme_wamr_adc_read(wasm_exec_env_t exec_env, int32_t handle, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 2);
+    uint16_t out;
+
+    int32_t res = drv->read(binding->ctx, handle, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &out, 2);
+    return WASME_ERRNO_OK;
+}
+
+/// `adc.read_multi`, see adc.witx
+static uint32_t wasme_wamr_adc_read_multi(wasm_exec_env_t exec_env, int32_t handle, uint32_t rate_hz, uint32_t buff_off, uint32_t buff_len)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->read_multi == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(buff_off, (uint64_t)buff_len * 2);
+    uint16_t *buff = (uint16_t *)wasm_runtime_addr_app_to_native(inst, buff_off);
+
+    int32_t res = drv->read_multi(binding->ctx, handle, rate_hz, buff, buff_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `adc` native symbols
+#define WASME_WAMR_ADC_SYMBOLS 4
+
+/// `adc` native symbol template, see `wasme_wamr_register_adc`
+static const NativeSymbol wasme_wamr_adc_symbols[WASME_WAMR_ADC_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_adc_init, "(iiiiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_adc_deinit, "(i)i", NULL },
+    { "read", (void *)wasme_wamr_adc_read, "(ii)i", NULL },
+    { "read_multi", (void *)wasme_wamr_adc_read_multi, "(iiii)i", NULL },
+};
+
+/// Register `adc` native symbols with WAMR,
+/// dispatching to the `adc_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_adc(NativeSymbol symbols[WASME_WAMR_ADC_SYMBOLS],
+                                           const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("adc", symbols, wasme_wamr_adc_symbols, WASME_WAMR_ADC_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,60 @@
+/// Embedded WASM WAMR host binding helpers
+///
+/// Generated by build.rs, do not edit
+
+#ifndef WASME_WAMR_COMMON_H
+#define WASME_WAMR_COMMON_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// Maximum number of records in a list argument (such as I2C or SPI operations),
+/// longer lists are rejected with WASME_ERRNO_INVALID_ARG
+#ifndef WASME_WAMR_MAX_OPS
+#define WASME_WAMR_MAX_OPS 16
+#endif
+
+/// Driver binding, attached to each registered native symbol
+typedef struct {
+    /// Driver object for the registered module (`gpio_drv_t` etc.)
+    const void *drv;
+    /// Platform context passed to driver functions
+    const void *ctx;
+} wasme_wamr_binding_t;
+
+/// Raise an exception and return if the guest memory region at `offset` is out of bounds,
+/// for use in native functions
+#define WASME_WAMR_CHECK_MEM(offset, len)                                                     \
+    if ((uint64_t)(offset) + (uint64_t)(len) > UINT32_MAX                                     \
+        || !wasm_runtime_validate_app_addr(inst, (uint32_t)(offset), (uint32_t)(len))) {     \
+        wasm_runtime_set_exception(inst, "out of bounds memory access");                    \
+        return 0;                                                                             \
+    }
+
+/// Populate the caller-owned `symbols` table from `defaults`, attaching `binding` to each
+/// symbol, and register it with WAMR
+static inline bool wasme_wamr_register(const char *module_name, NativeSymbol *symbols,
+                                       const NativeSymbol *defaults, uint32_t len,
+                                       const wasme_wamr_binding_t *binding)
+{
+    for (uint32_t i = 0; i < len; i++) {
+        symbols[i] = defaults[i];
+        symbols[i].attachment = (void *)binding;
+    }
+    return wasm_runtime_register_natives(module_name, symbols, len);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
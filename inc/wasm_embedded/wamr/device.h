@@ -0,0 +1,163 @@
+/// Embedded WASM WAMR DEVICE host bindings
+///
+/// Generated from device.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_DEVICE_H
+#define WASME_WAMR_DEVICE_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../device.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `device.spi`, see device.witx
+static uint32_t wasme_wamr_device_spi(wasm_exec_env_t exec_env, uint32_t spi_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->spi == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(spi_ref, 8);
+    uint32_t spi_off, spi_len;
+    memcpy(&spi_off, wasm_runtime_addr_app_to_native(inst, spi_ref), 4);
+    memcpy(&spi_len, wasm_runtime_addr_app_to_native(inst, spi_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(spi_off, (uint64_t)spi_len * 4);
+    int32_t *spi = (int32_t *)wasm_runtime_addr_app_to_native(inst, spi_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->spi(binding->ctx, spi, spi_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `device.i2c`, see device.witx
+static uint32_t wasme_wamr_device_i2c(wasm_exec_env_t exec_env, uint32_t i2c_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->i2c == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(i2c_ref, 8);
+    uint32_t i2c_off, i2c_len;
+    memcpy(&i2c_off, wasm_runtime_addr_app_to_native(inst, i2c_ref), 4);
+    memcpy(&i2c_len, wasm_runtime_addr_app_to_native(inst, i2c_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(i2c_off, (uint64_t)i2c_len * 4);
+    int32_t *i2c = (int32_t *)wasm_runtime_addr_app_to_native(inst, i2c_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->i2c(binding->ctx, i2c, i2c_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `device.gpio_in`, see device.witx
+static uint32_t wasme_wamr_device_gpio_in(wasm_exec_env_t exec_env, uint32_t gpio_in_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->gpio_in == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(gpio_in_ref, 8);
+    uint32_t gpio_in_off, gpio_in_len;
+    memcpy(&gpio_in_off, wasm_runtime_addr_app_to_native(inst, gpio_in_ref), 4);
+    memcpy(&gpio_in_len, wasm_runtime_addr_app_to_native(inst, gpio_in_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(gpio_in_off, (uint64_t)gpio_in_len * 4);
+    int32_t *gpio_in = (int32_t *)wasm_runtime_addr_app_to_native(inst, gpio_in_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->gpio_in(binding->ctx, gpio_in, gpio_in_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `device.gpio_out`, see device.witx
+static uint32_t wasme_wamr_device_gpio_out(wasm_exec_env_t exec_env, uint32_t gpio_out_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->gpio_out == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(gpio_out_ref, 8);
+    uint32_t gpio_out_off, gpio_out_len;
+    memcpy(&gpio_out_off, wasm_runtime_addr_app_to_native(inst, gpio_out_ref), 4);
+    memcpy(&gpio_out_len, wasm_runtime_addr_app_to_native(inst, gpio_out_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(gpio_out_off, (uint64_t)gpio_out_len * 4);
+    int32_t *gpio_out = (int32_t *)wasm_runtime_addr_app_to_native(inst, gpio_out_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->gpio_out(binding->ctx, gpio_out, gpio_out_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `device` native symbols
+#define WASME_WAMR_DEVICE_SYMBOLS 4
+
+/// `device` native symbol template, see `wasme_wamr_register_device`
+static const NativeSymbol wasme_wamr_device_symbols[WASME_WAMR_DEVICE_SYMBOLS] = {
+    { "spi", (void *)wasme_wamr_device_spi, "(ii)i", NULL },
+    { "i2c", (void *)wasme_wamr_device_i2c, "(ii)i", NULL },
+    { "gpio_in", (void *)wasme_wamr_device_gpio_in, "(ii)i", NULL },
+    { "gpio_out", (void *)wasme_wamr_device_gpio_out, "(ii)i", NULL },
+};
+
+/// Register `device` native symbols with WAMR,
+/// dispatching to the `device_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_device(NativeSymbol symbols[WASME_WAMR_DEVICE_SYMBOLS],
+                                              const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("device", symbols, wasme_wamr_device_symbols, WASME_WAMR_DEVICE_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,183 @@
+/// Embedded WASM WAMR GPIO host bindings
+///
+/// Generated from gpio.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_GPIO_H
+#define WASME_WAMR_GPIO_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../gpio.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `gpio.init`, see gpio.witx
+static uint32_t wasme_wamr_gpio_init(wasm_exec_env_t exec_env, int32_t port, int32_t pin, uint32_t cfg_off, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(cfg_off, 20);
+    const uint8_t *cfg_src = (const uint8_t *)wasm_runtime_addr_app_to_native(inst, cfg_off);
+    gpio_cfg_t cfg;
+    memcpy(&cfg.direction, cfg_src + 0, 4);
+    memcpy(&cfg.pull, cfg_src + 4, 4);
+    memcpy(&cfg.drive, cfg_src + 8, 4);
+    memcpy(&cfg.strength, cfg_src + 12, 4);
+    memcpy(&cfg.initial, cfg_src + 16, 4);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, pin, &cfg);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `gpio.deinit`, see gpio.witx
+static uint32_t wasme_wamr_gpio_deinit(wasm_exec_env_t exec_env, int32_t dev)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, dev);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `gpio.set`, see gpio.witx
+static uint32_t wasme_wamr_gpio_set(wasm_exec_env_t exec_env, int32_t dev, uint32_t value)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->set == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->set(binding->ctx, dev, value);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `gpio.get`, see gpio.witx
+static uint32_t wasme_wamr_gpio_get(wasm_exec_env_t exec_env, int32_t dev, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->get == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+    uint32_t out;
+
+    int32_t res = drv->get(binding->ctx, dev, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &out, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `gpio.set_interrupt`, see gpio.witx
+static uint32_t wasme_wamr_gpio_set_interrupt(wasm_exec_env_t exec_env, int32_t dev, uint32_t edge)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->set_interrupt == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->set_interrupt(binding->ctx, dev, edge);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `gpio.wait_event`, see gpio.witx
+static uint32_t wasme_wamr_gpio_wait_event(wasm_exec_env_t exec_env, uint32_t handles_off, uint32_t handles_len, uint32_t timeout_us, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->wait_event == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(handles_off, (uint64_t)handles_len * 4);
+    int32_t *handles = (int32_t *)wasm_runtime_addr_app_to_native(inst, handles_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 8);
+    gpio_event_t out;
+
+    int32_t res = drv->wait_event(binding->ctx, handles, handles_len, timeout_us, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    uint8_t *dst = (uint8_t *)wasm_runtime_addr_app_to_native(inst, out_off);
+    memcpy(dst + 0, &out.handle, 4);
+    memcpy(dst + 4, &out.edge, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `gpio` native symbols
+#define WASME_WAMR_GPIO_SYMBOLS 6
+
+/// `gpio` native symbol template, see `wasme_wamr_register_gpio`
+static const NativeSymbol wasme_wamr_gpio_symbols[WASME_WAMR_GPIO_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_gpio_init, "(iiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_gpio_deinit, "(i)i", NULL },
+    { "set", (void *)wasme_wamr_gpio_set, "(ii)i", NULL },
+    { "get", (void *)wasme_wamr_gpio_get, "(ii)i", NULL },
+    { "set_interrupt", (void *)wasme_wamr_gpio_set_interrupt, "(ii)i", NULL },
+    { "wait_event", (void *)wasme_wamr_gpio_wait_event, "(iiii)i", NULL },
+};
+
+/// Register `gpio` native symbols with WAMR,
+/// dispatching to the `gpio_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_gpio(NativeSymbol symbols[WASME_WAMR_GPIO_SYMBOLS],
+                                            const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("gpio", symbols, wasme_wamr_gpio_symbols, WASME_WAMR_GPIO_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,206 @@
+/// Embedded WASM WAMR I2C host bindings
+///
+/// Generated from i2c.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_I2C_H
+#define WASME_WAMR_I2C_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../i2c.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `i2c.init`, see i2c.witx
+static uint32_t wasme_wamr_i2c_init(wasm_exec_env_t exec_env, uint32_t port, uint32_t baud, int32_t sda, int32_t scl, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, sda, scl);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `i2c.deinit`, see i2c.witx
+static uint32_t wasme_wamr_i2c_deinit(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `i2c.write`, see i2c.witx
+static uint32_t wasme_wamr_i2c_write(wasm_exec_env_t exec_env, int32_t handle, uint32_t addr, uint32_t data_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, wasm_runtime_addr_app_to_native(inst, data_ref), 4);
+    memcpy(&data_len, wasm_runtime_addr_app_to_native(inst, data_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off);
+
+    int32_t res = drv->write(binding->ctx, handle, (uint16_t)addr, data, data_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `i2c.read`, see i2c.witx
+static uint32_t wasme_wamr_i2c_read(wasm_exec_env_t exec_env, int32_t handle, uint32_t addr, uint32_t buff_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, wasm_runtime_addr_app_to_native(inst, buff_ref), 4);
+    memcpy(&buff_len, wasm_runtime_addr_app_to_native(inst, buff_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)wasm_runtime_addr_app_to_native(inst, buff_off);
+
+    int32_t res = drv->read(binding->ctx, handle, (uint16_t)addr, buff, buff_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `i2c.write_read`, see i2c.witx
+static uint32_t wasme_wamr_i2c_write_read(wasm_exec_env_t exec_env, int32_t handle, uint32_t addr, uint32_t data_ref, uint32_t buff_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->write_read == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, wasm_runtime_addr_app_to_native(inst, data_ref), 4);
+    memcpy(&data_len, wasm_runtime_addr_app_to_native(inst, data_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off);
+
+    WASME_WAMR_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, wasm_runtime_addr_app_to_native(inst, buff_ref), 4);
+    memcpy(&buff_len, wasm_runtime_addr_app_to_native(inst, buff_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)wasm_runtime_addr_app_to_native(inst, buff_off);
+
+    int32_t res = drv->write_read(binding->ctx, handle, (uint16_t)addr, data, data_len, buff, buff_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `i2c.transaction`, see i2c.witx
+static uint32_t wasme_wamr_i2c_transaction(wasm_exec_env_t exec_env, int32_t handle, uint32_t addr, uint32_t ops_off, uint32_t ops_len)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->transaction == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    if (ops_len > WASME_WAMR_MAX_OPS) {
+        return WASME_ERRNO_INVALID_ARG;
+    }
+    WASME_WAMR_CHECK_MEM(ops_off, (uint64_t)ops_len * 12);
+    i2c_op_t ops[WASME_WAMR_MAX_OPS];
+    for (uint32_t i = 0; i < ops_len; i++) {
+        const uint8_t *src = (const uint8_t *)wasm_runtime_addr_app_to_native(inst, ops_off + i * 12);
+        memcpy(&ops[i].kind, src + 0, 4);
+        memcpy(&ops[i].len, src + 8, 4);
+        uint32_t ptr_off;
+        memcpy(&ptr_off, src + 4, 4);
+        WASME_WAMR_CHECK_MEM(ptr_off, (uint64_t)ops[i].len * 1);
+        ops[i].ptr = (uint8_t *)wasm_runtime_addr_app_to_native(inst, ptr_off);
+    }
+
+    int32_t res = drv->transaction(binding->ctx, handle, (uint16_t)addr, ops, ops_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `i2c` native symbols
+#define WASME_WAMR_I2C_SYMBOLS 6
+
+/// `i2c` native symbol template, see `wasme_wamr_register_i2c`
+static const NativeSymbol wasme_wamr_i2c_symbols[WASME_WAMR_I2C_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_i2c_init, "(iiiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_i2c_deinit, "(i)i", NULL },
+    { "write", (void *)wasme_wamr_i2c_write, "(iii)i", NULL },
+    { "read", (void *)wasme_wamr_i2c_read, "(iii)i", NULL },
+    { "write_read", (void *)wasme_wamr_i2c_write_read, "(iiii)i", NULL },
+    { "transaction", (void *)wasme_wamr_i2c_transaction, "(iiii)i", NULL },
+};
+
+/// Register `i2c` native symbols with WAMR,
+/// dispatching to the `i2c_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_i2c(NativeSymbol symbols[WASME_WAMR_I2C_SYMBOLS],
+                                           const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("i2c", symbols, wasme_wamr_i2c_symbols, WASME_WAMR_I2C_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,182 @@
+/// Embedded WASM WAMR PWM host bindings
+///
+/// Generated from pwm.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_PWM_H
+#define WASME_WAMR_PWM_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../pwm.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `pwm.init`, see pwm.witx
+static uint32_t wasme_wamr_pwm_init(wasm_exec_env_t exec_env, uint32_t port, uint32_t channel, int32_t pin, uint32_t freq_hz, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, channel, pin, freq_hz);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.deinit`, see pwm.witx
+static uint32_t wasme_wamr_pwm_deinit(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.set_duty`, see pwm.witx
+static uint32_t wasme_wamr_pwm_set_duty(wasm_exec_env_t exec_env, int32_t handle, uint32_t duty)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->set_duty == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->set_duty(binding->ctx, handle, duty);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.set_frequency`, see pwm.witx
+static uint32_t wasme_wamr_pwm_set_frequency(wasm_exec_env_t exec_env, int32_t handle, uint32_t freq_hz)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->set_frequency == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->set_frequency(binding->ctx, handle, freq_hz);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.enable`, see pwm.witx
+static uint32_t wasme_wamr_pwm_enable(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->enable == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->enable(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.disable`, see pwm.witx
+static uint32_t wasme_wamr_pwm_disable(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->disable == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->disable(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `pwm.get_max_duty`, see pwm.witx
+static uint32_t wasme_wamr_pwm_get_max_duty(wasm_exec_env_t exec_env, int32_t handle, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->get_max_duty == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+    uint32_t out;
+
+    int32_t res = drv->get_max_duty(binding->ctx, handle, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &out, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `pwm` native symbols
+#define WASME_WAMR_PWM_SYMBOLS 7
+
+/// `pwm` native symbol template, see `wasme_wamr_register_pwm`
+static const NativeSymbol wasme_wamr_pwm_symbols[WASME_WAMR_PWM_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_pwm_init, "(iiiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_pwm_deinit, "(i)i", NULL },
+    { "set_duty", (void *)wasme_wamr_pwm_set_duty, "(ii)i", NULL },
+    { "set_frequency", (void *)wasme_wamr_pwm_set_frequency, "(ii)i", NULL },
+    { "enable", (void *)wasme_wamr_pwm_enable, "(i)i", NULL },
+    { "disable", (void *)wasme_wamr_pwm_disable, "(i)i", NULL },
+    { "get_max_duty", (void *)wasme_wamr_pwm_get_max_duty, "(ii)i", NULL },
+};
+
+/// Register `pwm` native symbols with WAMR,
+/// dispatching to the `pwm_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_pwm(NativeSymbol symbols[WASME_WAMR_PWM_SYMBOLS],
+                                           const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("pwm", symbols, wasme_wamr_pwm_symbols, WASME_WAMR_PWM_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,232 @@
+/// Embedded WASM WAMR SPI host bindings
+///
+/// Generated from spi.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_SPI_H
+#define WASME_WAMR_SPI_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../spi.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `spi.init`, see spi.witx
+static uint32_t wasme_wamr_spi_init(wasm_exec_env_t exec_env, uint32_t port, uint32_t baud, int32_t mosi, int32_t miso, int32_t sck, int32_t cs, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, mosi, miso, sck, cs);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.deinit`, see spi.witx
+static uint32_t wasme_wamr_spi_deinit(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.read`, see spi.witx
+static uint32_t wasme_wamr_spi_read(wasm_exec_env_t exec_env, int32_t handle, uint32_t read_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(read_ref, 8);
+    uint32_t read_off, read_len;
+    memcpy(&read_off, wasm_runtime_addr_app_to_native(inst, read_ref), 4);
+    memcpy(&read_len, wasm_runtime_addr_app_to_native(inst, read_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(read_off, (uint64_t)read_len * 1);
+    uint8_t *read = (uint8_t *)wasm_runtime_addr_app_to_native(inst, read_off);
+
+    int32_t res = drv->read(binding->ctx, handle, read, read_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.write`, see spi.witx
+static uint32_t wasme_wamr_spi_write(wasm_exec_env_t exec_env, int32_t handle, uint32_t data_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, wasm_runtime_addr_app_to_native(inst, data_ref), 4);
+    memcpy(&data_len, wasm_runtime_addr_app_to_native(inst, data_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off);
+
+    int32_t res = drv->write(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.transfer`, see spi.witx
+static uint32_t wasme_wamr_spi_transfer(wasm_exec_env_t exec_env, int32_t handle, uint32_t read_ref, uint32_t write_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->transfer == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(read_ref, 8);
+    uint32_t read_off, read_len;
+    memcpy(&read_off, wasm_runtime_addr_app_to_native(inst, read_ref), 4);
+    memcpy(&read_len, wasm_runtime_addr_app_to_native(inst, read_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(read_off, (uint64_t)read_len * 1);
+    uint8_t *read = (uint8_t *)wasm_runtime_addr_app_to_native(inst, read_off);
+
+    WASME_WAMR_CHECK_MEM(write_ref, 8);
+    uint32_t write_off, write_len;
+    memcpy(&write_off, wasm_runtime_addr_app_to_native(inst, write_ref), 4);
+    memcpy(&write_len, wasm_runtime_addr_app_to_native(inst, write_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(write_off, (uint64_t)write_len * 1);
+    uint8_t *write = (uint8_t *)wasm_runtime_addr_app_to_native(inst, write_off);
+
+    int32_t res = drv->transfer(binding->ctx, handle, read, read_len, write, write_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.transfer_inplace`, see spi.witx
+static uint32_t wasme_wamr_spi_transfer_inplace(wasm_exec_env_t exec_env, int32_t handle, uint32_t data_ref)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->transfer_inplace == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, wasm_runtime_addr_app_to_native(inst, data_ref), 4);
+    memcpy(&data_len, wasm_runtime_addr_app_to_native(inst, data_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off);
+
+    int32_t res = drv->transfer_inplace(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `spi.exec`, see spi.witx
+static uint32_t wasme_wamr_spi_exec(wasm_exec_env_t exec_env, int32_t handle, uint32_t data_off, uint32_t data_len)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->exec == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    if (data_len > WASME_WAMR_MAX_OPS) {
+        return WASME_ERRNO_INVALID_ARG;
+    }
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 12);
+    spi_op_t data[WASME_WAMR_MAX_OPS];
+    for (uint32_t i = 0; i < data_len; i++) {
+        const uint8_t *src = (const uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off + i * 12);
+        memcpy(&data[i].kind, src + 0, 4);
+        memcpy(&data[i].len, src + 8, 4);
+        uint32_t ptr_off;
+        memcpy(&ptr_off, src + 4, 4);
+        WASME_WAMR_CHECK_MEM(ptr_off, (uint64_t)data[i].len * 1);
+        data[i].ptr = (uint8_t *)wasm_runtime_addr_app_to_native(inst, ptr_off);
+    }
+
+    int32_t res = drv->exec(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `spi` native symbols
+#define WASME_WAMR_SPI_SYMBOLS 7
+
+/// `spi` native symbol template, see `wasme_wamr_register_spi`
+static const NativeSymbol wasme_wamr_spi_symbols[WASME_WAMR_SPI_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_spi_init, "(iiiiiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_spi_deinit, "(i)i", NULL },
+    { "read", (void *)wasme_wamr_spi_read, "(ii)i", NULL },
+    { "write", (void *)wasme_wamr_spi_write, "(ii)i", NULL },
+    { "transfer", (void *)wasme_wamr_spi_transfer, "(iii)i", NULL },
+    { "transfer_inplace", (void *)wasme_wamr_spi_transfer_inplace, "(ii)i", NULL },
+    { "exec", (void *)wasme_wamr_spi_exec, "(iii)i", NULL },
+};
+
+/// Register `spi` native symbols with WAMR,
+/// dispatching to the `spi_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_spi(NativeSymbol symbols[WASME_WAMR_SPI_SYMBOLS],
+                                           const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("spi", symbols, wasme_wamr_spi_symbols, WASME_WAMR_SPI_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
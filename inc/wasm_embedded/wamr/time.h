@@ -0,0 +1,187 @@
+/// Embedded WASM WAMR TIME host bindings
+///
+/// Generated from time.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_TIME_H
+#define WASME_WAMR_TIME_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../time.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `time.now_us`, see time.witx
+static uint32_t wasme_wamr_time_now_us(wasm_exec_env_t exec_env, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->now_us == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 8);
+    uint64_t out;
+
+    int32_t res = drv->now_us(binding->ctx, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &out, 8);
+    return WASME_ERRNO_OK;
+}
+
+/// `time.delay_us`, see time.witx
+static uint32_t wasme_wamr_time_delay_us(wasm_exec_env_t exec_env, uint32_t us)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->delay_us == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->delay_us(binding->ctx, us);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `time.delay_ms`, see time.witx
+static uint32_t wasme_wamr_time_delay_ms(wasm_exec_env_t exec_env, uint32_t ms)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->delay_ms == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->delay_ms(binding->ctx, ms);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `time.alarm_start`, see time.witx
+static uint32_t wasme_wamr_time_alarm_start(wasm_exec_env_t exec_env, uint64_t timeout_us, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_start == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->alarm_start(binding->ctx, timeout_us);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `time.alarm_remaining`, see time.witx
+static uint32_t wasme_wamr_time_alarm_remaining(wasm_exec_env_t exec_env, int32_t handle, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_remaining == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 8);
+    uint64_t out;
+
+    int32_t res = drv->alarm_remaining(binding->ctx, handle, &out);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &out, 8);
+    return WASME_ERRNO_OK;
+}
+
+/// `time.alarm_wait`, see time.witx
+static uint32_t wasme_wamr_time_alarm_wait(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_wait == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->alarm_wait(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `time.alarm_cancel`, see time.witx
+static uint32_t wasme_wamr_time_alarm_cancel(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_cancel == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->alarm_cancel(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `time` native symbols
+#define WASME_WAMR_TIME_SYMBOLS 7
+
+/// `time` native symbol template, see `wasme_wamr_register_time`
+static const NativeSymbol wasme_wamr_time_symbols[WASME_WAMR_TIME_SYMBOLS] = {
+    { "now_us", (void *)wasme_wamr_time_now_us, "(i)i", NULL },
+    { "delay_us", (void *)wasme_wamr_time_delay_us, "(i)i", NULL },
+    { "delay_ms", (void *)wasme_wamr_time_delay_ms, "(i)i", NULL },
+    { "alarm_start", (void *)wasme_wamr_time_alarm_start, "(Ii)i", NULL },
+    { "alarm_remaining", (void *)wasme_wamr_time_alarm_remaining, "(ii)i", NULL },
+    { "alarm_wait", (void *)wasme_wamr_time_alarm_wait, "(i)i", NULL },
+    { "alarm_cancel", (void *)wasme_wamr_time_alarm_cancel, "(i)i", NULL },
+};
+
+/// Register `time` native symbols with WAMR,
+/// dispatching to the `time_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_time(NativeSymbol symbols[WASME_WAMR_TIME_SYMBOLS],
+                                            const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("time", symbols, wasme_wamr_time_symbols, WASME_WAMR_TIME_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,175 @@
+/// Embedded WASM WAMR UART host bindings
+///
+/// Generated from uart.witx by build.rs, do not edit
+
+#ifndef WASME_WAMR_UART_H
+#define WASME_WAMR_UART_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm_export.h"
+#include "../uart.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `uart.init`, see uart.witx
+static uint32_t wasme_wamr_uart_init(wasm_exec_env_t exec_env, uint32_t port, uint32_t baud, int32_t tx, int32_t rx, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, tx, rx);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `uart.deinit`, see uart.witx
+static uint32_t wasme_wamr_uart_deinit(wasm_exec_env_t exec_env, int32_t handle)
+{
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `uart.configure`, see uart.witx
+static uint32_t wasme_wamr_uart_configure(wasm_exec_env_t exec_env, int32_t handle, uint32_t cfg_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->configure == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(cfg_off, 28);
+    const uint8_t *cfg_src = (const uint8_t *)wasm_runtime_addr_app_to_native(inst, cfg_off);
+    uart_cfg_t cfg;
+    memcpy(&cfg.baud, cfg_src + 0, 4);
+    memcpy(&cfg.data_bits, cfg_src + 4, 4);
+    memcpy(&cfg.parity, cfg_src + 8, 4);
+    memcpy(&cfg.stop_bits, cfg_src + 12, 4);
+    memcpy(&cfg.flow_control, cfg_src + 16, 4);
+    memcpy(&cfg.rts, cfg_src + 20, 4);
+    memcpy(&cfg.cts, cfg_src + 24, 4);
+
+    int32_t res = drv->configure(binding->ctx, handle, &cfg);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    return WASME_ERRNO_OK;
+}
+
+/// `uart.write`, see uart.witx
+static uint32_t wasme_wamr_uart_write(wasm_exec_env_t exec_env, int32_t handle, uint32_t flags, uint32_t timeout_ms, uint32_t data_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, wasm_runtime_addr_app_to_native(inst, data_ref), 4);
+    memcpy(&data_len, wasm_runtime_addr_app_to_native(inst, data_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)wasm_runtime_addr_app_to_native(inst, data_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->write(binding->ctx, handle, flags, timeout_ms, data, data_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// `uart.read`, see uart.witx
+static uint32_t wasme_wamr_uart_read(wasm_exec_env_t exec_env, int32_t handle, uint32_t flags, uint32_t timeout_ms, uint32_t buff_ref, uint32_t out_off)
+{
+    wasm_module_inst_t inst = wasm_runtime_get_module_inst(exec_env);
+    const wasme_wamr_binding_t *binding = (const wasme_wamr_binding_t *)wasm_runtime_get_function_attachment(exec_env);
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        return WASME_ERRNO_UNSUPPORTED;
+    }
+
+    WASME_WAMR_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, wasm_runtime_addr_app_to_native(inst, buff_ref), 4);
+    memcpy(&buff_len, wasm_runtime_addr_app_to_native(inst, buff_ref + 4), 4);
+    WASME_WAMR_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)wasm_runtime_addr_app_to_native(inst, buff_off);
+
+    WASME_WAMR_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->read(binding->ctx, handle, flags, timeout_ms, buff, buff_len);
+    if (res < 0) {
+        return (uint32_t)-res;
+    }
+
+    memcpy(wasm_runtime_addr_app_to_native(inst, out_off), &res, 4);
+    return WASME_ERRNO_OK;
+}
+
+/// Number of `uart` native symbols
+#define WASME_WAMR_UART_SYMBOLS 5
+
+/// `uart` native symbol template, see `wasme_wamr_register_uart`
+static const NativeSymbol wasme_wamr_uart_symbols[WASME_WAMR_UART_SYMBOLS] = {
+    { "init", (void *)wasme_wamr_uart_init, "(iiiii)i", NULL },
+    { "deinit", (void *)wasme_wamr_uart_deinit, "(i)i", NULL },
+    { "configure", (void *)wasme_wamr_uart_configure, "(ii)i", NULL },
+    { "write", (void *)wasme_wamr_uart_write, "(iiiii)i", NULL },
+    { "read", (void *)wasme_wamr_uart_read, "(iiiii)i", NULL },
+};
+
+/// Register `uart` native symbols with WAMR,
+/// dispatching to the `uart_drv_t` driver object in `binding`.
+///
+/// `symbols` is populated and registered as the native symbol table, so each
+/// binding requires its own table. WAMR resolves imports by module name across
+/// all registered tables when modules are loaded, so only one binding should be
+/// registered at a time (see `wasm_runtime_unregister_natives`).
+/// `symbols` and `binding` must remain valid while registered.
+static inline bool wasme_wamr_register_uart(NativeSymbol symbols[WASME_WAMR_UART_SYMBOLS],
+                                            const wasme_wamr_binding_t *binding)
+{
+    return wasme_wamr_register("uart", symbols, wasme_wamr_uart_symbols, WASME_WAMR_UART_SYMBOLS,
+                               binding);
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
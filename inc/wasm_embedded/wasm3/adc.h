@@ -0,0 +1,158 @@
+/// Embedded WASM wasm3 ADC host bindings
+///
+/// Generated from adc.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_ADC_H
+#define WASME_WASM3_ADC_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../adc.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `adc.init`, see adc.witx
+static m3ApiRawFunction(wasme_wasm3_adc_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, port);
+    m3ApiGetArg(uint32_t, channel);
+    m3ApiGetArg(int32_t, pin);
+    m3ApiGetArg(uint32_t, resolution);
+    m3ApiGetArg(uint32_t, reference);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, channel, pin, resolution, reference);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `adc.deinit`, see adc.witx
+static m3ApiRawFunction(wasme_wasm3_adc_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `adc.read`, see adc.witx
+static m3ApiRawFunction(wasme_wasm3_adc_read)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 2);
+    uint16_t out;
+
+    int32_t res = drv->read(binding->ctx, handle, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &out, 2);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `adc.read_multi`, see adc.witx
+static m3ApiRawFunction(wasme_wasm3_adc_read_multi)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, rate_hz);
+    m3ApiGetArg(uint32_t, buff_off);
+    m3ApiGetArg(uint32_t, buff_len);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const adc_drv_t *drv = (const adc_drv_t *)binding->drv;
+    if (drv->read_multi == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(buff_off, (uint64_t)buff_len * 2);
+    uint16_t *buff = (uint16_t *)m3ApiOffsetToPtr(buff_off);
+
+    int32_t res = drv->read_multi(binding->ctx, handle, rate_hz, buff, buff_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `adc` functions into a wasm3 module,
+/// dispatching to the `adc_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_adc(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "adc", "init", "i(iiiiii)", &wasme_wasm3_adc_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "adc", "deinit", "i(i)", &wasme_wasm3_adc_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "adc", "read", "i(ii)", &wasme_wasm3_adc_read, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "adc", "read_multi", "i(iiii)", &wasme_wasm3_adc_read_multi, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,55 @@
+/// Embedded WASM wasm3 host binding helpers
+///
+/// Generated by build.rs, do not edit
+
+#ifndef WASME_WASM3_COMMON_H
+#define WASME_WASM3_COMMON_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// Maximum number of records in a list argument (such as I2C or SPI operations),
+/// longer lists are rejected with WASME_ERRNO_INVALID_ARG
+#ifndef WASME_WASM3_MAX_OPS
+#define WASME_WASM3_MAX_OPS 16
+#endif
+
+/// Driver binding, provided as userdata when linking functions
+typedef struct {
+    /// Driver object for the linked module (`gpio_drv_t` etc.)
+    const void *drv;
+    /// Platform context passed to driver functions
+    const void *ctx;
+} wasme_wasm3_binding_t;
+
+/// Trap if the guest memory region at `offset` is out of bounds, for use in raw functions
+#define WASME_WASM3_CHECK_MEM(offset, len)                                    \
+    if ((uint64_t)(offset) + (uint64_t)(len) > m3_GetMemorySize(runtime)) {   \
+        m3ApiTrap(m3Err_trapOutOfBoundsMemoryAccess);                         \
+    }
+
+/// Link a raw function, skipping functions not imported by the module
+static inline M3Result wasme_wasm3_link(IM3Module module, const char *module_name,
+                                        const char *name, const char *sig, M3RawCall call,
+                                        const wasme_wasm3_binding_t *binding)
+{
+    M3Result res = m3_LinkRawFunctionEx(module, module_name, name, sig, call, binding);
+    if (res == m3Err_functionLookupFailed) {
+        return m3Err_none;
+    }
+    return res;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,180 @@
+/// Embedded WASM wasm3 DEVICE host bindings
+///
+/// Generated from device.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_DEVICE_H
+#define WASME_WASM3_DEVICE_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../device.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `device.spi`, see device.witx
+static m3ApiRawFunction(wasme_wasm3_device_spi)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, spi_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->spi == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(spi_ref, 8);
+    uint32_t spi_off, spi_len;
+    memcpy(&spi_off, m3ApiOffsetToPtr(spi_ref), 4);
+    memcpy(&spi_len, m3ApiOffsetToPtr(spi_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(spi_off, (uint64_t)spi_len * 4);
+    int32_t *spi = (int32_t *)m3ApiOffsetToPtr(spi_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->spi(binding->ctx, spi, spi_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `device.i2c`, see device.witx
+static m3ApiRawFunction(wasme_wasm3_device_i2c)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, i2c_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->i2c == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(i2c_ref, 8);
+    uint32_t i2c_off, i2c_len;
+    memcpy(&i2c_off, m3ApiOffsetToPtr(i2c_ref), 4);
+    memcpy(&i2c_len, m3ApiOffsetToPtr(i2c_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(i2c_off, (uint64_t)i2c_len * 4);
+    int32_t *i2c = (int32_t *)m3ApiOffsetToPtr(i2c_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->i2c(binding->ctx, i2c, i2c_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `device.gpio_in`, see device.witx
+static m3ApiRawFunction(wasme_wasm3_device_gpio_in)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, gpio_in_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->gpio_in == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(gpio_in_ref, 8);
+    uint32_t gpio_in_off, gpio_in_len;
+    memcpy(&gpio_in_off, m3ApiOffsetToPtr(gpio_in_ref), 4);
+    memcpy(&gpio_in_len, m3ApiOffsetToPtr(gpio_in_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(gpio_in_off, (uint64_t)gpio_in_len * 4);
+    int32_t *gpio_in = (int32_t *)m3ApiOffsetToPtr(gpio_in_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->gpio_in(binding->ctx, gpio_in, gpio_in_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `device.gpio_out`, see device.witx
+static m3ApiRawFunction(wasme_wasm3_device_gpio_out)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, gpio_out_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const device_drv_t *drv = (const device_drv_t *)binding->drv;
+    if (drv->gpio_out == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(gpio_out_ref, 8);
+    uint32_t gpio_out_off, gpio_out_len;
+    memcpy(&gpio_out_off, m3ApiOffsetToPtr(gpio_out_ref), 4);
+    memcpy(&gpio_out_len, m3ApiOffsetToPtr(gpio_out_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(gpio_out_off, (uint64_t)gpio_out_len * 4);
+    int32_t *gpio_out = (int32_t *)m3ApiOffsetToPtr(gpio_out_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->gpio_out(binding->ctx, gpio_out, gpio_out_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `device` functions into a wasm3 module,
+/// dispatching to the `device_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_device(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "device", "spi", "i(ii)", &wasme_wasm3_device_spi, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "device", "i2c", "i(ii)", &wasme_wasm3_device_i2c, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "device", "gpio_in", "i(ii)", &wasme_wasm3_device_gpio_in, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "device", "gpio_out", "i(ii)", &wasme_wasm3_device_gpio_out, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,229 @@
+/// Embedded WASM wasm3 GPIO host bindings
+///
+/// Generated from gpio.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_GPIO_H
+#define WASME_WASM3_GPIO_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../gpio.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `gpio.init`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, port);
+    m3ApiGetArg(int32_t, pin);
+    m3ApiGetArg(uint32_t, cfg_off);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(cfg_off, 20);
+    const uint8_t *cfg_src = (const uint8_t *)m3ApiOffsetToPtr(cfg_off);
+    gpio_cfg_t cfg;
+    memcpy(&cfg.direction, cfg_src + 0, 4);
+    memcpy(&cfg.pull, cfg_src + 4, 4);
+    memcpy(&cfg.drive, cfg_src + 8, 4);
+    memcpy(&cfg.strength, cfg_src + 12, 4);
+    memcpy(&cfg.initial, cfg_src + 16, 4);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, pin, &cfg);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `gpio.deinit`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, dev);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, dev);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `gpio.set`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_set)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, dev);
+    m3ApiGetArg(uint32_t, value);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->set == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->set(binding->ctx, dev, value);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `gpio.get`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_get)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, dev);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->get == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+    uint32_t out;
+
+    int32_t res = drv->get(binding->ctx, dev, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &out, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `gpio.set_interrupt`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_set_interrupt)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, dev);
+    m3ApiGetArg(uint32_t, edge);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->set_interrupt == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->set_interrupt(binding->ctx, dev, edge);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `gpio.wait_event`, see gpio.witx
+static m3ApiRawFunction(wasme_wasm3_gpio_wait_event)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, handles_off);
+    m3ApiGetArg(uint32_t, handles_len);
+    m3ApiGetArg(uint32_t, timeout_us);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const gpio_drv_t *drv = (const gpio_drv_t *)binding->drv;
+    if (drv->wait_event == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(handles_off, (uint64_t)handles_len * 4);
+    int32_t *handles = (int32_t *)m3ApiOffsetToPtr(handles_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 8);
+    gpio_event_t out;
+
+    int32_t res = drv->wait_event(binding->ctx, handles, handles_len, timeout_us, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    uint8_t *dst = (uint8_t *)m3ApiOffsetToPtr(out_off);
+    memcpy(dst + 0, &out.handle, 4);
+    memcpy(dst + 4, &out.edge, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `gpio` functions into a wasm3 module,
+/// dispatching to the `gpio_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_gpio(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "gpio", "init", "i(iiii)", &wasme_wasm3_gpio_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "gpio", "deinit", "i(i)", &wasme_wasm3_gpio_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "gpio", "set", "i(ii)", &wasme_wasm3_gpio_set, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "gpio", "get", "i(ii)", &wasme_wasm3_gpio_get, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "gpio", "set_interrupt", "i(ii)", &wasme_wasm3_gpio_set_interrupt, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "gpio", "wait_event", "i(iiii)", &wasme_wasm3_gpio_wait_event, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
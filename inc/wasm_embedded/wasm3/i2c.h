@@ -0,0 +1,249 @@
+/// Embedded WASM wasm3 I2C host bindings
+///
+/// Generated from i2c.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_I2C_H
+#define WASME_WASM3_I2C_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../i2c.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `i2c.init`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, port);
+    m3ApiGetArg(uint32_t, baud);
+    m3ApiGetArg(int32_t, sda);
+    m3ApiGetArg(int32_t, scl);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, sda, scl);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `i2c.deinit`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `i2c.write`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_write)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, addr);
+    m3ApiGetArg(uint32_t, data_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, m3ApiOffsetToPtr(data_ref), 4);
+    memcpy(&data_len, m3ApiOffsetToPtr(data_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)m3ApiOffsetToPtr(data_off);
+
+    int32_t res = drv->write(binding->ctx, handle, (uint16_t)addr, data, data_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `i2c.read`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_read)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, addr);
+    m3ApiGetArg(uint32_t, buff_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, m3ApiOffsetToPtr(buff_ref), 4);
+    memcpy(&buff_len, m3ApiOffsetToPtr(buff_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)m3ApiOffsetToPtr(buff_off);
+
+    int32_t res = drv->read(binding->ctx, handle, (uint16_t)addr, buff, buff_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `i2c.write_read`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_write_read)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, addr);
+    m3ApiGetArg(uint32_t, data_ref);
+    m3ApiGetArg(uint32_t, buff_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->write_read == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, m3ApiOffsetToPtr(data_ref), 4);
+    memcpy(&data_len, m3ApiOffsetToPtr(data_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)m3ApiOffsetToPtr(data_off);
+
+    WASME_WASM3_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, m3ApiOffsetToPtr(buff_ref), 4);
+    memcpy(&buff_len, m3ApiOffsetToPtr(buff_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)m3ApiOffsetToPtr(buff_off);
+
+    int32_t res = drv->write_read(binding->ctx, handle, (uint16_t)addr, data, data_len, buff, buff_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `i2c.transaction`, see i2c.witx
+static m3ApiRawFunction(wasme_wasm3_i2c_transaction)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, addr);
+    m3ApiGetArg(uint32_t, ops_off);
+    m3ApiGetArg(uint32_t, ops_len);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const i2c_drv_t *drv = (const i2c_drv_t *)binding->drv;
+    if (drv->transaction == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    if (ops_len > WASME_WASM3_MAX_OPS) {
+        m3ApiReturn(WASME_ERRNO_INVALID_ARG);
+    }
+    WASME_WASM3_CHECK_MEM(ops_off, (uint64_t)ops_len * 12);
+    i2c_op_t ops[WASME_WASM3_MAX_OPS];
+    for (uint32_t i = 0; i < ops_len; i++) {
+        const uint8_t *src = (const uint8_t *)m3ApiOffsetToPtr(ops_off + i * 12);
+        memcpy(&ops[i].kind, src + 0, 4);
+        memcpy(&ops[i].len, src + 8, 4);
+        uint32_t ptr_off;
+        memcpy(&ptr_off, src + 4, 4);
+        WASME_WASM3_CHECK_MEM(ptr_off, (uint64_t)ops[i].len * 1);
+        ops[i].ptr = (uint8_t *)m3ApiOffsetToPtr(ptr_off);
+    }
+
+    int32_t res = drv->transaction(binding->ctx, handle, (uint16_t)addr, ops, ops_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `i2c` functions into a wasm3 module,
+/// dispatching to the `i2c_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_i2c(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "i2c", "init", "i(iiiii)", &wasme_wasm3_i2c_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "i2c", "deinit", "i(i)", &wasme_wasm3_i2c_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "i2c", "write", "i(iii)", &wasme_wasm3_i2c_write, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "i2c", "read", "i(iii)", &wasme_wasm3_i2c_read, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "i2c", "write_read", "i(iiii)", &wasme_wasm3_i2c_write_read, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "i2c", "transaction", "i(iiii)", &wasme_wasm3_i2c_transaction, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,240 @@
+/// Embedded WASM wasm3 PWM host bindings
+///
+/// Generated from pwm.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_PWM_H
+#define WASME_WASM3_PWM_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../pwm.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `pwm.init`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, port);
+    m3ApiGetArg(uint32_t, channel);
+    m3ApiGetArg(int32_t, pin);
+    m3ApiGetArg(uint32_t, freq_hz);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, channel, pin, freq_hz);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.deinit`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.set_duty`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_set_duty)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, duty);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->set_duty == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->set_duty(binding->ctx, handle, duty);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.set_frequency`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_set_frequency)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, freq_hz);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->set_frequency == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->set_frequency(binding->ctx, handle, freq_hz);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.enable`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_enable)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->enable == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->enable(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.disable`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_disable)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->disable == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->disable(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `pwm.get_max_duty`, see pwm.witx
+static m3ApiRawFunction(wasme_wasm3_pwm_get_max_duty)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const pwm_drv_t *drv = (const pwm_drv_t *)binding->drv;
+    if (drv->get_max_duty == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+    uint32_t out;
+
+    int32_t res = drv->get_max_duty(binding->ctx, handle, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &out, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `pwm` functions into a wasm3 module,
+/// dispatching to the `pwm_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_pwm(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "pwm", "init", "i(iiiii)", &wasme_wasm3_pwm_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "deinit", "i(i)", &wasme_wasm3_pwm_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "set_duty", "i(ii)", &wasme_wasm3_pwm_set_duty, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "set_frequency", "i(ii)", &wasme_wasm3_pwm_set_frequency, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "enable", "i(i)", &wasme_wasm3_pwm_enable, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "disable", "i(i)", &wasme_wasm3_pwm_disable, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "pwm", "get_max_duty", "i(ii)", &wasme_wasm3_pwm_get_max_duty, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
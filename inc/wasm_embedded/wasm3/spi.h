@@ -0,0 +1,280 @@
+/// Embedded WASM wasm3 SPI host bindings
+///
+/// Generated from spi.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_SPI_H
+#define WASME_WASM3_SPI_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../spi.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `spi.init`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, port);
+    m3ApiGetArg(uint32_t, baud);
+    m3ApiGetArg(int32_t, mosi);
+    m3ApiGetArg(int32_t, miso);
+    m3ApiGetArg(int32_t, sck);
+    m3ApiGetArg(int32_t, cs);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, mosi, miso, sck, cs);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.deinit`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.read`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_read)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, read_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(read_ref, 8);
+    uint32_t read_off, read_len;
+    memcpy(&read_off, m3ApiOffsetToPtr(read_ref), 4);
+    memcpy(&read_len, m3ApiOffsetToPtr(read_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(read_off, (uint64_t)read_len * 1);
+    uint8_t *read = (uint8_t *)m3ApiOffsetToPtr(read_off);
+
+    int32_t res = drv->read(binding->ctx, handle, read, read_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.write`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_write)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, data_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, m3ApiOffsetToPtr(data_ref), 4);
+    memcpy(&data_len, m3ApiOffsetToPtr(data_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)m3ApiOffsetToPtr(data_off);
+
+    int32_t res = drv->write(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.transfer`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_transfer)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, read_ref);
+    m3ApiGetArg(uint32_t, write_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->transfer == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(read_ref, 8);
+    uint32_t read_off, read_len;
+    memcpy(&read_off, m3ApiOffsetToPtr(read_ref), 4);
+    memcpy(&read_len, m3ApiOffsetToPtr(read_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(read_off, (uint64_t)read_len * 1);
+    uint8_t *read = (uint8_t *)m3ApiOffsetToPtr(read_off);
+
+    WASME_WASM3_CHECK_MEM(write_ref, 8);
+    uint32_t write_off, write_len;
+    memcpy(&write_off, m3ApiOffsetToPtr(write_ref), 4);
+    memcpy(&write_len, m3ApiOffsetToPtr(write_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(write_off, (uint64_t)write_len * 1);
+    uint8_t *write = (uint8_t *)m3ApiOffsetToPtr(write_off);
+
+    int32_t res = drv->transfer(binding->ctx, handle, read, read_len, write, write_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.transfer_inplace`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_transfer_inplace)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, data_ref);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->transfer_inplace == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, m3ApiOffsetToPtr(data_ref), 4);
+    memcpy(&data_len, m3ApiOffsetToPtr(data_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)m3ApiOffsetToPtr(data_off);
+
+    int32_t res = drv->transfer_inplace(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `spi.exec`, see spi.witx
+static m3ApiRawFunction(wasme_wasm3_spi_exec)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, data_off);
+    m3ApiGetArg(uint32_t, data_len);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const spi_drv_t *drv = (const spi_drv_t *)binding->drv;
+    if (drv->exec == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    if (data_len > WASME_WASM3_MAX_OPS) {
+        m3ApiReturn(WASME_ERRNO_INVALID_ARG);
+    }
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 12);
+    spi_op_t data[WASME_WASM3_MAX_OPS];
+    for (uint32_t i = 0; i < data_len; i++) {
+        const uint8_t *src = (const uint8_t *)m3ApiOffsetToPtr(data_off + i * 12);
+        memcpy(&data[i].kind, src + 0, 4);
+        memcpy(&data[i].len, src + 8, 4);
+        uint32_t ptr_off;
+        memcpy(&ptr_off, src + 4, 4);
+        WASME_WASM3_CHECK_MEM(ptr_off, (uint64_t)data[i].len * 1);
+        data[i].ptr = (uint8_t *)m3ApiOffsetToPtr(ptr_off);
+    }
+
+    int32_t res = drv->exec(binding->ctx, handle, data, data_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `spi` functions into a wasm3 module,
+/// dispatching to the `spi_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_spi(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "spi", "init", "i(iiiiiii)", &wasme_wasm3_spi_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "deinit", "i(i)", &wasme_wasm3_spi_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "read", "i(ii)", &wasme_wasm3_spi_read, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "write", "i(ii)", &wasme_wasm3_spi_write, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "transfer", "i(iii)", &wasme_wasm3_spi_transfer, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "transfer_inplace", "i(ii)", &wasme_wasm3_spi_transfer_inplace, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "spi", "exec", "i(iii)", &wasme_wasm3_spi_exec, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
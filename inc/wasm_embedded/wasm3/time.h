@@ -0,0 +1,236 @@
+/// Embedded WASM wasm3 TIME host bindings
+///
+/// Generated from time.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_TIME_H
+#define WASME_WASM3_TIME_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../time.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `time.now_us`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_now_us)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->now_us == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 8);
+    uint64_t out;
+
+    int32_t res = drv->now_us(binding->ctx, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &out, 8);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.delay_us`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_delay_us)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, us);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->delay_us == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->delay_us(binding->ctx, us);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.delay_ms`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_delay_ms)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, ms);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->delay_ms == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->delay_ms(binding->ctx, ms);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.alarm_start`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_alarm_start)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint64_t, timeout_us);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_start == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->alarm_start(binding->ctx, timeout_us);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.alarm_remaining`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_alarm_remaining)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_remaining == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 8);
+    uint64_t out;
+
+    int32_t res = drv->alarm_remaining(binding->ctx, handle, &out);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &out, 8);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.alarm_wait`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_alarm_wait)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_wait == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->alarm_wait(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `time.alarm_cancel`, see time.witx
+static m3ApiRawFunction(wasme_wasm3_time_alarm_cancel)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const time_drv_t *drv = (const time_drv_t *)binding->drv;
+    if (drv->alarm_cancel == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->alarm_cancel(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `time` functions into a wasm3 module,
+/// dispatching to the `time_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_time(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "time", "now_us", "i(i)", &wasme_wasm3_time_now_us, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "delay_us", "i(i)", &wasme_wasm3_time_delay_us, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "delay_ms", "i(i)", &wasme_wasm3_time_delay_ms, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "alarm_start", "i(Ii)", &wasme_wasm3_time_alarm_start, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "alarm_remaining", "i(ii)", &wasme_wasm3_time_alarm_remaining, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "alarm_wait", "i(i)", &wasme_wasm3_time_alarm_wait, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "time", "alarm_cancel", "i(i)", &wasme_wasm3_time_alarm_cancel, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,211 @@
+/// Embedded WASM wasm3 UART host bindings
+///
+/// Generated from uart.witx by build.rs, do not edit
+
+#ifndef WASME_WASM3_UART_H
+#define WASME_WASM3_UART_H
+
+#include <stdint.h>
+#include <string.h>
+
+#include "wasm3.h"
+#include "../uart.h"
+#include "common.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/// `uart.init`, see uart.witx
+static m3ApiRawFunction(wasme_wasm3_uart_init)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(uint32_t, port);
+    m3ApiGetArg(uint32_t, baud);
+    m3ApiGetArg(int32_t, tx);
+    m3ApiGetArg(int32_t, rx);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->init == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->init(binding->ctx, port, baud, tx, rx);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `uart.deinit`, see uart.witx
+static m3ApiRawFunction(wasme_wasm3_uart_deinit)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+
+    (void)runtime;
+    (void)_mem;
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->deinit == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    int32_t res = drv->deinit(binding->ctx, handle);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `uart.configure`, see uart.witx
+static m3ApiRawFunction(wasme_wasm3_uart_configure)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, cfg_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->configure == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(cfg_off, 28);
+    const uint8_t *cfg_src = (const uint8_t *)m3ApiOffsetToPtr(cfg_off);
+    uart_cfg_t cfg;
+    memcpy(&cfg.baud, cfg_src + 0, 4);
+    memcpy(&cfg.data_bits, cfg_src + 4, 4);
+    memcpy(&cfg.parity, cfg_src + 8, 4);
+    memcpy(&cfg.stop_bits, cfg_src + 12, 4);
+    memcpy(&cfg.flow_control, cfg_src + 16, 4);
+    memcpy(&cfg.rts, cfg_src + 20, 4);
+    memcpy(&cfg.cts, cfg_src + 24, 4);
+
+    int32_t res = drv->configure(binding->ctx, handle, &cfg);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `uart.write`, see uart.witx
+static m3ApiRawFunction(wasme_wasm3_uart_write)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, flags);
+    m3ApiGetArg(uint32_t, timeout_ms);
+    m3ApiGetArg(uint32_t, data_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->write == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(data_ref, 8);
+    uint32_t data_off, data_len;
+    memcpy(&data_off, m3ApiOffsetToPtr(data_ref), 4);
+    memcpy(&data_len, m3ApiOffsetToPtr(data_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(data_off, (uint64_t)data_len * 1);
+    uint8_t *data = (uint8_t *)m3ApiOffsetToPtr(data_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->write(binding->ctx, handle, flags, timeout_ms, data, data_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// `uart.read`, see uart.witx
+static m3ApiRawFunction(wasme_wasm3_uart_read)
+{
+    m3ApiReturnType(uint32_t);
+    m3ApiGetArg(int32_t, handle);
+    m3ApiGetArg(uint32_t, flags);
+    m3ApiGetArg(uint32_t, timeout_ms);
+    m3ApiGetArg(uint32_t, buff_ref);
+    m3ApiGetArg(uint32_t, out_off);
+
+    const wasme_wasm3_binding_t *binding = (const wasme_wasm3_binding_t *)_ctx->userdata;
+    const uart_drv_t *drv = (const uart_drv_t *)binding->drv;
+    if (drv->read == NULL) {
+        m3ApiReturn(WASME_ERRNO_UNSUPPORTED);
+    }
+
+    WASME_WASM3_CHECK_MEM(buff_ref, 8);
+    uint32_t buff_off, buff_len;
+    memcpy(&buff_off, m3ApiOffsetToPtr(buff_ref), 4);
+    memcpy(&buff_len, m3ApiOffsetToPtr(buff_ref + 4), 4);
+    WASME_WASM3_CHECK_MEM(buff_off, (uint64_t)buff_len * 1);
+    uint8_t *buff = (uint8_t *)m3ApiOffsetToPtr(buff_off);
+
+    WASME_WASM3_CHECK_MEM(out_off, 4);
+
+    int32_t res = drv->read(binding->ctx, handle, flags, timeout_ms, buff, buff_len);
+    if (res < 0) {
+        m3ApiReturn((uint32_t)-res);
+    }
+
+    memcpy(m3ApiOffsetToPtr(out_off), &res, 4);
+    m3ApiReturn(WASME_ERRNO_OK);
+}
+
+/// Link `uart` functions into a wasm3 module,
+/// dispatching to the `uart_drv_t` driver object in `binding`.
+///
+/// Functions not imported by the module are skipped,
+/// `binding` must remain valid for the lifetime of the runtime.
+static inline M3Result wasme_wasm3_link_uart(IM3Module module, const wasme_wasm3_binding_t *binding)
+{
+    M3Result res;
+
+    res = wasme_wasm3_link(module, "uart", "init", "i(iiiii)", &wasme_wasm3_uart_init, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "uart", "deinit", "i(i)", &wasme_wasm3_uart_deinit, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "uart", "configure", "i(ii)", &wasme_wasm3_uart_configure, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "uart", "write", "i(iiiii)", &wasme_wasm3_uart_write, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    res = wasme_wasm3_link(module, "uart", "read", "i(iiiii)", &wasme_wasm3_uart_read, binding);
+    if (res != m3Err_none) {
+        return res;
+    }
+
+    return m3Err_none;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
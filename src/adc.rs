@@ -0,0 +1,102 @@
+//! Low-level ADC API abstraction.
+//!
+//! Provides a common ADC trait for adaptation between specifications and implementations.
+
+use super::Error;
+
+/// ADC voltage reference
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum AdcReference {
+    /// Platform default reference
+    Default,
+    /// Internal reference
+    Internal,
+    /// External reference pin
+    External,
+    /// Supply voltage
+    Supply,
+}
+
+/// ADC context abstraction.
+///
+/// This hides runtime implementation details to simplify implementing ADC contexts.
+/// Hopefully one day generation is improved so we don't _need_ this any more
+pub trait Adc {
+    /// Initialise an ADC channel with resolution (in bits) and reference, returning a handle
+    fn init(
+        &mut self,
+        dev: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error>;
+
+    /// Deinitialise an ADC channel by handle
+    fn deinit(&mut self, handle: i32) -> Result<(), Error>;
+
+    /// Read a single sample by handle
+    fn read(&mut self, handle: i32) -> Result<u16, Error>;
+
+    /// Read samples into the provided buffer at `rate_hz` (0 for as fast as possible) by handle
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error>;
+}
+
+/// Blanket Adc implementation for &mut T where T: Adc
+impl<T: Adc> Adc for &mut T {
+    fn init(
+        &mut self,
+        dev: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error> {
+        <T as Adc>::init(self, dev, channel, pin, resolution, reference)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Adc>::deinit(self, handle)
+    }
+
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        <T as Adc>::read(self, handle)
+    }
+
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error> {
+        <T as Adc>::read_multi(self, handle, rate_hz, buff)
+    }
+}
+
+/// Null [Adc] implementation to simplify engine instantiation
+pub struct NullAdc;
+
+impl Adc for NullAdc {
+    fn init(
+        &mut self,
+        _dev: u32,
+        _channel: u32,
+        _pin: i32,
+        _resolution: u8,
+        _reference: AdcReference,
+    ) -> Result<i32, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn deinit(&mut self, _handle: i32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn read(&mut self, _handle: i32) -> Result<u16, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn read_multi(&mut self, _handle: i32, _rate_hz: u32, _buff: &mut [u16]) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+}
@@ -0,0 +1,51 @@
+//! Conformance test runner, checks a compiled wasm guest against a TOML expectation file.
+//!
+//! Usage: `wasm-embedded-conformance [--entry NAME] GUEST.wasm FIXTURE.toml`
+
+use std::process::ExitCode;
+
+use wasm_embedded_spec::conformance;
+
+const USAGE: &str = "Usage: wasm-embedded-conformance [--entry NAME] GUEST.wasm FIXTURE.toml";
+
+fn main() -> ExitCode {
+    let mut entry = None;
+    let mut files = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "--entry" | "-e" => match args.next() {
+                Some(e) => entry = Some(e),
+                None => {
+                    eprintln!("{}", USAGE);
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => files.push(a),
+        }
+    }
+
+    let (wasm, fixture) = match &files[..] {
+        [w, f] => (w, f),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match conformance::run_file(wasm, fixture, entry.as_deref()) {
+        Ok(()) => {
+            println!("PASS {} ({})", wasm, fixture);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("FAIL {} ({}): {}", wasm, fixture, e);
+            ExitCode::FAILURE
+        }
+    }
+}
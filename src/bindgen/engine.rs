@@ -0,0 +1,463 @@
+//! [Engine] implementation backed by C platform driver objects
+
+use cty::c_void;
+use embedded_hal::digital::PinState;
+
+use super::*;
+use crate::{res_to_result, Engine, Error};
+use crate::{Edge, Gpio, GpioConfig, GpioEvent, I2c, I2cOp, Spi, SpiOp, Uart, UartConfig, UartFlags};
+
+/// Maximum number of operations in a single I2C transaction or SPI exec
+pub const MAX_OPS: usize = 16;
+
+/// [Engine] calling C platform drivers (`gpio_drv_t` etc.).
+///
+/// Each driver function is called with the platform context provided on construction,
+/// with negative return codes mapped to [Error]. Peripherals without a driver (and driver
+/// functions that are `NULL`) return [Error::Unsupported].
+///
+/// ```ignore
+/// let engine = unsafe {
+///     CEngine::new(&platform as *const _ as *const c_void)
+///         .with_gpio(gpio_drv)
+///         .with_spi(spi_drv)
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct CEngine {
+    ctx: *const c_void,
+    gpio: Option<gpio_drv_t>,
+    i2c: Option<i2c_drv_t>,
+    spi: Option<spi_drv_t>,
+    uart: Option<uart_drv_t>,
+}
+
+engine_wrapper!(
+    /// GPIO view of a [CEngine]
+    pub CGpio
+);
+engine_wrapper!(
+    /// I2C view of a [CEngine]
+    pub CI2c
+);
+engine_wrapper!(
+    /// SPI view of a [CEngine]
+    pub CSpi
+);
+engine_wrapper!(
+    /// UART view of a [CEngine]
+    pub CUart
+);
+
+impl CEngine {
+    /// Create a new [CEngine] with the provided platform context and no drivers.
+    ///
+    /// # Safety
+    /// `ctx` must remain valid for the lifetime of the engine, and be valid to pass
+    /// to all driver functions subsequently attached.
+    pub unsafe fn new(ctx: *const c_void) -> Self {
+        Self { ctx, gpio: None, i2c: None, spi: None, uart: None }
+    }
+
+    /// Attach a GPIO driver
+    ///
+    /// # Safety
+    /// Driver functions must be safe to call with the engine context, see [CEngine::new]
+    pub unsafe fn with_gpio(mut self, drv: gpio_drv_t) -> Self {
+        self.gpio = Some(drv);
+        self
+    }
+
+    /// Attach an I2C driver
+    ///
+    /// # Safety
+    /// Driver functions must be safe to call with the engine context, see [CEngine::new]
+    pub unsafe fn with_i2c(mut self, drv: i2c_drv_t) -> Self {
+        self.i2c = Some(drv);
+        self
+    }
+
+    /// Attach an SPI driver
+    ///
+    /// # Safety
+    /// Driver functions must be safe to call with the engine context, see [CEngine::new]
+    pub unsafe fn with_spi(mut self, drv: spi_drv_t) -> Self {
+        self.spi = Some(drv);
+        self
+    }
+
+    /// Attach a UART driver
+    ///
+    /// # Safety
+    /// Driver functions must be safe to call with the engine context, see [CEngine::new]
+    pub unsafe fn with_uart(mut self, drv: uart_drv_t) -> Self {
+        self.uart = Some(drv);
+        self
+    }
+
+    /// Fetch the platform context
+    pub fn ctx(&self) -> *const c_void {
+        self.ctx
+    }
+}
+
+impl Engine for CEngine {
+    type Gpio = CGpio<Self>;
+    type I2c = CI2c<Self>;
+    type Spi = CSpi<Self>;
+    type Uart = CUart<Self>;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        match self.gpio.is_some() {
+            true => Some(CGpio::from_mut(self)),
+            false => None,
+        }
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        match self.i2c.is_some() {
+            true => Some(CI2c::from_mut(self)),
+            false => None,
+        }
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        match self.spi.is_some() {
+            true => Some(CSpi::from_mut(self)),
+            false => None,
+        }
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        match self.uart.is_some() {
+            true => Some(CUart::from_mut(self)),
+            false => None,
+        }
+    }
+}
+
+/// Fetch a driver function, returning [Error::Unsupported] if this is not set
+fn func<F>(f: Option<F>) -> Result<F, Error> {
+    f.ok_or(Error::Unsupported)
+}
+
+/// Convert a result code where only success or failure is expected
+fn res_to_unit(res: i32) -> Result<(), Error> {
+    res_to_result(res).map(|_| ())
+}
+
+impl CGpio<CEngine> {
+    fn drv(&self) -> Result<&gpio_drv_t, Error> {
+        self.0.gpio.as_ref().ok_or(Error::Unsupported)
+    }
+}
+
+impl Gpio for CGpio<CEngine> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        let f = func(self.drv()?.init)?;
+        let cfg = gpio_cfg_t {
+            direction: cfg.direction as u32,
+            pull: cfg.pull as u32,
+            drive: cfg.drive as u32,
+            strength: cfg.strength as u32,
+            initial: match cfg.initial {
+                PinState::Low => gpio_value_e_GPIO_VALUE_LOW,
+                PinState::High => gpio_value_e_GPIO_VALUE_HIGH,
+            },
+        };
+
+        res_to_result(unsafe { f(self.0.ctx, port, pin, &cfg) })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let f = func(self.drv()?.deinit)?;
+        res_to_unit(unsafe { f(self.0.ctx, handle) })
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        let f = func(self.drv()?.set)?;
+        let value = match state {
+            PinState::Low => gpio_value_e_GPIO_VALUE_LOW,
+            PinState::High => gpio_value_e_GPIO_VALUE_HIGH,
+        };
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, value) })
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        let f = func(self.drv()?.get)?;
+        let mut value = 0;
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, &mut value) })?;
+
+        match value {
+            gpio_value_e_GPIO_VALUE_LOW => Ok(PinState::Low),
+            gpio_value_e_GPIO_VALUE_HIGH => Ok(PinState::High),
+            _ => Err(Error::Unexpected),
+        }
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        let f = func(self.drv()?.set_interrupt)?;
+        res_to_unit(unsafe { f(self.0.ctx, handle, edge as u32) })
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        let f = func(self.drv()?.wait_event)?;
+        let mut event = gpio_event_t { handle: 0, edge: 0 };
+
+        // Handles are not modified by the driver
+        let (ptr, len) = (handles.as_ptr() as *mut i32, handles.len() as u32);
+        res_to_unit(unsafe { f(self.0.ctx, ptr, len, timeout_us, &mut event) })?;
+
+        let edge = match event.edge {
+            gpio_edge_e_GPIO_EDGE_NONE => Edge::None,
+            gpio_edge_e_GPIO_EDGE_RISING => Edge::Rising,
+            gpio_edge_e_GPIO_EDGE_FALLING => Edge::Falling,
+            gpio_edge_e_GPIO_EDGE_BOTH => Edge::Both,
+            _ => return Err(Error::Unexpected),
+        };
+
+        Ok(GpioEvent { handle: event.handle, edge })
+    }
+}
+
+impl CI2c<CEngine> {
+    fn drv(&self) -> Result<&i2c_drv_t, Error> {
+        self.0.i2c.as_ref().ok_or(Error::Unsupported)
+    }
+}
+
+impl I2c for CI2c<CEngine> {
+    fn init(&mut self, dev: u32, baud: u32, sda: i32, scl: i32) -> Result<i32, Error> {
+        let f = func(self.drv()?.init)?;
+        res_to_result(unsafe { f(self.0.ctx, dev, baud, sda, scl) })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let f = func(self.drv()?.deinit)?;
+        res_to_unit(unsafe { f(self.0.ctx, handle) })
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.write)?;
+        let (ptr, len) = (data.as_ptr() as *mut u8, data.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, addr, ptr, len) })
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.read)?;
+        let (ptr, len) = (buff.as_mut_ptr(), buff.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, addr, ptr, len) })
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        let f = func(self.drv()?.write_read)?;
+        let (d, d_len) = (data.as_ptr() as *mut u8, data.len() as u32);
+        let (b, b_len) = (buff.as_mut_ptr(), buff.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, addr, d, d_len, b, b_len) })
+    }
+
+    /// Execute a transaction of up to [MAX_OPS] operations,
+    /// falling back to per-operation calls if the driver does not provide `transaction`.
+    ///
+    /// As with [I2c::transaction], fallback writes followed by reads use `write_read`
+    /// to preserve the repeated start.
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        let f = match self.drv()?.transaction {
+            Some(f) => f,
+            None => {
+                let mut ops = ops.iter_mut().peekable();
+                while let Some(op) = ops.next() {
+                    match (op, ops.peek_mut()) {
+                        (I2cOp::Write(data), Some(I2cOp::Read(buff))) => {
+                            self.write_read(handle, addr, data, buff)?;
+                            ops.next();
+                        }
+                        (I2cOp::Write(data), _) => self.write(handle, addr, data)?,
+                        (I2cOp::Read(buff), _) => self.read(handle, addr, buff)?,
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        if ops.len() > MAX_OPS {
+            return Err(Error::InvalidArg);
+        }
+
+        let empty = i2c_op_t { kind: 0, ptr: core::ptr::null_mut(), len: 0 };
+        let mut c_ops = [empty; MAX_OPS];
+        for (c, op) in c_ops.iter_mut().zip(ops.iter_mut()) {
+            let (kind, ptr, len) = match op {
+                I2cOp::Read(b) => (i2c_op_kind_e_I2C_OP_KIND_READ, b.as_mut_ptr(), b.len()),
+                I2cOp::Write(d) => (i2c_op_kind_e_I2C_OP_KIND_WRITE, d.as_ptr() as *mut _, d.len()),
+            };
+            *c = i2c_op_t { kind, ptr, len: len as u32 };
+        }
+
+        let (ptr, len) = (c_ops.as_mut_ptr(), ops.len() as u32);
+        res_to_unit(unsafe { f(self.0.ctx, handle, addr, ptr, len) })
+    }
+}
+
+impl CSpi<CEngine> {
+    fn drv(&self) -> Result<&spi_drv_t, Error> {
+        self.0.spi.as_ref().ok_or(Error::Unsupported)
+    }
+}
+
+impl Spi for CSpi<CEngine> {
+    fn init(
+        &mut self,
+        dev: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        let f = func(self.drv()?.init)?;
+        res_to_result(unsafe { f(self.0.ctx, dev, baud, mosi, miso, sck, cs) })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let f = func(self.drv()?.deinit)?;
+        res_to_unit(unsafe { f(self.0.ctx, handle) })
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.read)?;
+        let (ptr, len) = (data.as_mut_ptr(), data.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, ptr, len) })
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.write)?;
+        let (ptr, len) = (data.as_ptr() as *mut u8, data.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, ptr, len) })
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.transfer)?;
+        let (r, r_len) = (read.as_mut_ptr(), read.len() as u32);
+        let (w, w_len) = (write.as_ptr() as *mut u8, write.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, r, r_len, w, w_len) })
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let f = func(self.drv()?.transfer_inplace)?;
+        let (ptr, len) = (data.as_mut_ptr(), data.len() as u32);
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, ptr, len) })
+    }
+
+    /// Execute up to [MAX_OPS] operations,
+    /// falling back to per-operation calls if the driver does not provide `exec`
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        let f = match self.drv()?.exec {
+            Some(f) => f,
+            None => {
+                for op in ops.iter_mut() {
+                    match op {
+                        SpiOp::Read(buff) => self.read(handle, buff)?,
+                        SpiOp::Write(data) => self.write(handle, data)?,
+                        SpiOp::Transfer(data) => self.transfer_inplace(handle, data)?,
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        if ops.len() > MAX_OPS {
+            return Err(Error::InvalidArg);
+        }
+
+        let empty = spi_op_t { kind: 0, ptr: core::ptr::null_mut(), len: 0 };
+        let mut c_ops = [empty; MAX_OPS];
+        for (c, op) in c_ops.iter_mut().zip(ops.iter_mut()) {
+            let (kind, ptr, len) = match op {
+                SpiOp::Transfer(b) => (spi_op_kind_e_SPI_OP_KIND_TRANSFER, b.as_mut_ptr(), b.len()),
+                SpiOp::Read(b) => (spi_op_kind_e_SPI_OP_KIND_READ, b.as_mut_ptr(), b.len()),
+                SpiOp::Write(d) => (spi_op_kind_e_SPI_OP_KIND_WRITE, d.as_ptr() as *mut _, d.len()),
+            };
+            *c = spi_op_t { kind, ptr, len: len as u32 };
+        }
+
+        let (ptr, len) = (c_ops.as_mut_ptr(), ops.len() as u32);
+        res_to_unit(unsafe { f(self.0.ctx, handle, ptr, len) })
+    }
+}
+
+impl CUart<CEngine> {
+    fn drv(&self) -> Result<&uart_drv_t, Error> {
+        self.0.uart.as_ref().ok_or(Error::Unsupported)
+    }
+}
+
+impl Uart for CUart<CEngine> {
+    fn init(&mut self, dev: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        let f = func(self.drv()?.init)?;
+        res_to_result(unsafe { f(self.0.ctx, dev, baud, tx, rx) })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let f = func(self.drv()?.deinit)?;
+        res_to_unit(unsafe { f(self.0.ctx, handle) })
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        let f = func(self.drv()?.configure)?;
+        let cfg = uart_cfg_t {
+            baud: cfg.baud,
+            data_bits: cfg.data_bits as u32,
+            parity: cfg.parity as u32,
+            stop_bits: cfg.stop_bits as u32,
+            flow_control: cfg.flow_control as u32,
+            rts: cfg.rts,
+            cts: cfg.cts,
+        };
+
+        res_to_unit(unsafe { f(self.0.ctx, handle, &cfg) })
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        let f = func(self.drv()?.write)?;
+        let (ptr, len) = (data.as_ptr() as *mut u8, data.len() as u32);
+
+        let n = res_to_result(unsafe { f(self.0.ctx, handle, flags.0, timeout_ms, ptr, len) })?;
+        Ok(n as usize)
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        let f = func(self.drv()?.read)?;
+        let (ptr, len) = (buff.as_mut_ptr(), buff.len() as u32);
+
+        let n = res_to_result(unsafe { f(self.0.ctx, handle, flags.0, timeout_ms, ptr, len) })?;
+        Ok(n as usize)
+    }
+}
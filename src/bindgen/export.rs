@@ -0,0 +1,519 @@
+//! Export a Rust [Engine] as C platform driver objects
+
+use cty::c_void;
+use embedded_hal::digital::PinState;
+
+use super::*;
+use crate::{error_to_res, Engine, Error};
+use crate::{Direction, Drive, DriveStrength, Edge, FlowControl, GpioConfig, I2cOp, Parity};
+use crate::{Pull, SpiOp, StopBits, UartConfig, UartFlags};
+use crate::{Gpio, I2c, Spi, Uart};
+
+/// C platform driver objects (`gpio_drv_t` etc.) backed by a Rust [Engine].
+///
+/// Driver functions expect [CDrivers::ctx] as their context argument, and are only valid
+/// while the [CDrivers] object is alive. Calls are not synchronised, C runtimes must not
+/// call drivers from multiple threads concurrently.
+///
+/// ```ignore
+/// let drivers = CDrivers::new(Box::new(engine));
+/// let gpio = drivers.gpio();
+///
+/// unsafe { runtime_bind_gpio(drivers.ctx(), &gpio) };
+/// ```
+pub struct CDrivers<E: Engine> {
+    engine: *mut E,
+}
+
+impl<E: Engine> CDrivers<E> {
+    /// Export the provided engine
+    pub fn new(engine: Box<E>) -> Self {
+        Self { engine: Box::into_raw(engine) }
+    }
+
+    /// Opaque context to be passed to driver functions
+    pub fn ctx(&self) -> *const c_void {
+        self.engine as *const c_void
+    }
+
+    /// Fetch the exported engine
+    pub fn engine(&mut self) -> &mut E {
+        unsafe { &mut *self.engine }
+    }
+
+    /// Fetch the GPIO driver object, functions return `WASME_ERRNO_UNSUPPORTED`
+    /// where the engine does not provide a GPIO driver
+    pub fn gpio(&self) -> gpio_drv_t {
+        gpio_drv_t {
+            init: Some(gpio::init::<E>),
+            deinit: Some(gpio::deinit::<E>),
+            set: Some(gpio::set::<E>),
+            get: Some(gpio::get::<E>),
+            set_interrupt: Some(gpio::set_interrupt::<E>),
+            wait_event: Some(gpio::wait_event::<E>),
+        }
+    }
+
+    /// Fetch the I2C driver object
+    pub fn i2c(&self) -> i2c_drv_t {
+        i2c_drv_t {
+            init: Some(i2c::init::<E>),
+            deinit: Some(i2c::deinit::<E>),
+            write: Some(i2c::write::<E>),
+            read: Some(i2c::read::<E>),
+            write_read: Some(i2c::write_read::<E>),
+            transaction: Some(i2c::transaction::<E>),
+        }
+    }
+
+    /// Fetch the SPI driver object
+    pub fn spi(&self) -> spi_drv_t {
+        spi_drv_t {
+            init: Some(spi::init::<E>),
+            deinit: Some(spi::deinit::<E>),
+            read: Some(spi::read::<E>),
+            write: Some(spi::write::<E>),
+            transfer: Some(spi::transfer::<E>),
+            transfer_inplace: Some(spi::transfer_inplace::<E>),
+            exec: Some(spi::exec::<E>),
+        }
+    }
+
+    /// Fetch the UART driver object
+    pub fn uart(&self) -> uart_drv_t {
+        uart_drv_t {
+            init: Some(uart::init::<E>),
+            deinit: Some(uart::deinit::<E>),
+            configure: Some(uart::configure::<E>),
+            write: Some(uart::write::<E>),
+            read: Some(uart::read::<E>),
+        }
+    }
+
+    /// Release the exported engine, invalidating any driver objects
+    pub fn into_inner(self) -> Box<E> {
+        let engine = unsafe { Box::from_raw(self.engine) };
+        core::mem::forget(self);
+        engine
+    }
+}
+
+impl<E: Engine> Drop for CDrivers<E> {
+    fn drop(&mut self) {
+        let _ = unsafe { Box::from_raw(self.engine) };
+    }
+}
+
+/// Call a driver on the engine behind a C context, mapping the result to a result code.
+///
+/// Null contexts are rejected, and panics are caught (as unwinding into C is undefined)
+/// and reported as [Error::Failed].
+unsafe fn call<E: Engine, D: ?Sized>(
+    ctx: *const c_void,
+    driver: impl FnOnce(&mut E) -> Option<&mut D>,
+    f: impl FnOnce(&mut D) -> Result<i32, Error>,
+) -> i32 {
+    if ctx.is_null() {
+        return error_to_res(&Error::InvalidArg);
+    }
+    let engine = &mut *(ctx as *mut E);
+
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match driver(engine) {
+        Some(d) => f(d),
+        None => Err(Error::Unsupported),
+    }));
+    let res = match res {
+        Ok(r) => r,
+        Err(_) => {
+            log::error!("Driver call panicked");
+            Err(Error::Failed)
+        }
+    };
+
+    match res {
+        Ok(v) => v,
+        Err(e) => error_to_res(&e),
+    }
+}
+
+/// Borrow a C buffer, rejecting null pointers with a non-zero length
+unsafe fn slice<'a, T>(ptr: *const T, len: u32) -> Result<&'a [T], Error> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(Error::InvalidArg),
+        (false, _) => Ok(core::slice::from_raw_parts(ptr, len as usize)),
+    }
+}
+
+/// Mutably borrow a C buffer, rejecting null pointers with a non-zero length
+unsafe fn slice_mut<'a, T>(ptr: *mut T, len: u32) -> Result<&'a mut [T], Error> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&mut []),
+        (true, _) => Err(Error::InvalidArg),
+        (false, _) => Ok(core::slice::from_raw_parts_mut(ptr, len as usize)),
+    }
+}
+
+/// Write an output value, rejecting null pointers
+unsafe fn out<T>(ptr: *mut T, v: T) -> Result<i32, Error> {
+    match ptr.is_null() {
+        true => Err(Error::InvalidArg),
+        false => {
+            ptr.write(v);
+            Ok(0)
+        }
+    }
+}
+
+mod gpio {
+    use super::*;
+
+    fn config(cfg: &gpio_cfg_t) -> Result<GpioConfig, Error> {
+        let direction = match cfg.direction {
+            gpio_mode_e_GPIO_MODE_INPUT => Direction::Input,
+            gpio_mode_e_GPIO_MODE_OUTPUT => Direction::Output,
+            _ => return Err(Error::InvalidArg),
+        };
+        let pull = match cfg.pull {
+            gpio_pull_e_GPIO_PULL_NONE => Pull::None,
+            gpio_pull_e_GPIO_PULL_UP => Pull::Up,
+            gpio_pull_e_GPIO_PULL_DOWN => Pull::Down,
+            _ => return Err(Error::InvalidArg),
+        };
+        let drive = match cfg.drive {
+            gpio_drive_e_GPIO_DRIVE_PUSH_PULL => Drive::PushPull,
+            gpio_drive_e_GPIO_DRIVE_OPEN_DRAIN => Drive::OpenDrain,
+            _ => return Err(Error::InvalidArg),
+        };
+        let strength = match cfg.strength {
+            gpio_drive_strength_e_GPIO_DRIVE_STRENGTH_DEFAULT => DriveStrength::Default,
+            gpio_drive_strength_e_GPIO_DRIVE_STRENGTH_LOW => DriveStrength::Low,
+            gpio_drive_strength_e_GPIO_DRIVE_STRENGTH_MEDIUM => DriveStrength::Medium,
+            gpio_drive_strength_e_GPIO_DRIVE_STRENGTH_HIGH => DriveStrength::High,
+            _ => return Err(Error::InvalidArg),
+        };
+
+        Ok(GpioConfig { direction, pull, drive, strength, initial: state(cfg.initial)? })
+    }
+
+    fn state(value: u32) -> Result<PinState, Error> {
+        match value {
+            gpio_value_e_GPIO_VALUE_LOW => Ok(PinState::Low),
+            gpio_value_e_GPIO_VALUE_HIGH => Ok(PinState::High),
+            _ => Err(Error::InvalidArg),
+        }
+    }
+
+    pub(super) unsafe extern "C" fn init<E: Engine>(
+        ctx: *const c_void,
+        port: i32,
+        pin: i32,
+        cfg: *const gpio_cfg_t,
+    ) -> i32 {
+        call(ctx, E::gpio, |d| {
+            let cfg = config(cfg.as_ref().ok_or(Error::InvalidArg)?)?;
+            d.init(port, pin, &cfg)
+        })
+    }
+
+    pub(super) unsafe extern "C" fn deinit<E: Engine>(ctx: *const c_void, dev: i32) -> i32 {
+        call(ctx, E::gpio, |d| d.deinit(dev).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn set<E: Engine>(
+        ctx: *const c_void,
+        dev: i32,
+        value: u32,
+    ) -> i32 {
+        call(ctx, E::gpio, |d| d.set(dev, state(value)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn get<E: Engine>(
+        ctx: *const c_void,
+        dev: i32,
+        value: *mut u32,
+    ) -> i32 {
+        call(ctx, E::gpio, |d| match d.get(dev)? {
+            PinState::Low => out(value, 0),
+            PinState::High => out(value, 1),
+        })
+    }
+
+    pub(super) unsafe extern "C" fn set_interrupt<E: Engine>(
+        ctx: *const c_void,
+        dev: i32,
+        edge: u32,
+    ) -> i32 {
+        let edge = match edge {
+            gpio_edge_e_GPIO_EDGE_NONE => Edge::None,
+            gpio_edge_e_GPIO_EDGE_RISING => Edge::Rising,
+            gpio_edge_e_GPIO_EDGE_FALLING => Edge::Falling,
+            gpio_edge_e_GPIO_EDGE_BOTH => Edge::Both,
+            _ => return error_to_res(&Error::InvalidArg),
+        };
+
+        call(ctx, E::gpio, |d| d.set_interrupt(dev, edge).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn wait_event<E: Engine>(
+        ctx: *const c_void,
+        handles: *mut i32,
+        handles_len: u32,
+        timeout_us: u32,
+        event: *mut gpio_event_t,
+    ) -> i32 {
+        call(ctx, E::gpio, |d| {
+            let e = d.wait_event(slice(handles, handles_len)?, timeout_us)?;
+            out(event, gpio_event_t { handle: e.handle, edge: e.edge as u32 })
+        })
+    }
+}
+
+mod i2c {
+    use super::*;
+
+    pub(super) unsafe extern "C" fn init<E: Engine>(
+        ctx: *const c_void,
+        port: u32,
+        baud: u32,
+        sda: i32,
+        scl: i32,
+    ) -> i32 {
+        call(ctx, E::i2c, |d| d.init(port, baud, sda, scl))
+    }
+
+    pub(super) unsafe extern "C" fn deinit<E: Engine>(ctx: *const c_void, handle: i32) -> i32 {
+        call(ctx, E::i2c, |d| d.deinit(handle).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn write<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        addr: u16,
+        data: *mut u8,
+        data_len: u32,
+    ) -> i32 {
+        call(ctx, E::i2c, |d| d.write(handle, addr, slice(data, data_len)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn read<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        addr: u16,
+        buff: *mut u8,
+        buff_len: u32,
+    ) -> i32 {
+        call(ctx, E::i2c, |d| d.read(handle, addr, slice_mut(buff, buff_len)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn write_read<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        addr: u16,
+        data: *mut u8,
+        data_len: u32,
+        buff: *mut u8,
+        buff_len: u32,
+    ) -> i32 {
+        call(ctx, E::i2c, |d| {
+            let (data, buff) = (slice(data, data_len)?, slice_mut(buff, buff_len)?);
+            d.write_read(handle, addr, data, buff).map(|_| 0)
+        })
+    }
+
+    pub(super) unsafe extern "C" fn transaction<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        addr: u16,
+        ops: *mut i2c_op_t,
+        ops_len: u32,
+    ) -> i32 {
+        call(ctx, E::i2c, |d| {
+            let mut ops = slice(ops, ops_len)?
+                .iter()
+                .map(|o| match o.kind {
+                    i2c_op_kind_e_I2C_OP_KIND_READ => Ok(I2cOp::Read(slice_mut(o.ptr, o.len)?)),
+                    i2c_op_kind_e_I2C_OP_KIND_WRITE => Ok(I2cOp::Write(slice(o.ptr, o.len)?)),
+                    _ => Err(Error::InvalidArg),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            d.transaction(handle, addr, &mut ops).map(|_| 0)
+        })
+    }
+}
+
+mod spi {
+    use super::*;
+
+    pub(super) unsafe extern "C" fn init<E: Engine>(
+        ctx: *const c_void,
+        port: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| d.init(port, baud, mosi, miso, sck, cs))
+    }
+
+    pub(super) unsafe extern "C" fn deinit<E: Engine>(ctx: *const c_void, handle: i32) -> i32 {
+        call(ctx, E::spi, |d| d.deinit(handle).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn read<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        read: *mut u8,
+        read_len: u32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| d.read(handle, slice_mut(read, read_len)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn write<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        data: *mut u8,
+        data_len: u32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| d.write(handle, slice(data, data_len)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn transfer<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        read: *mut u8,
+        read_len: u32,
+        write: *mut u8,
+        write_len: u32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| {
+            let (read, write) = (slice_mut(read, read_len)?, slice(write, write_len)?);
+            d.transfer(handle, read, write).map(|_| 0)
+        })
+    }
+
+    pub(super) unsafe extern "C" fn transfer_inplace<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        data: *mut u8,
+        data_len: u32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| d.transfer_inplace(handle, slice_mut(data, data_len)?).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn exec<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        data: *mut spi_op_t,
+        data_len: u32,
+    ) -> i32 {
+        call(ctx, E::spi, |d| {
+            let mut ops = slice(data, data_len)?
+                .iter()
+                .map(|o| match o.kind {
+                    spi_op_kind_e_SPI_OP_KIND_TRANSFER => {
+                        Ok(SpiOp::Transfer(slice_mut(o.ptr, o.len)?))
+                    }
+                    spi_op_kind_e_SPI_OP_KIND_READ => Ok(SpiOp::Read(slice_mut(o.ptr, o.len)?)),
+                    spi_op_kind_e_SPI_OP_KIND_WRITE => Ok(SpiOp::Write(slice(o.ptr, o.len)?)),
+                    _ => Err(Error::InvalidArg),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            d.exec(handle, &mut ops).map(|_| 0)
+        })
+    }
+}
+
+mod uart {
+    use super::*;
+
+    fn config(cfg: &uart_cfg_t) -> Result<UartConfig, Error> {
+        let data_bits = match cfg.data_bits {
+            5..=9 => cfg.data_bits as u8,
+            _ => return Err(Error::InvalidArg),
+        };
+        let parity = match cfg.parity {
+            uart_parity_e_UART_PARITY_NONE => Parity::None,
+            uart_parity_e_UART_PARITY_EVEN => Parity::Even,
+            uart_parity_e_UART_PARITY_ODD => Parity::Odd,
+            _ => return Err(Error::InvalidArg),
+        };
+        let stop_bits = match cfg.stop_bits {
+            uart_stop_bits_e_UART_STOP_BITS_ONE => StopBits::One,
+            uart_stop_bits_e_UART_STOP_BITS_ONE_POINT_FIVE => StopBits::OnePointFive,
+            uart_stop_bits_e_UART_STOP_BITS_TWO => StopBits::Two,
+            _ => return Err(Error::InvalidArg),
+        };
+        let flow_control = match cfg.flow_control {
+            uart_flow_control_e_UART_FLOW_CONTROL_NONE => FlowControl::None,
+            uart_flow_control_e_UART_FLOW_CONTROL_RTS_CTS => FlowControl::RtsCts,
+            _ => return Err(Error::InvalidArg),
+        };
+
+        Ok(UartConfig {
+            baud: cfg.baud,
+            data_bits,
+            parity,
+            stop_bits,
+            flow_control,
+            rts: cfg.rts,
+            cts: cfg.cts,
+        })
+    }
+
+    pub(super) unsafe extern "C" fn init<E: Engine>(
+        ctx: *const c_void,
+        port: u32,
+        baud: u32,
+        tx: i32,
+        rx: i32,
+    ) -> i32 {
+        call(ctx, E::uart, |d| d.init(port, baud, tx, rx))
+    }
+
+    pub(super) unsafe extern "C" fn deinit<E: Engine>(ctx: *const c_void, handle: i32) -> i32 {
+        call(ctx, E::uart, |d| d.deinit(handle).map(|_| 0))
+    }
+
+    pub(super) unsafe extern "C" fn configure<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        cfg: *const uart_cfg_t,
+    ) -> i32 {
+        call(ctx, E::uart, |d| {
+            let cfg = config(cfg.as_ref().ok_or(Error::InvalidArg)?)?;
+            d.configure(handle, &cfg).map(|_| 0)
+        })
+    }
+
+    pub(super) unsafe extern "C" fn write<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        flags: u32,
+        timeout_ms: u32,
+        data: *mut u8,
+        data_len: u32,
+    ) -> i32 {
+        call(ctx, E::uart, |d| {
+            let n = d.write(handle, UartFlags(flags), timeout_ms, slice(data, data_len)?)?;
+            Ok(n as i32)
+        })
+    }
+
+    pub(super) unsafe extern "C" fn read<E: Engine>(
+        ctx: *const c_void,
+        handle: i32,
+        flags: u32,
+        timeout_ms: u32,
+        buff: *mut u8,
+        buff_len: u32,
+    ) -> i32 {
+        call(ctx, E::uart, |d| {
+            let n = d.read(handle, UartFlags(flags), timeout_ms, slice_mut(buff, buff_len)?)?;
+            Ok(n as i32)
+        })
+    }
+}
@@ -1,13 +1,28 @@
 //! Bindgen based C API definitions
 
-#![allow(
-    non_snake_case,
-    non_camel_case_types,
-    non_upper_case_globals,
-    clippy::all
-)]
-
 pub use cty::c_char;
 
-// Include generated bindings if enabled
-include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+/// Generated C bindings
+mod sys {
+    #![allow(
+        non_snake_case,
+        non_camel_case_types,
+        non_upper_case_globals,
+        clippy::all
+    )]
+
+    // Include generated bindings if enabled
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+pub use sys::*;
+
+// Generated enum constants (such as `i2c_op_kind_e_I2C_OP_KIND_READ`) are used as patterns
+#[allow(non_upper_case_globals)]
+mod engine;
+pub use engine::*;
+
+#[cfg(feature = "std")]
+#[allow(non_upper_case_globals)]
+mod export;
+#[cfg(feature = "std")]
+pub use export::*;
@@ -0,0 +1,693 @@
+//! Board descriptions for pre-configured devices.
+//!
+//! A [Board] describes the buses and pins a board exposes, loaded from TOML:
+//!
+//! ```toml
+//! name = "example"
+//!
+//! [[i2c]]
+//! name = "sensor_i2c"
+//! port = 0
+//! baud = 400000
+//! sda = 4
+//! scl = 5
+//!
+//! [[spi]]
+//! name = "spi0"
+//! aliases = [ "display_spi" ]
+//! port = 0
+//! mosi = 19
+//! miso = 16
+//! sck = 18
+//! cs = 17
+//!
+//! [[uart]]
+//! name = "console"
+//! port = 0
+//! tx = 0
+//! rx = 1
+//!
+//! [[gpio]]
+//! name = "led0"
+//! port = 0
+//! pin = 25
+//! direction = "output"
+//! ```
+//!
+//! [BoardEngine] wraps an [Engine] with a [Board], opening named devices for
+//! [Device] enumeration and rejecting `init` calls for devices the board does not expose.
+//! I2C, SPI and UART pins passed as -1 and bauds passed as 0 are replaced with the board
+//! configuration, GPIO pins must match a board pin exactly.
+//!
+//! Each board device is opened at most once until released with `deinit`. `init` rejects
+//! devices that are already open, while [BoardEngine::handle] and [Device] enumeration open
+//! devices on first use and otherwise return the existing handle.
+
+use std::path::Path;
+
+use embedded_hal::digital::PinState;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Board description, see [crate::board] for the file format
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Board {
+    /// Board name
+    #[serde(default)]
+    pub name: String,
+    /// GPIO pins exposed by the board
+    #[serde(default)]
+    pub gpio: Vec<GpioDesc>,
+    /// I2C buses exposed by the board
+    #[serde(default)]
+    pub i2c: Vec<I2cDesc>,
+    /// SPI devices exposed by the board
+    #[serde(default)]
+    pub spi: Vec<SpiDesc>,
+    /// UARTs exposed by the board
+    #[serde(default)]
+    pub uart: Vec<UartDesc>,
+}
+
+/// Board GPIO pin description
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GpioDesc {
+    /// Pin name
+    pub name: String,
+    /// Alternative names for the pin
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// GPIO port
+    pub port: i32,
+    /// GPIO pin
+    pub pin: i32,
+    /// Pin direction, used for enumeration
+    pub direction: Direction,
+    /// Pull resistor
+    #[serde(default = "default_pull")]
+    pub pull: Pull,
+}
+
+/// Board I2C bus description
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct I2cDesc {
+    /// Bus name
+    pub name: String,
+    /// Alternative names for the bus
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// I2C port
+    pub port: u32,
+    /// Default baud rate
+    #[serde(default = "default_i2c_baud")]
+    pub baud: u32,
+    /// SDA pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub sda: i32,
+    /// SCL pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub scl: i32,
+}
+
+/// Board SPI device description
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpiDesc {
+    /// Device name
+    pub name: String,
+    /// Alternative names for the device
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// SPI port
+    pub port: u32,
+    /// Default baud rate
+    #[serde(default = "default_spi_baud")]
+    pub baud: u32,
+    /// MOSI pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub mosi: i32,
+    /// MISO pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub miso: i32,
+    /// SCK pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub sck: i32,
+    /// CS pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub cs: i32,
+}
+
+/// Board UART description
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct UartDesc {
+    /// UART name
+    pub name: String,
+    /// Alternative names for the UART
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// UART port
+    pub port: u32,
+    /// Default baud rate
+    #[serde(default = "default_uart_baud")]
+    pub baud: u32,
+    /// TX pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub tx: i32,
+    /// RX pin, -1 for platform default
+    #[serde(default = "default_pin")]
+    pub rx: i32,
+}
+
+fn default_pin() -> i32 {
+    -1
+}
+
+fn default_pull() -> Pull {
+    Pull::None
+}
+
+fn default_i2c_baud() -> u32 {
+    100_000
+}
+
+fn default_spi_baud() -> u32 {
+    1_000_000
+}
+
+fn default_uart_baud() -> u32 {
+    115_200
+}
+
+/// Match a requested pin against a board pin, -1 requests the board pin
+fn pin_matches(requested: i32, board: i32) -> bool {
+    requested == -1 || requested == board
+}
+
+/// Check whether a name or one of the aliases matches
+fn name_matches(n: &str, name: &str, aliases: &[String]) -> bool {
+    n == name || aliases.iter().any(|a| a == n)
+}
+
+/// Find the first closed board device matching `f`, returning its index.
+///
+/// Returns [Error::NoDevice] if no devices match, or [Error::InvalidArg] if all matching
+/// devices are already open.
+fn find_closed<D>(
+    descs: &[D],
+    handles: &[Option<i32>],
+    f: impl Fn(&D) -> bool,
+) -> Result<usize, Error> {
+    let mut matched = descs.iter().enumerate().filter(|(_, d)| f(d)).map(|(i, _)| i).peekable();
+    if matched.peek().is_none() {
+        return Err(Error::NoDevice);
+    }
+    matched.find(|i| handles[*i].is_none()).ok_or(Error::InvalidArg)
+}
+
+/// Describe a [find_closed] error for logging
+fn unavailable(e: &Error) -> &'static str {
+    match e {
+        Error::InvalidArg => "already open",
+        _ => "not available on board",
+    }
+}
+
+impl Board {
+    /// Load a board description from a TOML file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
+        let s = std::fs::read_to_string(path)?;
+        let b = toml::from_str(&s)?;
+        Ok(b)
+    }
+}
+
+impl core::str::FromStr for Board {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+/// Configuration-backed [Engine] wrapper, see [crate::board]
+pub struct BoardEngine<E: Engine> {
+    board: Board,
+    inner: E,
+    gpio: Vec<Option<i32>>,
+    i2c: Vec<Option<i32>>,
+    spi: Vec<Option<i32>>,
+    uart: Vec<Option<i32>>,
+}
+
+impl<E: Engine> BoardEngine<E> {
+    /// Create a new [BoardEngine] wrapping the provided engine
+    pub fn new(board: Board, inner: E) -> Self {
+        Self {
+            gpio: vec![None; board.gpio.len()],
+            i2c: vec![None; board.i2c.len()],
+            spi: vec![None; board.spi.len()],
+            uart: vec![None; board.uart.len()],
+            board,
+            inner,
+        }
+    }
+
+    /// Fetch the board description
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Fetch the wrapped engine
+    pub fn inner(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Fetch the handle for a named board device, opening the device if not already open.
+    ///
+    /// Devices already opened by `init` or enumeration return the existing handle.
+    pub fn handle(&mut self, name: &str) -> Result<i32, Error> {
+        let b = &self.board;
+
+        if let Some(i) = b.gpio.iter().position(|d| name_matches(name, &d.name, &d.aliases)) {
+            self.open_gpio(i)
+        } else if let Some(i) = b.i2c.iter().position(|d| name_matches(name, &d.name, &d.aliases)) {
+            self.open_i2c(i)
+        } else if let Some(i) = b.spi.iter().position(|d| name_matches(name, &d.name, &d.aliases)) {
+            self.open_spi(i)
+        } else if let Some(i) = b.uart.iter().position(|d| name_matches(name, &d.name, &d.aliases)) {
+            self.open_uart(i)
+        } else {
+            Err(Error::NoDevice)
+        }
+    }
+
+    fn open_gpio(&mut self, i: usize) -> Result<i32, Error> {
+        if let Some(h) = self.gpio[i] {
+            return Ok(h);
+        }
+
+        let d = &self.board.gpio[i];
+        let cfg = GpioConfig {
+            direction: d.direction,
+            pull: d.pull,
+            ..GpioConfig::input()
+        };
+
+        let h = self.inner.gpio().ok_or(Error::Unsupported)?.init(d.port, d.pin, &cfg)?;
+        self.gpio[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn open_i2c(&mut self, i: usize) -> Result<i32, Error> {
+        if let Some(h) = self.i2c[i] {
+            return Ok(h);
+        }
+
+        let d = &self.board.i2c[i];
+        let h = self.inner.i2c().ok_or(Error::Unsupported)?.init(d.port, d.baud, d.sda, d.scl)?;
+        self.i2c[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn open_spi(&mut self, i: usize) -> Result<i32, Error> {
+        if let Some(h) = self.spi[i] {
+            return Ok(h);
+        }
+
+        let d = &self.board.spi[i];
+        let h = self.inner.spi().ok_or(Error::Unsupported)?
+            .init(d.port, d.baud, d.mosi, d.miso, d.sck, d.cs)?;
+        self.spi[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn open_uart(&mut self, i: usize) -> Result<i32, Error> {
+        if let Some(h) = self.uart[i] {
+            return Ok(h);
+        }
+
+        let d = &self.board.uart[i];
+        let h = self.inner.uart().ok_or(Error::Unsupported)?.init(d.port, d.baud, d.tx, d.rx)?;
+        self.uart[i] = Some(h);
+
+        Ok(h)
+    }
+
+    /// Forget an opened handle once closed by deinit
+    fn forget(handles: &mut [Option<i32>], handle: i32) {
+        handles.iter_mut().filter(|h| **h == Some(handle)).for_each(|h| *h = None);
+    }
+}
+
+impl<E: Engine> Engine for BoardEngine<E> {
+    type Gpio = BoardGpio<Self>;
+    type I2c = BoardI2c<Self>;
+    type Spi = BoardSpi<Self>;
+    type Uart = BoardUart<Self>;
+    type Adc = E::Adc;
+    type Pwm = E::Pwm;
+    type Time = E::Time;
+    type Device = BoardDevice<Self>;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        self.inner.gpio()?;
+        Some(BoardGpio::from_mut(self))
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        self.inner.i2c()?;
+        Some(BoardI2c::from_mut(self))
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        self.inner.spi()?;
+        Some(BoardSpi::from_mut(self))
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        self.inner.uart()?;
+        Some(BoardUart::from_mut(self))
+    }
+
+    fn adc(&mut self) -> Option<&mut Self::Adc> {
+        self.inner.adc()
+    }
+
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> {
+        self.inner.pwm()
+    }
+
+    fn time(&mut self) -> Option<&mut Self::Time> {
+        self.inner.time()
+    }
+
+    fn device(&mut self) -> Option<&mut Self::Device> {
+        Some(BoardDevice::from_mut(self))
+    }
+}
+
+engine_wrapper!(
+    /// [Gpio] view of a [BoardEngine], limited to board pins
+    pub BoardGpio
+);
+engine_wrapper!(
+    /// [I2c] view of a [BoardEngine], limited to board buses
+    pub BoardI2c
+);
+engine_wrapper!(
+    /// [Spi] view of a [BoardEngine], limited to board devices
+    pub BoardSpi
+);
+engine_wrapper!(
+    /// [Uart] view of a [BoardEngine], limited to board UARTs
+    pub BoardUart
+);
+engine_wrapper!(
+    /// [Device] view of a [BoardEngine], enumerating board devices
+    pub BoardDevice
+);
+
+impl<E: Engine> BoardGpio<BoardEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Gpio, Error> {
+        self.0.inner.gpio().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Gpio for BoardGpio<BoardEngine<E>> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        let found = find_closed(&self.0.board.gpio, &self.0.gpio, |d| {
+            d.port == port && d.pin == pin
+        });
+        let i = match found {
+            Ok(i) => i,
+            Err(e) => {
+                log::warn!("GPIO port: {} pin: {} {}", port, pin, unavailable(&e));
+                return Err(e);
+            }
+        };
+
+        let h = self.drv()?.init(port, pin, cfg)?;
+        self.0.gpio[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)?;
+        BoardEngine::<E>::forget(&mut self.0.gpio, handle);
+        Ok(())
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        self.drv()?.set(handle, state)
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        self.drv()?.get(handle)
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        self.drv()?.set_interrupt(handle, edge)
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        self.drv()?.wait_event(handles, timeout_us)
+    }
+}
+
+impl<E: Engine> BoardI2c<BoardEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::I2c, Error> {
+        self.0.inner.i2c().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> I2c for BoardI2c<BoardEngine<E>> {
+    fn init(&mut self, dev: u32, baud: u32, sda: i32, sck: i32) -> Result<i32, Error> {
+        let found = find_closed(&self.0.board.i2c, &self.0.i2c, |d| {
+            d.port == dev && pin_matches(sda, d.sda) && pin_matches(sck, d.scl)
+        });
+        let i = match found {
+            Ok(i) => i,
+            Err(e) => {
+                log::warn!("I2C port: {} (sda: {} scl: {}) {}", dev, sda, sck, unavailable(&e));
+                return Err(e);
+            }
+        };
+
+        let d = self.0.board.i2c[i].clone();
+        let baud = if baud == 0 { d.baud } else { baud };
+
+        let h = self.drv()?.init(dev, baud, d.sda, d.scl)?;
+        self.0.i2c[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)?;
+        BoardEngine::<E>::forget(&mut self.0.i2c, handle);
+        Ok(())
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        self.drv()?.write(handle, addr, data)
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.read(handle, addr, buff)
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        self.drv()?.write_read(handle, addr, data, buff)
+    }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        self.drv()?.transaction(handle, addr, ops)
+    }
+}
+
+impl<E: Engine> BoardSpi<BoardEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Spi, Error> {
+        self.0.inner.spi().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Spi for BoardSpi<BoardEngine<E>> {
+    fn init(
+        &mut self,
+        dev: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        let found = find_closed(&self.0.board.spi, &self.0.spi, |d| {
+            d.port == dev
+                && pin_matches(mosi, d.mosi)
+                && pin_matches(miso, d.miso)
+                && pin_matches(sck, d.sck)
+                && pin_matches(cs, d.cs)
+        });
+        let i = match found {
+            Ok(i) => i,
+            Err(e) => {
+                log::warn!(
+                    "SPI port: {} (mosi: {} miso: {} sck: {} cs: {}) {}",
+                    dev, mosi, miso, sck, cs, unavailable(&e)
+                );
+                return Err(e);
+            }
+        };
+
+        let d = self.0.board.spi[i].clone();
+        let baud = if baud == 0 { d.baud } else { baud };
+
+        let h = self.drv()?.init(dev, baud, d.mosi, d.miso, d.sck, d.cs)?;
+        self.0.spi[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)?;
+        BoardEngine::<E>::forget(&mut self.0.spi, handle);
+        Ok(())
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.read(handle, data)
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        self.drv()?.write(handle, data)
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        self.drv()?.transfer(handle, read, write)
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.transfer_inplace(handle, data)
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        self.drv()?.exec(handle, ops)
+    }
+}
+
+impl<E: Engine> BoardUart<BoardEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Uart, Error> {
+        self.0.inner.uart().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Uart for BoardUart<BoardEngine<E>> {
+    fn init(&mut self, dev: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        let found = find_closed(&self.0.board.uart, &self.0.uart, |d| {
+            d.port == dev && pin_matches(tx, d.tx) && pin_matches(rx, d.rx)
+        });
+        let i = match found {
+            Ok(i) => i,
+            Err(e) => {
+                log::warn!("UART port: {} (tx: {} rx: {}) {}", dev, tx, rx, unavailable(&e));
+                return Err(e);
+            }
+        };
+
+        let d = self.0.board.uart[i].clone();
+        let baud = if baud == 0 { d.baud } else { baud };
+
+        let h = self.drv()?.init(dev, baud, d.tx, d.rx)?;
+        self.0.uart[i] = Some(h);
+
+        Ok(h)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)?;
+        BoardEngine::<E>::forget(&mut self.0.uart, handle);
+        Ok(())
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        self.drv()?.configure(handle, cfg)
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        self.drv()?.write(handle, flags, timeout_ms, data)
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.drv()?.read(handle, flags, timeout_ms, buff)
+    }
+}
+
+impl<E: Engine> BoardDevice<BoardEngine<E>> {
+    /// Open board devices by index, filling the provided handles and returning the number of
+    /// devices available. Devices past the end of `handles` are not opened.
+    fn enumerate(
+        &mut self,
+        idx: &[usize],
+        open: fn(&mut BoardEngine<E>, usize) -> Result<i32, Error>,
+        handles: &mut [i32],
+    ) -> Result<usize, Error> {
+        for (i, v) in idx.iter().zip(handles.iter_mut()) {
+            *v = open(&mut self.0, *i)?;
+        }
+
+        Ok(idx.len())
+    }
+
+    /// Fetch indices of board GPIOs with the provided direction
+    fn gpio_idx(&self, direction: Direction) -> Vec<usize> {
+        self.0.board.gpio.iter()
+            .enumerate()
+            .filter(|(_, d)| d.direction == direction)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl<E: Engine> Device for BoardDevice<BoardEngine<E>> {
+    fn spi(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        let idx: Vec<_> = (0..self.0.board.spi.len()).collect();
+        self.enumerate(&idx, BoardEngine::open_spi, handles)
+    }
+
+    fn i2c(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        let idx: Vec<_> = (0..self.0.board.i2c.len()).collect();
+        self.enumerate(&idx, BoardEngine::open_i2c, handles)
+    }
+
+    fn gpio_in(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        let idx = self.gpio_idx(Direction::Input);
+        self.enumerate(&idx, BoardEngine::open_gpio, handles)
+    }
+
+    fn gpio_out(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        let idx = self.gpio_idx(Direction::Output);
+        self.enumerate(&idx, BoardEngine::open_gpio, handles)
+    }
+}
@@ -0,0 +1,173 @@
+//! Conformance test runner for wasm guests.
+//!
+//! Runs a compiled guest against a [MockEngine] loaded from one of the `tests/*.toml`
+//! expectation files, reporting the first operation that diverged from the fixture.
+//! See the `wasm-embedded-conformance` binary for command line use.
+
+use std::path::Path;
+
+use wasmtime::{Linker, Module, Store, Val};
+
+use crate::mock::{Expectations, MockEngine, MockError};
+use crate::wiggle::add_engine_to_linker;
+
+/// Default guest entry point
+pub const DEFAULT_ENTRY: &str = "_start";
+
+/// Conformance run errors
+#[derive(Debug)]
+pub enum ConformanceError {
+    /// Failed to load, compile or instantiate the guest
+    Setup(anyhow::Error),
+    /// Guest trapped, with the mock state at the time of the trap
+    Trap {
+        /// Trap reported by the runtime
+        trap: anyhow::Error,
+        /// Divergence from the fixture if one occurred
+        mock: Option<MockError>,
+    },
+    /// Guest completed but diverged from (or did not complete) the fixture
+    Mismatch(MockError),
+}
+
+impl core::fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConformanceError::Setup(e) => write!(f, "setup failed: {:#}", e),
+            ConformanceError::Trap { trap, mock: Some(m) } => {
+                write!(f, "guest trapped: {:#} ({})", trap, m)
+            }
+            ConformanceError::Trap { trap, mock: None } => write!(f, "guest trapped: {:#}", trap),
+            ConformanceError::Mismatch(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+impl std::error::Error for ConformanceError {}
+
+impl From<anyhow::Error> for ConformanceError {
+    fn from(e: anyhow::Error) -> Self {
+        ConformanceError::Setup(e)
+    }
+}
+
+/// WASI `proc_exit` call from the guest
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct GuestExit(i32);
+
+impl core::fmt::Display for GuestExit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "guest exited with code {}", self.0)
+    }
+}
+
+impl std::error::Error for GuestExit {}
+
+/// Run a guest from file against an expectation file, see [run]
+pub fn run_file<W: AsRef<Path>, F: AsRef<Path>>(
+    wasm: W,
+    fixture: F,
+    entry: Option<&str>,
+) -> Result<(), ConformanceError> {
+    let wasm = std::fs::read(wasm).map_err(|e| ConformanceError::Setup(e.into()))?;
+    let expectations = Expectations::load(fixture)?;
+
+    run(&wasm, expectations, entry)
+}
+
+/// Run a guest (binary or text format) against the provided expectations.
+///
+/// The guest is linked with all spec modules backed by a [MockEngine], with any other
+/// imports (such as WASI, aside from `proc_exit`) trapping if called.
+/// The `entry` export (defaulting to [DEFAULT_ENTRY]) must take no arguments.
+pub fn run(
+    wasm: &[u8],
+    expectations: Expectations,
+    entry: Option<&str>,
+) -> Result<(), ConformanceError> {
+    let entry = entry.unwrap_or(DEFAULT_ENTRY);
+    let num_ops = expectations.ops.len();
+
+    #[cfg(not(feature = "async"))]
+    let engine = wasmtime::Engine::default();
+
+    #[cfg(feature = "async")]
+    let engine = {
+        let mut cfg = wasmtime::Config::new();
+        cfg.async_support(true);
+        wasmtime::Engine::new(&cfg)?
+    };
+
+    let module = Module::new(&engine, wasm)?;
+
+    let mut linker = Linker::new(&engine);
+    add_engine_to_linker(&mut linker, |e: &mut MockEngine| e)?;
+    linker.func_wrap("wasi_snapshot_preview1", "proc_exit", |code: i32| -> anyhow::Result<()> {
+        Err(GuestExit(code).into())
+    })?;
+    linker.define_unknown_imports_as_traps(&module)?;
+
+    let mut store = Store::new(&engine, MockEngine::new(expectations.ops));
+
+    #[cfg(not(feature = "async"))]
+    let instance = linker.instantiate(&mut store, &module)?;
+
+    #[cfg(feature = "async")]
+    let instance = block_on(linker.instantiate_async(&mut store, &module))?;
+
+    let func = match instance.get_func(&mut store, entry) {
+        Some(f) => f,
+        None => return Err(anyhow::anyhow!("guest does not export '{}'", entry).into()),
+    };
+
+    let ty = func.ty(&store);
+    if ty.params().len() != 0 {
+        return Err(anyhow::anyhow!("entry point '{}' must take no arguments", entry).into());
+    }
+    let mut results: Vec<_> = ty.results().map(|_| Val::I32(0)).collect();
+
+    log::debug!("Running '{}' against {} expected ops", entry, num_ops);
+
+    #[cfg(not(feature = "async"))]
+    let res = func.call(&mut store, &[], &mut results);
+
+    #[cfg(feature = "async")]
+    let res = block_on(func.call_async(&mut store, &[], &mut results));
+
+    // Treat a zero WASI exit code as the guest returning
+    let exited = res.as_ref().err().and_then(|e| e.downcast_ref::<GuestExit>());
+
+    match res {
+        Ok(_) => store.data().done().map_err(ConformanceError::Mismatch),
+        Err(_) if exited == Some(&GuestExit(0)) => {
+            store.data().done().map_err(ConformanceError::Mismatch)
+        }
+        Err(trap) => Err(ConformanceError::Trap { trap, mock: store.data().done().err() }),
+    }
+}
+
+/// Drive a future to completion on the current thread
+#[cfg(feature = "async")]
+fn block_on<F: core::future::Future>(f: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut f = core::pin::pin!(f);
+
+    loop {
+        match f.as_mut().poll(&mut cx) {
+            Poll::Ready(v) => break v,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
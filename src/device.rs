@@ -0,0 +1,65 @@
+//! Low-level device enumeration API abstraction.
+//!
+//! Provides a common trait for platforms to supply pre-configured device handles,
+//! so guests can use the devices a board provides without hardcoding ports and pins.
+
+use super::Error;
+
+/// Device enumeration context abstraction.
+///
+/// Each method fills `handles` with up to `handles.len()` pre-configured handles,
+/// returning the total number of handles available. Returned handles are valid
+/// for use with the corresponding peripheral driver.
+pub trait Device {
+    /// Fetch pre-configured [crate::Spi] handles
+    fn spi(&mut self, handles: &mut [i32]) -> Result<usize, Error>;
+
+    /// Fetch pre-configured [crate::I2c] handles
+    fn i2c(&mut self, handles: &mut [i32]) -> Result<usize, Error>;
+
+    /// Fetch pre-configured [crate::Gpio] input handles
+    fn gpio_in(&mut self, handles: &mut [i32]) -> Result<usize, Error>;
+
+    /// Fetch pre-configured [crate::Gpio] output handles
+    fn gpio_out(&mut self, handles: &mut [i32]) -> Result<usize, Error>;
+}
+
+/// Blanket Device implementation for &mut T where T: Device
+impl<T: Device> Device for &mut T {
+    fn spi(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        <T as Device>::spi(self, handles)
+    }
+
+    fn i2c(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        <T as Device>::i2c(self, handles)
+    }
+
+    fn gpio_in(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        <T as Device>::gpio_in(self, handles)
+    }
+
+    fn gpio_out(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        <T as Device>::gpio_out(self, handles)
+    }
+}
+
+/// Null [Device] implementation to simplify engine instantiation
+pub struct NullDevice;
+
+impl Device for NullDevice {
+    fn spi(&mut self, _handles: &mut [i32]) -> Result<usize, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn i2c(&mut self, _handles: &mut [i32]) -> Result<usize, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn gpio_in(&mut self, _handles: &mut [i32]) -> Result<usize, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn gpio_out(&mut self, _handles: &mut [i32]) -> Result<usize, Error> {
+        Err(Error::Unsupported)
+    }
+}
@@ -0,0 +1,613 @@
+//! Fault-injecting [Engine] wrapper, for testing guest error handling.
+//!
+//! Faults are described by [Rule]s, each matching calls by kind, handle and I2C address
+//! and firing by call count or probability:
+//!
+//! ```
+//! use wasm_embedded_spec::{Engine, Error};
+//! use wasm_embedded_spec::fault::{Call, Fault, FaultInjectingEngine, Rule};
+//!
+//! struct Platform;
+//! impl Engine for Platform {}
+//!
+//! let e = FaultInjectingEngine::new(Platform, 1234)
+//!     // Fail the 3rd I2C write to 0x50
+//!     .with_rule(Rule::new(Fault::Error(Error::Failed)).call(Call::I2cWrite).addr(0x50).nth(3))
+//!     // Return short UART reads 10% of the time
+//!     .with_rule(Rule::new(Fault::Short(1)).call(Call::UartRead).probability(0.1));
+//! ```
+//!
+//! Randomness is provided by a seeded generator so runs are reproducible.
+//! I2C transactions and SPI exec calls are checked as their component operations,
+//! with the whole transaction failing without calling the inner engine if any fault fires.
+
+use embedded_hal::digital::PinState;
+
+use crate::*;
+
+/// Engine calls that may be matched by a [Rule]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Call {
+    GpioInit,
+    GpioDeinit,
+    GpioSet,
+    GpioGet,
+    GpioSetInterrupt,
+    /// GPIO event waits are matched without a handle, as these apply to multiple handles
+    GpioWaitEvent,
+    I2cInit,
+    I2cDeinit,
+    I2cWrite,
+    I2cRead,
+    I2cWriteRead,
+    SpiInit,
+    SpiDeinit,
+    SpiRead,
+    SpiWrite,
+    SpiTransfer,
+    UartInit,
+    UartDeinit,
+    UartConfigure,
+    UartWrite,
+    UartRead,
+    AdcInit,
+    AdcDeinit,
+    AdcRead,
+    AdcReadMulti,
+    PwmInit,
+    PwmDeinit,
+    PwmSetDuty,
+    PwmSetFrequency,
+    PwmEnable,
+    PwmDisable,
+    PwmGetMaxDuty,
+}
+
+/// Fault to be injected
+#[derive(Clone, PartialEq, Debug)]
+pub enum Fault {
+    /// Return an error without calling the inner engine
+    Error(Error),
+    /// Limit UART reads and writes to at most N bytes.
+    ///
+    /// Rules with short faults only match UART reads and writes, other calls are not counted.
+    Short(usize),
+}
+
+/// When a matching [Rule] fires
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Trigger {
+    /// On every matching call
+    Always,
+    /// On the Nth matching call (1-indexed)
+    Nth(usize),
+    /// On every matching call from the Nth (1-indexed)
+    From(usize),
+    /// With the provided probability (0.0 to 1.0) on each matching call
+    Probability(f64),
+}
+
+/// Fault injection rule
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rule {
+    /// Call kind to match, `None` for any
+    pub call: Option<Call>,
+    /// Handle to match, `None` for any
+    pub handle: Option<i32>,
+    /// I2C address to match, `None` for any
+    pub addr: Option<u16>,
+    /// Trigger condition
+    pub trigger: Trigger,
+    /// Fault to inject
+    pub fault: Fault,
+}
+
+impl Rule {
+    /// Create a rule injecting the provided fault on every call
+    pub fn new(fault: Fault) -> Self {
+        Self { call: None, handle: None, addr: None, trigger: Trigger::Always, fault }
+    }
+
+    /// Match only the provided call kind
+    pub fn call(mut self, call: Call) -> Self {
+        self.call = Some(call);
+        self
+    }
+
+    /// Match only the provided handle
+    pub fn handle(mut self, handle: i32) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Match only the provided I2C address
+    pub fn addr(mut self, addr: u16) -> Self {
+        self.addr = Some(addr);
+        self
+    }
+
+    /// Fire on the Nth matching call (1-indexed)
+    pub fn nth(mut self, n: usize) -> Self {
+        self.trigger = Trigger::Nth(n);
+        self
+    }
+
+    /// Fire on every matching call from the Nth (1-indexed)
+    pub fn from_nth(mut self, n: usize) -> Self {
+        self.trigger = Trigger::From(n);
+        self
+    }
+
+    /// Fire with the provided probability on each matching call
+    pub fn probability(mut self, p: f64) -> Self {
+        self.trigger = Trigger::Probability(p);
+        self
+    }
+
+    fn matches(&self, call: Call, handle: Option<i32>, addr: Option<u16>) -> bool {
+        if let Fault::Short(_) = self.fault {
+            if !matches!(call, Call::UartWrite | Call::UartRead) {
+                return false;
+            }
+        }
+
+        self.call.map(|c| c == call).unwrap_or(true)
+            && self.handle.map(|h| Some(h) == handle).unwrap_or(true)
+            && self.addr.map(|a| Some(a) == addr).unwrap_or(true)
+    }
+}
+
+/// SplitMix64 generator, reproducible for a given seed
+#[derive(Clone, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a value in [0.0, 1.0)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// [Engine] wrapper injecting faults into calls to an inner engine, see [crate::fault]
+pub struct FaultInjectingEngine<E: Engine> {
+    inner: E,
+    rules: Vec<(Rule, usize)>,
+    rng: Rng,
+    injected: usize,
+}
+
+impl<E: Engine> FaultInjectingEngine<E> {
+    /// Create a new [FaultInjectingEngine] with the provided RNG seed
+    pub fn new(inner: E, seed: u64) -> Self {
+        Self { inner, rules: vec![], rng: Rng(seed), injected: 0 }
+    }
+
+    /// Add a fault injection rule, rules are checked in the order added
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push((rule, 0));
+        self
+    }
+
+    /// Add a fault injection rule, rules are checked in the order added
+    pub fn add_rule(&mut self, rule: Rule) {
+        self.rules.push((rule, 0));
+    }
+
+    /// Fetch the wrapped engine
+    pub fn inner(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Fetch the number of faults injected
+    pub fn injected(&self) -> usize {
+        self.injected
+    }
+
+    /// Check rules for a call, returning the fault to inject if any.
+    ///
+    /// All matching rules have their counts updated, the first to fire is returned.
+    fn check(&mut self, call: Call, handle: Option<i32>, addr: Option<u16>) -> Option<Fault> {
+        let mut fault = None;
+
+        for (rule, count) in self.rules.iter_mut() {
+            if !rule.matches(call, handle, addr) {
+                continue;
+            }
+            *count += 1;
+
+            let fire = match rule.trigger {
+                Trigger::Always => true,
+                Trigger::Nth(n) => *count == n,
+                Trigger::From(n) => *count >= n,
+                Trigger::Probability(p) => self.rng.next_f64() < p,
+            };
+
+            if fire && fault.is_none() {
+                fault = Some(rule.fault.clone());
+            }
+        }
+
+        if let Some(f) = &fault {
+            log::debug!("Injecting fault {:?} for {:?} (handle: {:?})", f, call, handle);
+            self.injected += 1;
+        }
+
+        fault
+    }
+
+    /// Check rules for a call, returning injected errors
+    fn check_err(
+        &mut self,
+        call: Call,
+        handle: Option<i32>,
+        addr: Option<u16>,
+    ) -> Result<(), Error> {
+        match self.check(call, handle, addr) {
+            Some(Fault::Error(e)) => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<E: Engine> Engine for FaultInjectingEngine<E> {
+    type Gpio = FaultGpio<Self>;
+    type I2c = FaultI2c<Self>;
+    type Spi = FaultSpi<Self>;
+    type Uart = FaultUart<Self>;
+    type Adc = FaultAdc<Self>;
+    type Pwm = FaultPwm<Self>;
+    type Time = E::Time;
+    type Device = E::Device;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        self.inner.gpio()?;
+        Some(FaultGpio::from_mut(self))
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        self.inner.i2c()?;
+        Some(FaultI2c::from_mut(self))
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        self.inner.spi()?;
+        Some(FaultSpi::from_mut(self))
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        self.inner.uart()?;
+        Some(FaultUart::from_mut(self))
+    }
+
+    fn adc(&mut self) -> Option<&mut Self::Adc> {
+        self.inner.adc()?;
+        Some(FaultAdc::from_mut(self))
+    }
+
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> {
+        self.inner.pwm()?;
+        Some(FaultPwm::from_mut(self))
+    }
+
+    fn time(&mut self) -> Option<&mut Self::Time> {
+        self.inner.time()
+    }
+
+    fn device(&mut self) -> Option<&mut Self::Device> {
+        self.inner.device()
+    }
+}
+
+engine_wrapper!(
+    /// [Gpio] view of a [FaultInjectingEngine]
+    pub FaultGpio
+);
+engine_wrapper!(
+    /// [I2c] view of a [FaultInjectingEngine]
+    pub FaultI2c
+);
+engine_wrapper!(
+    /// [Spi] view of a [FaultInjectingEngine]
+    pub FaultSpi
+);
+engine_wrapper!(
+    /// [Uart] view of a [FaultInjectingEngine]
+    pub FaultUart
+);
+engine_wrapper!(
+    /// [Adc] view of a [FaultInjectingEngine]
+    pub FaultAdc
+);
+engine_wrapper!(
+    /// [Pwm] view of a [FaultInjectingEngine]
+    pub FaultPwm
+);
+
+impl<E: Engine> FaultGpio<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Gpio, Error> {
+        self.0.inner.gpio().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Gpio for FaultGpio<FaultInjectingEngine<E>> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        self.0.check_err(Call::GpioInit, None, None)?;
+        self.drv()?.init(port, pin, cfg)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::GpioDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        self.0.check_err(Call::GpioSet, Some(handle), None)?;
+        self.drv()?.set(handle, state)
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        self.0.check_err(Call::GpioGet, Some(handle), None)?;
+        self.drv()?.get(handle)
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        self.0.check_err(Call::GpioSetInterrupt, Some(handle), None)?;
+        self.drv()?.set_interrupt(handle, edge)
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        self.0.check_err(Call::GpioWaitEvent, None, None)?;
+        self.drv()?.wait_event(handles, timeout_us)
+    }
+}
+
+impl<E: Engine> FaultI2c<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::I2c, Error> {
+        self.0.inner.i2c().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> I2c for FaultI2c<FaultInjectingEngine<E>> {
+    fn init(&mut self, dev: u32, baud: u32, sda: i32, scl: i32) -> Result<i32, Error> {
+        self.0.check_err(Call::I2cInit, None, None)?;
+        self.drv()?.init(dev, baud, sda, scl)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::I2cDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        self.0.check_err(Call::I2cWrite, Some(handle), Some(addr))?;
+        self.drv()?.write(handle, addr, data)
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        self.0.check_err(Call::I2cRead, Some(handle), Some(addr))?;
+        self.drv()?.read(handle, addr, buff)
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        self.0.check_err(Call::I2cWriteRead, Some(handle), Some(addr))?;
+        self.drv()?.write_read(handle, addr, data, buff)
+    }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        for op in ops.iter() {
+            let call = match op {
+                I2cOp::Write(_) => Call::I2cWrite,
+                I2cOp::Read(_) => Call::I2cRead,
+            };
+            self.0.check_err(call, Some(handle), Some(addr))?;
+        }
+        self.drv()?.transaction(handle, addr, ops)
+    }
+}
+
+impl<E: Engine> FaultSpi<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Spi, Error> {
+        self.0.inner.spi().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Spi for FaultSpi<FaultInjectingEngine<E>> {
+    fn init(
+        &mut self,
+        dev: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        self.0.check_err(Call::SpiInit, None, None)?;
+        self.drv()?.init(dev, baud, mosi, miso, sck, cs)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::SpiDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.0.check_err(Call::SpiRead, Some(handle), None)?;
+        self.drv()?.read(handle, data)
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        self.0.check_err(Call::SpiWrite, Some(handle), None)?;
+        self.drv()?.write(handle, data)
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        self.0.check_err(Call::SpiTransfer, Some(handle), None)?;
+        self.drv()?.transfer(handle, read, write)
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.0.check_err(Call::SpiTransfer, Some(handle), None)?;
+        self.drv()?.transfer_inplace(handle, data)
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        for op in ops.iter() {
+            let call = match op {
+                SpiOp::Read(_) => Call::SpiRead,
+                SpiOp::Write(_) => Call::SpiWrite,
+                SpiOp::Transfer(_) => Call::SpiTransfer,
+            };
+            self.0.check_err(call, Some(handle), None)?;
+        }
+        self.drv()?.exec(handle, ops)
+    }
+}
+
+impl<E: Engine> FaultUart<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Uart, Error> {
+        self.0.inner.uart().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Uart for FaultUart<FaultInjectingEngine<E>> {
+    fn init(&mut self, dev: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        self.0.check_err(Call::UartInit, None, None)?;
+        self.drv()?.init(dev, baud, tx, rx)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::UartDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        self.0.check_err(Call::UartConfigure, Some(handle), None)?;
+        self.drv()?.configure(handle, cfg)
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        match self.0.check(Call::UartWrite, Some(handle), None) {
+            Some(Fault::Error(e)) => Err(e),
+            Some(Fault::Short(n)) => {
+                let n = n.min(data.len());
+                self.drv()?.write(handle, flags, timeout_ms, &data[..n])
+            }
+            None => self.drv()?.write(handle, flags, timeout_ms, data),
+        }
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        match self.0.check(Call::UartRead, Some(handle), None) {
+            Some(Fault::Error(e)) => Err(e),
+            Some(Fault::Short(n)) => {
+                let n = n.min(buff.len());
+                self.drv()?.read(handle, flags, timeout_ms, &mut buff[..n])
+            }
+            None => self.drv()?.read(handle, flags, timeout_ms, buff),
+        }
+    }
+}
+
+impl<E: Engine> FaultAdc<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Adc, Error> {
+        self.0.inner.adc().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Adc for FaultAdc<FaultInjectingEngine<E>> {
+    fn init(
+        &mut self,
+        dev: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error> {
+        self.0.check_err(Call::AdcInit, None, None)?;
+        self.drv()?.init(dev, channel, pin, resolution, reference)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::AdcDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        self.0.check_err(Call::AdcRead, Some(handle), None)?;
+        self.drv()?.read(handle)
+    }
+
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error> {
+        self.0.check_err(Call::AdcReadMulti, Some(handle), None)?;
+        self.drv()?.read_multi(handle, rate_hz, buff)
+    }
+}
+
+impl<E: Engine> FaultPwm<FaultInjectingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Pwm, Error> {
+        self.0.inner.pwm().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Pwm for FaultPwm<FaultInjectingEngine<E>> {
+    fn init(&mut self, dev: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        self.0.check_err(Call::PwmInit, None, None)?;
+        self.drv()?.init(dev, channel, pin, freq_hz)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::PwmDeinit, Some(handle), None)?;
+        self.drv()?.deinit(handle)
+    }
+
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        self.0.check_err(Call::PwmSetDuty, Some(handle), None)?;
+        self.drv()?.set_duty(handle, duty)
+    }
+
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        self.0.check_err(Call::PwmSetFrequency, Some(handle), None)?;
+        self.drv()?.set_frequency(handle, freq_hz)
+    }
+
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::PwmEnable, Some(handle), None)?;
+        self.drv()?.enable(handle)
+    }
+
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.check_err(Call::PwmDisable, Some(handle), None)?;
+        self.drv()?.disable(handle)
+    }
+
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        self.0.check_err(Call::PwmGetMaxDuty, Some(handle), None)?;
+        self.drv()?.get_max_duty(handle)
+    }
+}
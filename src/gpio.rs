@@ -2,18 +2,140 @@
 //!
 //! Provides a common GPIO trait for adaptation between specifications and implementations.
 
+#[cfg(feature = "std")]
+use std::{collections::VecDeque, sync::{Arc, Condvar, Mutex}, time::{Duration, Instant}};
+
 use embedded_hal::digital::PinState;
 
 use super::Error;
 
+/// GPIO pin direction
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// GPIO pull resistor
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Pull {
+    None,
+    Up,
+    Down,
+}
+
+/// GPIO output drive mode
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Drive {
+    PushPull,
+    OpenDrain,
+}
+
+/// GPIO output drive strength, platforms may round to the nearest supported value
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DriveStrength {
+    Default,
+    Low,
+    Medium,
+    High,
+}
+
+/// GPIO pin configuration, see `$gpio_cfg` in `gpio.witx`
+#[derive(Clone, PartialEq, Debug)]
+pub struct GpioConfig {
+    /// Pin direction
+    pub direction: Direction,
+    /// Pull resistor
+    pub pull: Pull,
+    /// Output drive mode, ignored for inputs
+    pub drive: Drive,
+    /// Output drive strength, ignored for inputs
+    pub strength: DriveStrength,
+    /// Initial output level, ignored for inputs
+    pub initial: PinState,
+}
+
+impl GpioConfig {
+    /// Floating input configuration
+    pub const fn input() -> Self {
+        Self {
+            direction: Direction::Input,
+            pull: Pull::None,
+            drive: Drive::PushPull,
+            strength: DriveStrength::Default,
+            initial: PinState::Low,
+        }
+    }
+
+    /// Push-pull output configuration, initially low
+    pub const fn output() -> Self {
+        Self {
+            direction: Direction::Output,
+            ..Self::input()
+        }
+    }
+}
+
+impl Default for GpioConfig {
+    fn default() -> Self {
+        Self::input()
+    }
+}
+
+/// GPIO interrupt edge
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Edge {
+    /// No edge, disables interrupts
+    None,
+    /// Rising (low to high) edge
+    Rising,
+    /// Falling (high to low) edge
+    Falling,
+    /// Either edge
+    Both,
+}
+
+/// GPIO edge event
+#[derive(Clone, PartialEq, Debug)]
+pub struct GpioEvent {
+    /// Handle of the GPIO pin on which the event occurred
+    pub handle: i32,
+    /// Edge that occurred
+    pub edge: Edge,
+}
+
 /// Platform GPIO API abstraction.
 ///
 /// This hides runtime implementation details to simplify implementing GPIO contexts.
 /// Hopefully one day generation is improved so we don't _need_ this any more
-#[cfg_attr(feature = "mocks", mockall::automock)]
 pub trait Gpio {
-    /// Initialise a GPIO by port and pin, returning a handle
-    fn init(&mut self, port: i32, pin: i32, output: bool) -> Result<i32, Error>;
+    /// Initialise a GPIO by port and pin with the provided configuration, returning a handle
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error>;
 
     /// Deinitialise a GPIO by handle
     fn deinit(&mut self, handle: i32) -> Result<(), Error>;
@@ -23,12 +145,25 @@ pub trait Gpio {
 
     /// Fetch a GPIO state by handle
     fn get(&mut self, handle: i32) -> Result<PinState, Error>;
+
+    /// Configure the edge(s) on which events are generated for a GPIO by handle
+    fn set_interrupt(&mut self, _handle: i32, _edge: Edge) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Wait for an event on any of the provided GPIO handles.
+    ///
+    /// A `timeout_us` of 0 polls without blocking and `u32::MAX` waits forever,
+    /// returning [Error::Timeout] if no event occurs.
+    fn wait_event(&mut self, _handles: &[i32], _timeout_us: u32) -> Result<GpioEvent, Error> {
+        Err(Error::Unsupported)
+    }
 }
 
 /// Blanket implementation of Gpio for mutable references to Gpio
 impl <T: Gpio> Gpio for &mut T {
-    fn init(&mut self, port: i32, pin: i32, output: bool) -> Result<i32, Error> {
-        <T as Gpio>::init(self, port, pin, output)
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        <T as Gpio>::init(self, port, pin, cfg)
     }
 
     fn deinit(&mut self, handle: i32) -> Result<(), Error> {
@@ -42,12 +177,20 @@ impl <T: Gpio> Gpio for &mut T {
     fn get(&mut self, handle: i32) -> Result<PinState, Error> {
         <T as Gpio>::get(self, handle)
     }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        <T as Gpio>::set_interrupt(self, handle, edge)
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        <T as Gpio>::wait_event(self, handles, timeout_us)
+    }
 }
 
 /// Blanket implementation of Gpio for dyn Gpio types
 impl <T: Gpio> Gpio for Box<T> {
-    fn init(&mut self, port: i32, pin: i32, output: bool) -> Result<i32, Error> {
-        <T as Gpio>::init(self, port, pin, output)
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        <T as Gpio>::init(self, port, pin, cfg)
     }
 
     fn deinit(&mut self, handle: i32) -> Result<(), Error> {
@@ -61,13 +204,21 @@ impl <T: Gpio> Gpio for Box<T> {
     fn get(&mut self, handle: i32) -> Result<PinState, Error> {
         <T as Gpio>::get(self, handle)
     }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        <T as Gpio>::set_interrupt(self, handle, edge)
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        <T as Gpio>::wait_event(self, handles, timeout_us)
+    }
 }
 
 /// Null GPIO implementation to simplify engine definitions
 pub struct NullGpio;
 
 impl Gpio for NullGpio {
-    fn init(&mut self, _port: i32, _pin: i32, _output: bool) -> Result<i32, Error> {
+    fn init(&mut self, _port: i32, _pin: i32, _cfg: &GpioConfig) -> Result<i32, Error> {
         return Err(Error::Unsupported)
     }
 
@@ -83,3 +234,66 @@ impl Gpio for NullGpio {
         return Err(Error::Unsupported)
     }
 }
+
+/// Queue of GPIO events, shared between platform interrupt handlers and [Gpio::wait_event].
+///
+/// Clones refer to the same underlying queue.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct GpioEventQueue {
+    inner: Arc<(Mutex<VecDeque<GpioEvent>>, Condvar)>,
+}
+
+#[cfg(feature = "std")]
+impl GpioEventQueue {
+    /// Create a new empty event queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push an event to the queue, waking any waiters
+    pub fn push(&self, event: GpioEvent) {
+        let (events, cv) = &*self.inner;
+
+        events.lock().unwrap().push_back(event);
+        cv.notify_all();
+    }
+
+    /// Remove pending events for a GPIO handle, for use on deinit or edge changes
+    pub fn clear(&self, handle: i32) {
+        let (events, _cv) = &*self.inner;
+
+        events.lock().unwrap().retain(|e| e.handle != handle);
+    }
+
+    /// Wait for an event on any of the provided handles, see [Gpio::wait_event] for timeouts.
+    ///
+    /// Events for other handles are left in the queue.
+    pub fn wait(&self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        let (events, cv) = &*self.inner;
+        let deadline = match timeout_us {
+            u32::MAX => None,
+            t => Some(Instant::now() + Duration::from_micros(t as u64)),
+        };
+
+        let mut q = events.lock().unwrap();
+        loop {
+            // Return the oldest matching event if available
+            if let Some(i) = q.iter().position(|e| handles.contains(&e.handle)) {
+                return Ok(q.remove(i).unwrap());
+            }
+
+            // Otherwise wait for the next push or timeout
+            q = match deadline {
+                None => cv.wait(q).unwrap(),
+                Some(d) => {
+                    let now = Instant::now();
+                    if now >= d {
+                        return Err(Error::Timeout);
+                    }
+                    cv.wait_timeout(q, d - now).unwrap().0
+                }
+            };
+        }
+    }
+}
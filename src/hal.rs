@@ -0,0 +1,531 @@
+//! Adapters from embedded-hal and embedded-io drivers to the engine traits.
+//!
+//! Each adapter holds a collection of pre-configured driver objects keyed by port (and pin
+//! or chip select), mapping `init` calls to handles and driver errors to [Error].
+//! Each object may be opened once, until released with `deinit`.
+//! As these objects are configured by the platform, baud rates and pin assignments
+//! provided by the guest are not applied.
+//!
+//! ```ignore
+//! let gpio = HalGpio::new()
+//!     .with_input(0, 3, button)
+//!     .with_output(0, 25, led);
+//! let i2c = HalI2c::new().with_bus(0, i2c0);
+//! let spi = HalSpiDevice::new().with_device(0, 17, display);
+//! let uart = HalUart::new().with_serial(0, serial0);
+//! ```
+
+use embedded_hal::digital::{InputPin, OutputPin, PinState};
+use embedded_hal::spi::{SpiBus, SpiDevice};
+
+use crate::*;
+
+/// Table of driver objects, indexed by handle
+struct Table<K, T> {
+    entries: Vec<Entry<K, T>>,
+}
+
+struct Entry<K, T> {
+    key: K,
+    dev: T,
+    open: bool,
+}
+
+impl<K, T> Table<K, T> {
+    const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    fn add(&mut self, key: K, dev: T) {
+        self.entries.push(Entry { key, dev, open: false });
+    }
+
+    /// Open the first closed entry matching the provided filter, returning a handle
+    fn open(&mut self, f: impl Fn(&K) -> bool) -> Result<i32, Error> {
+        self.open_with(f, |_| Ok(()))
+    }
+
+    /// Open the first closed entry matching the provided filter if `setup` succeeds,
+    /// returning a handle.
+    ///
+    /// Returns [Error::NoDevice] if no entries match, or [Error::InvalidArg] if all matching
+    /// entries are already open.
+    fn open_with(
+        &mut self,
+        f: impl Fn(&K) -> bool,
+        setup: impl FnOnce(&mut T) -> Result<(), Error>,
+    ) -> Result<i32, Error> {
+        if !self.entries.iter().any(|e| f(&e.key)) {
+            return Err(Error::NoDevice);
+        }
+        let i = self.entries.iter().position(|e| f(&e.key) && !e.open).ok_or(Error::InvalidArg)?;
+        setup(&mut self.entries[i].dev)?;
+        self.entries[i].open = true;
+        Ok(i as i32)
+    }
+
+    /// Fetch an open entry by handle
+    fn get(&mut self, handle: i32) -> Result<&mut T, Error> {
+        match usize::try_from(handle).ok().and_then(|i| self.entries.get_mut(i)) {
+            Some(e) if e.open => Ok(&mut e.dev),
+            _ => Err(Error::InvalidArg),
+        }
+    }
+
+    /// Close an open entry by handle
+    fn close(&mut self, handle: i32) -> Result<(), Error> {
+        match usize::try_from(handle).ok().and_then(|i| self.entries.get_mut(i)) {
+            Some(e) if e.open => {
+                e.open = false;
+                Ok(())
+            }
+            _ => Err(Error::InvalidArg),
+        }
+    }
+}
+
+fn digital_err<E: embedded_hal::digital::Error>(e: E) -> Error {
+    log::debug!("GPIO error: {:?}", e.kind());
+    e.kind().into()
+}
+
+fn i2c_err<E: embedded_hal::i2c::Error>(e: E) -> Error {
+    log::debug!("I2C error: {:?}", e.kind());
+    e.kind().into()
+}
+
+fn spi_err<E: embedded_hal::spi::Error>(e: E) -> Error {
+    log::debug!("SPI error: {:?}", e.kind());
+    e.kind().into()
+}
+
+fn io_err<E: embedded_io::Error>(e: E) -> Error {
+    log::debug!("IO error: {:?}", e.kind());
+    e.kind().into()
+}
+
+/// GPIO pin, outputs track the last state set
+enum Pin<I, O> {
+    Input(I),
+    Output(O, PinState),
+}
+
+/// [Gpio] adapter over embedded-hal [InputPin] and [OutputPin] objects, keyed by port and pin.
+///
+/// Pins are used in the direction they are provided, with pull and drive configuration
+/// left to the platform.
+pub struct HalGpio<I: InputPin, O: OutputPin> {
+    pins: Table<(i32, i32), Pin<I, O>>,
+}
+
+impl<I: InputPin, O: OutputPin> HalGpio<I, O> {
+    /// Create an empty [HalGpio] adapter
+    pub const fn new() -> Self {
+        Self { pins: Table::new() }
+    }
+
+    /// Add an input pin
+    pub fn with_input(mut self, port: i32, pin: i32, p: I) -> Self {
+        self.add_input(port, pin, p);
+        self
+    }
+
+    /// Add an output pin
+    pub fn with_output(mut self, port: i32, pin: i32, p: O) -> Self {
+        self.add_output(port, pin, p);
+        self
+    }
+
+    /// Add an input pin
+    pub fn add_input(&mut self, port: i32, pin: i32, p: I) {
+        self.pins.add((port, pin), Pin::Input(p));
+    }
+
+    /// Add an output pin
+    pub fn add_output(&mut self, port: i32, pin: i32, p: O) {
+        self.pins.add((port, pin), Pin::Output(p, PinState::Low));
+    }
+}
+
+impl<I: InputPin, O: OutputPin> Default for HalGpio<I, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: InputPin, O: OutputPin> Gpio for HalGpio<I, O> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        self.pins.open_with(
+            |k| *k == (port, pin),
+            |p| match (p, cfg.direction) {
+                (Pin::Input(_), Direction::Input) => Ok(()),
+                (Pin::Output(p, s), Direction::Output) => {
+                    p.set_state(cfg.initial).map_err(digital_err)?;
+                    *s = cfg.initial;
+                    Ok(())
+                }
+                _ => Err(Error::Unsupported),
+            },
+        )
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.pins.close(handle)
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        match self.pins.get(handle)? {
+            Pin::Output(p, s) => {
+                p.set_state(state).map_err(digital_err)?;
+                *s = state;
+                Ok(())
+            }
+            Pin::Input(_) => Err(Error::Unsupported),
+        }
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        match self.pins.get(handle)? {
+            Pin::Input(p) => p.is_high().map(PinState::from).map_err(digital_err),
+            Pin::Output(_, s) => Ok(*s),
+        }
+    }
+}
+
+/// [I2c] adapter over embedded-hal [I2c](embedded_hal::i2c::I2c) buses, keyed by port.
+///
+/// Only 7-bit addresses are supported.
+pub struct HalI2c<T: embedded_hal::i2c::I2c> {
+    buses: Table<u32, T>,
+}
+
+impl<T: embedded_hal::i2c::I2c> HalI2c<T> {
+    /// Create an empty [HalI2c] adapter
+    pub const fn new() -> Self {
+        Self { buses: Table::new() }
+    }
+
+    /// Add an I2C bus
+    pub fn with_bus(mut self, port: u32, bus: T) -> Self {
+        self.add_bus(port, bus);
+        self
+    }
+
+    /// Add an I2C bus
+    pub fn add_bus(&mut self, port: u32, bus: T) {
+        self.buses.add(port, bus);
+    }
+}
+
+impl<T: embedded_hal::i2c::I2c> Default for HalI2c<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert a guest address to a 7-bit address
+fn i2c_addr(addr: u16) -> Result<u8, Error> {
+    match u8::try_from(addr) {
+        Ok(a) if a <= 0x7f => Ok(a),
+        _ => Err(Error::InvalidArg),
+    }
+}
+
+impl<T: embedded_hal::i2c::I2c> I2c for HalI2c<T> {
+    fn init(&mut self, dev: u32, _baud: u32, _sda: i32, _sck: i32) -> Result<i32, Error> {
+        self.buses.open(|k| *k == dev)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.buses.close(handle)
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        let addr = i2c_addr(addr)?;
+        self.buses.get(handle)?.write(addr, data).map_err(i2c_err)
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        let addr = i2c_addr(addr)?;
+        self.buses.get(handle)?.read(addr, buff).map_err(i2c_err)
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        let addr = i2c_addr(addr)?;
+        self.buses.get(handle)?.write_read(addr, data, buff).map_err(i2c_err)
+    }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        use embedded_hal::i2c::Operation;
+
+        let addr = i2c_addr(addr)?;
+        let mut ops: Vec<_> = ops
+            .iter_mut()
+            .map(|o| match o {
+                I2cOp::Read(b) => Operation::Read(b),
+                I2cOp::Write(b) => Operation::Write(b),
+            })
+            .collect();
+
+        self.buses.get(handle)?.transaction(addr, &mut ops).map_err(i2c_err)
+    }
+}
+
+/// [Spi] adapter over embedded-hal [SpiDevice] objects, keyed by port and chip select.
+///
+/// A chip select of -1 opens the first device on the port.
+pub struct HalSpiDevice<T: SpiDevice> {
+    devices: Table<(u32, i32), T>,
+}
+
+impl<T: SpiDevice> HalSpiDevice<T> {
+    /// Create an empty [HalSpiDevice] adapter
+    pub const fn new() -> Self {
+        Self { devices: Table::new() }
+    }
+
+    /// Add an SPI device
+    pub fn with_device(mut self, port: u32, cs: i32, dev: T) -> Self {
+        self.add_device(port, cs, dev);
+        self
+    }
+
+    /// Add an SPI device
+    pub fn add_device(&mut self, port: u32, cs: i32, dev: T) {
+        self.devices.add((port, cs), dev);
+    }
+}
+
+impl<T: SpiDevice> Default for HalSpiDevice<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpiDevice> Spi for HalSpiDevice<T> {
+    fn init(
+        &mut self,
+        dev: u32,
+        _baud: u32,
+        _mosi: i32,
+        _miso: i32,
+        _sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        self.devices.open(|(p, c)| *p == dev && (cs == -1 || *c == cs))
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.devices.close(handle)
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.devices.get(handle)?.read(data).map_err(spi_err)
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        self.devices.get(handle)?.write(data).map_err(spi_err)
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        self.devices.get(handle)?.transfer(read, write).map_err(spi_err)
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.devices.get(handle)?.transfer_in_place(data).map_err(spi_err)
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        use embedded_hal::spi::Operation;
+
+        let mut ops: Vec<_> = ops
+            .iter_mut()
+            .map(|o| match o {
+                SpiOp::Read(b) => Operation::Read(b),
+                SpiOp::Write(b) => Operation::Write(b),
+                SpiOp::Transfer(b) => Operation::TransferInPlace(b),
+            })
+            .collect();
+
+        self.devices.get(handle)?.transaction(&mut ops).map_err(spi_err)
+    }
+}
+
+/// [Spi] adapter over embedded-hal [SpiBus] objects, keyed by port.
+///
+/// Chip select is not managed by the adapter so `init` requires a chip select of -1.
+pub struct HalSpiBus<T: SpiBus> {
+    buses: Table<u32, T>,
+}
+
+impl<T: SpiBus> HalSpiBus<T> {
+    /// Create an empty [HalSpiBus] adapter
+    pub const fn new() -> Self {
+        Self { buses: Table::new() }
+    }
+
+    /// Add an SPI bus
+    pub fn with_bus(mut self, port: u32, bus: T) -> Self {
+        self.add_bus(port, bus);
+        self
+    }
+
+    /// Add an SPI bus
+    pub fn add_bus(&mut self, port: u32, bus: T) {
+        self.buses.add(port, bus);
+    }
+}
+
+impl<T: SpiBus> Default for HalSpiBus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SpiBus> Spi for HalSpiBus<T> {
+    fn init(
+        &mut self,
+        dev: u32,
+        _baud: u32,
+        _mosi: i32,
+        _miso: i32,
+        _sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        if cs != -1 {
+            return Err(Error::Unsupported);
+        }
+        self.buses.open(|p| *p == dev)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.buses.close(handle)
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let b = self.buses.get(handle)?;
+        b.read(data).and_then(|_| b.flush()).map_err(spi_err)
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        let b = self.buses.get(handle)?;
+        b.write(data).and_then(|_| b.flush()).map_err(spi_err)
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        let b = self.buses.get(handle)?;
+        b.transfer(read, write).and_then(|_| b.flush()).map_err(spi_err)
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let b = self.buses.get(handle)?;
+        b.transfer_in_place(data).and_then(|_| b.flush()).map_err(spi_err)
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        let b = self.buses.get(handle)?;
+
+        for o in ops.iter_mut() {
+            match o {
+                SpiOp::Read(d) => b.read(d),
+                SpiOp::Write(d) => b.write(d),
+                SpiOp::Transfer(d) => b.transfer_in_place(d),
+            }
+            .map_err(spi_err)?;
+        }
+
+        b.flush().map_err(spi_err)
+    }
+}
+
+/// [Uart] adapter over embedded-io serial objects, keyed by port.
+///
+/// Blocking operation and [UartFlags::READ_UNTIL_IDLE] reads are supported,
+/// with [UartFlags::NON_BLOCKING] and [UartFlags::TIMEOUT] returning [Error::Unsupported].
+pub struct HalUart<T: embedded_io::Read + embedded_io::Write> {
+    ports: Table<u32, T>,
+}
+
+impl<T: embedded_io::Read + embedded_io::Write> HalUart<T> {
+    /// Create an empty [HalUart] adapter
+    pub const fn new() -> Self {
+        Self { ports: Table::new() }
+    }
+
+    /// Add a serial port
+    pub fn with_serial(mut self, port: u32, serial: T) -> Self {
+        self.add_serial(port, serial);
+        self
+    }
+
+    /// Add a serial port
+    pub fn add_serial(&mut self, port: u32, serial: T) {
+        self.ports.add(port, serial);
+    }
+}
+
+impl<T: embedded_io::Read + embedded_io::Write> Default for HalUart<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check for flags not supported by embedded-io objects
+fn uart_check_flags(flags: UartFlags) -> Result<(), Error> {
+    if flags.contains(UartFlags::NON_BLOCKING) || flags.contains(UartFlags::TIMEOUT) {
+        return Err(Error::Unsupported);
+    }
+    Ok(())
+}
+
+impl<T: embedded_io::Read + embedded_io::Write> Uart for HalUart<T> {
+    fn init(&mut self, dev: u32, _baud: u32, _tx: i32, _rx: i32) -> Result<i32, Error> {
+        self.ports.open(|p| *p == dev)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.ports.close(handle)
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        _timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        uart_check_flags(flags)?;
+
+        self.ports.get(handle)?.write_all(data).map_err(io_err)?;
+        Ok(data.len())
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        _timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        use embedded_io::ReadExactError;
+
+        uart_check_flags(flags)?;
+        let p = self.ports.get(handle)?;
+
+        if flags.contains(UartFlags::READ_UNTIL_IDLE) {
+            return p.read(buff).map_err(io_err);
+        }
+
+        match p.read_exact(buff) {
+            Ok(_) => Ok(buff.len()),
+            Err(ReadExactError::UnexpectedEof) => Err(Error::Failed),
+            Err(ReadExactError::Other(e)) => Err(io_err(e)),
+        }
+    }
+}
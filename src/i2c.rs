@@ -4,6 +4,15 @@
 
 use super::Error;
 
+/// I2C transaction operation, see `$i2c_op` in `i2c.witx`
+#[derive(Debug, PartialEq)]
+pub enum I2cOp<'a> {
+    /// Read into the provided buffer
+    Read(&'a mut [u8]),
+    /// Write out the provided data
+    Write(&'a [u8]),
+}
+
 /// I2C context abstraction.
 ///
 /// This hides runtime implementation details to simplify implementing I2C contexts.
@@ -24,6 +33,28 @@ pub trait I2c {
         data: &[u8],
         buff: &mut [u8],
     ) -> Result<(), Error>;
+
+    /// Execute a list of operations as a single transaction, with repeated starts between operations.
+    ///
+    /// The default implementation combines writes followed by reads into `write_read` calls
+    /// and falls back to `write` and `read` for the remainder, so platforms that support
+    /// arbitrary transactions should override this.
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        let mut ops = ops.iter_mut().peekable();
+
+        while let Some(op) = ops.next() {
+            match (op, ops.peek_mut()) {
+                (I2cOp::Write(data), Some(I2cOp::Read(buff))) => {
+                    self.write_read(handle, addr, data, buff)?;
+                    ops.next();
+                }
+                (I2cOp::Write(data), _) => self.write(handle, addr, data)?,
+                (I2cOp::Read(buff), _) => self.read(handle, addr, buff)?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Blanket implementation of I2c for references to I2c types
@@ -53,6 +84,10 @@ impl<T: I2c> I2c for &mut T {
     ) -> Result<(), Error> {
         <T as I2c>::write_read(self, handle, addr, data, buff)
     }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        <T as I2c>::transaction(self, handle, addr, ops)
+    }
 }
 
 /// Null I2c implementation to simplify engine instantiation
@@ -84,4 +119,8 @@ impl I2c for NullI2c {
     ) -> Result<(), Error> {
         return Err(Error::Unsupported)
     }
+
+    fn transaction(&mut self, _handle: i32, _addr: u16, _ops: &mut [I2cOp]) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
 }
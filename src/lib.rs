@@ -9,17 +9,72 @@
 #![feature(associated_type_defaults)]
 
 
+/// Define a transparent wrapper over an engine type, used to expose
+/// per-module views of an engine where a `&mut` driver is required
+#[allow(unused_macros)]
+macro_rules! engine_wrapper {
+    ($(#[$meta:meta])* $vis:vis $name:ident) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        $vis struct $name<E>(E);
+
+        impl<E> $name<E> {
+            #[allow(dead_code)]
+            pub(crate) fn from_mut(e: &mut E) -> &mut Self {
+                // Safe as the wrapper is transparent over E
+                unsafe { &mut *(e as *mut E as *mut Self) }
+            }
+        }
+    };
+}
+
 mod gpio;
-pub use gpio::{Gpio, NullGpio};
+pub use gpio::{Gpio, GpioConfig, Direction, Pull, Drive, DriveStrength, Edge, GpioEvent, NullGpio};
+
+#[cfg(feature = "std")]
+pub use gpio::GpioEventQueue;
 
 mod i2c;
-pub use i2c::{I2c, NullI2c};
+pub use i2c::{I2c, I2cOp, NullI2c};
 
 mod spi;
-pub use spi::{Spi, NullSpi};
+pub use spi::{Spi, SpiOp, NullSpi};
+
+mod adc;
+pub use adc::{Adc, AdcReference, NullAdc};
+
+mod pwm;
+pub use pwm::{Pwm, NullPwm};
+
+mod time;
+pub use time::{Time, NullTime};
+
+#[cfg(feature = "std")]
+pub use time::StdTime;
+
+#[cfg(feature = "async")]
+pub use time::Sleep;
+
+mod device;
+pub use device::{Device, NullDevice};
 
 mod uart;
-pub use uart::{Uart, NullUart};
+pub use uart::{Uart, UartFlags, UartConfig, Parity, StopBits, FlowControl, NullUart};
+
+#[cfg(feature = "board")]
+pub mod board;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "fault")]
+pub mod fault;
+
+#[cfg(feature = "hal")]
+pub mod hal;
+
+#[cfg(feature = "conformance")]
+pub mod conformance;
 
 #[cfg(feature = "wiggle")]
 pub mod wiggle;
@@ -34,6 +89,10 @@ pub trait Engine {
     type I2c: I2c = i2c::NullI2c;
     type Spi: Spi = spi::NullSpi;
     type Uart: Uart = uart::NullUart;
+    type Adc: Adc = adc::NullAdc;
+    type Pwm: Pwm = pwm::NullPwm;
+    type Time: Time = time::NullTime;
+    type Device: Device = device::NullDevice;
 
     /// Fetch [Gpio] driver if available 
     fn gpio(&mut self) -> Option<&mut Self::Gpio> { return None }
@@ -46,6 +105,18 @@ pub trait Engine {
 
     /// Fetch [Uart] driver if available 
     fn uart(&mut self) -> Option<&mut Self::Uart> { return None }
+
+    /// Fetch [Adc] driver if available 
+    fn adc(&mut self) -> Option<&mut Self::Adc> { return None }
+
+    /// Fetch [Pwm] driver if available 
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> { return None }
+
+    /// Fetch [Time] driver if available 
+    fn time(&mut self) -> Option<&mut Self::Time> { return None }
+
+    /// Fetch [Device] enumeration driver if available 
+    fn device(&mut self) -> Option<&mut Self::Device> { return None }
 }
 
 
@@ -59,5 +130,134 @@ pub enum Error {
     Failed,
     NoDevice,
     Unsupported,
+    Timeout,
+    /// No acknowledgement received (I2C address or data NACK)
+    NoAcknowledge,
+    /// Bus arbitration lost
+    ArbitrationLoss,
+    /// Bus error, such as a misplaced start or stop condition
+    Bus,
+    /// Receive data overrun
+    Overrun,
+    /// SPI mode fault
+    ModeFault,
+    /// Frame format error (SPI frame or UART framing)
+    FrameFormat,
+    /// SPI chip select fault
+    ChipSelectFault,
+    /// UART parity error
+    Parity,
+    /// UART noise detected
+    Noise,
+}
+
+/// Convert a result code to a result, negative values are negated `errno` values.
+///
+/// This is the convention used by C drivers (see `inc/wasm_embedded/common.h`) and mock fixtures.
+pub fn res_to_result(res: i32) -> Result<i32, Error> {
+    match res {
+        r if r >= 0 => Ok(r),
+        -1 => Err(Error::InvalidArg),
+        -2 => Err(Error::Failed),
+        -3 => Err(Error::NoDevice),
+        -4 => Err(Error::Unexpected),
+        -5 => Err(Error::Unsupported),
+        -6 => Err(Error::Timeout),
+        -7 => Err(Error::NoAcknowledge),
+        -8 => Err(Error::ArbitrationLoss),
+        -9 => Err(Error::Bus),
+        -10 => Err(Error::Overrun),
+        -11 => Err(Error::ModeFault),
+        -12 => Err(Error::FrameFormat),
+        -13 => Err(Error::ChipSelectFault),
+        -14 => Err(Error::Parity),
+        -15 => Err(Error::Noise),
+        _ => Err(Error::Failed),
+    }
+}
+
+/// Convert an error to a (negative) result code
+pub fn error_to_res(e: &Error) -> i32 {
+    match e {
+        Error::InvalidArg => -1,
+        Error::Failed => -2,
+        Error::NoDevice => -3,
+        Error::Unexpected => -4,
+        Error::Unsupported => -5,
+        Error::Timeout => -6,
+        Error::NoAcknowledge => -7,
+        Error::ArbitrationLoss => -8,
+        Error::Bus => -9,
+        Error::Overrun => -10,
+        Error::ModeFault => -11,
+        Error::FrameFormat => -12,
+        Error::ChipSelectFault => -13,
+        Error::Parity => -14,
+        Error::Noise => -15,
+    }
+}
+
+impl From<embedded_hal::i2c::ErrorKind> for Error {
+    fn from(k: embedded_hal::i2c::ErrorKind) -> Self {
+        use embedded_hal::i2c::ErrorKind;
+
+        match k {
+            ErrorKind::Bus => Error::Bus,
+            ErrorKind::ArbitrationLoss => Error::ArbitrationLoss,
+            ErrorKind::NoAcknowledge(_) => Error::NoAcknowledge,
+            ErrorKind::Overrun => Error::Overrun,
+            _ => Error::Failed,
+        }
+    }
+}
+
+impl From<embedded_hal::spi::ErrorKind> for Error {
+    fn from(k: embedded_hal::spi::ErrorKind) -> Self {
+        use embedded_hal::spi::ErrorKind;
+
+        match k {
+            ErrorKind::Overrun => Error::Overrun,
+            ErrorKind::ModeFault => Error::ModeFault,
+            ErrorKind::FrameFormat => Error::FrameFormat,
+            ErrorKind::ChipSelectFault => Error::ChipSelectFault,
+            _ => Error::Failed,
+        }
+    }
+}
+
+impl From<embedded_hal::digital::ErrorKind> for Error {
+    fn from(_k: embedded_hal::digital::ErrorKind) -> Self {
+        Error::Failed
+    }
+}
+
+#[cfg(feature = "hal")]
+impl From<embedded_hal_nb::serial::ErrorKind> for Error {
+    fn from(k: embedded_hal_nb::serial::ErrorKind) -> Self {
+        use embedded_hal_nb::serial::ErrorKind;
+
+        match k {
+            ErrorKind::Overrun => Error::Overrun,
+            ErrorKind::FrameFormat => Error::FrameFormat,
+            ErrorKind::Parity => Error::Parity,
+            ErrorKind::Noise => Error::Noise,
+            _ => Error::Failed,
+        }
+    }
+}
+
+#[cfg(feature = "hal")]
+impl From<embedded_io::ErrorKind> for Error {
+    fn from(k: embedded_io::ErrorKind) -> Self {
+        use embedded_io::ErrorKind;
+
+        match k {
+            ErrorKind::TimedOut => Error::Timeout,
+            ErrorKind::Unsupported => Error::Unsupported,
+            ErrorKind::InvalidInput | ErrorKind::InvalidData => Error::InvalidArg,
+            ErrorKind::NotFound => Error::NoDevice,
+            _ => Error::Failed,
+        }
+    }
 }
 
@@ -0,0 +1,770 @@
+//! Mock [Engine] driven by expectation files.
+//!
+//! Expectations are loaded from TOML files containing an ordered list of operations,
+//! as used for the shared fixtures in `./tests`:
+//!
+//! ```toml
+//! ops = [
+//!   { kind = "gpio_init", port = 2, pin = 3, output = true, res = 1 },
+//!   { kind = "gpio_set", handle = 1, state = "high", res = 0 },
+//! ]
+//! ```
+//!
+//! Each call to the [MockEngine] is checked against the next expected operation,
+//! with `res` providing the handle (for `*_init` operations) or result code, where
+//! negative values are the negated [Error] `errno` values from `common.witx`.
+//! Scripted data (`data_in`, `state`, `value`, `duty`, `event` and `edge`, UART write `count`)
+//! is returned to the caller.
+//!
+//! On divergence the mismatch is recorded and [Error::Unexpected] returned for this and
+//! all subsequent calls, use [MockEngine::done] to check all expectations were met.
+
+use std::path::Path;
+
+use embedded_hal::digital::PinState;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+mod record;
+pub use record::{
+    RecordingEngine, RecordGpio, RecordI2c, RecordSpi, RecordUart, RecordAdc, RecordPwm,
+};
+
+mod replay;
+pub use replay::{ReplayEngine, ReplayMode};
+
+pub use crate::{error_to_res, res_to_result};
+
+/// Expectation file, containing an ordered list of operations
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Expectations {
+    /// Expected operations, in order
+    pub ops: Vec<MockOp>,
+}
+
+impl Expectations {
+    /// Load expectations from a TOML file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
+        let s = std::fs::read_to_string(path)?;
+        let e = toml::from_str(&s)?;
+        Ok(e)
+    }
+
+    /// Save expectations to a TOML file, in the same format as the `tests/*.toml` fixtures
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), anyhow::Error> {
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+/// Format expectations as `ops = [ ... ]` with one inline table per operation
+impl core::fmt::Display for Expectations {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "ops = [")?;
+
+        for o in &self.ops {
+            let t = match toml::Value::try_from(o) {
+                Ok(toml::Value::Table(t)) => t,
+                _ => return Err(core::fmt::Error),
+            };
+
+            let fields: Vec<_> = t.iter().map(|(k, v)| format!("{} = {}", k, v)).collect();
+            writeln!(f, "  {{ {} }},", fields.join(", "))?;
+        }
+
+        writeln!(f, "]")
+    }
+}
+
+impl core::str::FromStr for Expectations {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+/// Expected operation with result code
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MockOp {
+    /// Operation
+    #[serde(flatten)]
+    pub op: Op,
+    /// Handle for `*_init` operations, or result code, negative values are errors
+    #[serde(default)]
+    pub res: i32,
+}
+
+impl MockOp {
+    /// Create a new [MockOp] with the provided handle or result code
+    pub fn new(op: Op, res: i32) -> Self {
+        Self { op, res }
+    }
+}
+
+/// GPIO pin state, serialised as `"low"` or `"high"`
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    #[default]
+    Low,
+    High,
+}
+
+impl From<PinState> for State {
+    fn from(s: PinState) -> Self {
+        match s {
+            PinState::Low => State::Low,
+            PinState::High => State::High,
+        }
+    }
+}
+
+impl From<State> for PinState {
+    fn from(s: State) -> Self {
+        match s {
+            State::Low => PinState::Low,
+            State::High => PinState::High,
+        }
+    }
+}
+
+/// Mock operations, `data_out` is written by the caller and `data_in` returned to it.
+///
+/// `data_in` lengths must match the caller buffer, except for UART reads which return
+/// up to the buffer length.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Op {
+    GpioInit {
+        port: i32,
+        pin: i32,
+        output: bool,
+        #[serde(default = "default_pull")]
+        pull: Pull,
+        #[serde(default = "default_drive")]
+        drive: Drive,
+        #[serde(default = "default_strength")]
+        strength: DriveStrength,
+        #[serde(default)]
+        initial: State,
+    },
+    GpioDeinit { handle: i32 },
+    GpioSet { handle: i32, state: State },
+    GpioGet { handle: i32, state: State },
+    GpioSetInterrupt { handle: i32, edge: Edge },
+    /// Wait for an event on `handles`, returning the `event` handle and `edge`
+    GpioWaitEvent { handles: Vec<i32>, timeout_us: u32, event: i32, edge: Edge },
+
+    I2cInit { port: u32, baud: u32, sda: i32, scl: i32 },
+    I2cDeinit { handle: i32 },
+    I2cWrite { handle: i32, addr: u16, data_out: Vec<u8> },
+    I2cRead { handle: i32, addr: u16, data_in: Vec<u8> },
+    I2cWriteRead { handle: i32, addr: u16, data_out: Vec<u8>, data_in: Vec<u8> },
+
+    SpiInit { port: u32, baud: u32, mosi: i32, miso: i32, sck: i32, cs: i32 },
+    SpiDeinit { handle: i32 },
+    SpiWrite { handle: i32, data_out: Vec<u8> },
+    SpiRead { handle: i32, data_in: Vec<u8> },
+    SpiTransfer { handle: i32, data_out: Vec<u8>, data_in: Vec<u8> },
+
+    UartInit { port: u32, baud: u32, tx: i32, rx: i32 },
+    UartDeinit { handle: i32 },
+    UartConfigure {
+        handle: i32,
+        baud: u32,
+        data_bits: u8,
+        parity: Parity,
+        stop_bits: StopBits,
+        flow_control: FlowControl,
+        rts: i32,
+        cts: i32,
+    },
+    /// Write `data_out`, `count` is the number of bytes accepted (defaulting to all).
+    /// `timeout_ms` defaults to 0, as used when [UartFlags::TIMEOUT] is not set.
+    UartWrite {
+        handle: i32,
+        flags: u32,
+        #[serde(default)]
+        timeout_ms: u32,
+        data_out: Vec<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<u32>,
+    },
+    /// Read up to the caller buffer length, returning `data_in`
+    UartRead {
+        handle: i32,
+        flags: u32,
+        #[serde(default)]
+        timeout_ms: u32,
+        data_in: Vec<u8>,
+    },
+
+    AdcInit { port: u32, channel: u32, pin: i32, resolution: u8, reference: AdcReference },
+    AdcDeinit { handle: i32 },
+    AdcRead { handle: i32, value: u16 },
+    AdcReadMulti { handle: i32, rate_hz: u32, data_in: Vec<u16> },
+
+    PwmInit { port: u32, channel: u32, pin: i32, freq_hz: u32 },
+    PwmDeinit { handle: i32 },
+    PwmSetDuty { handle: i32, duty: u32 },
+    PwmSetFrequency { handle: i32, freq_hz: u32 },
+    PwmEnable { handle: i32 },
+    PwmDisable { handle: i32 },
+    PwmGetMaxDuty { handle: i32, duty: u32 },
+}
+
+impl Op {
+    /// Build a `gpio_init` operation from a pin configuration
+    pub fn gpio_init(port: i32, pin: i32, cfg: &GpioConfig) -> Self {
+        Op::GpioInit {
+            port,
+            pin,
+            output: cfg.direction == Direction::Output,
+            pull: cfg.pull,
+            drive: cfg.drive,
+            strength: cfg.strength,
+            initial: cfg.initial.into(),
+        }
+    }
+
+    /// Build a `uart_configure` operation from a line configuration
+    pub fn uart_configure(handle: i32, cfg: &UartConfig) -> Self {
+        Op::UartConfigure {
+            handle,
+            baud: cfg.baud,
+            data_bits: cfg.data_bits,
+            parity: cfg.parity,
+            stop_bits: cfg.stop_bits,
+            flow_control: cfg.flow_control,
+            rts: cfg.rts,
+            cts: cfg.cts,
+        }
+    }
+
+    /// Fetch the handle an operation applies to,
+    /// `None` for `*_init` operations and GPIO event waits over multiple handles
+    pub fn handle(&self) -> Option<i32> {
+        match self {
+            Op::GpioInit { .. }
+            | Op::GpioWaitEvent { .. }
+            | Op::I2cInit { .. }
+            | Op::SpiInit { .. }
+            | Op::UartInit { .. }
+            | Op::AdcInit { .. }
+            | Op::PwmInit { .. } => None,
+
+            Op::GpioDeinit { handle }
+            | Op::GpioSet { handle, .. }
+            | Op::GpioGet { handle, .. }
+            | Op::GpioSetInterrupt { handle, .. }
+            | Op::I2cDeinit { handle }
+            | Op::I2cWrite { handle, .. }
+            | Op::I2cRead { handle, .. }
+            | Op::I2cWriteRead { handle, .. }
+            | Op::SpiDeinit { handle }
+            | Op::SpiWrite { handle, .. }
+            | Op::SpiRead { handle, .. }
+            | Op::SpiTransfer { handle, .. }
+            | Op::UartDeinit { handle }
+            | Op::UartConfigure { handle, .. }
+            | Op::UartWrite { handle, .. }
+            | Op::UartRead { handle, .. }
+            | Op::AdcDeinit { handle }
+            | Op::AdcRead { handle, .. }
+            | Op::AdcReadMulti { handle, .. }
+            | Op::PwmDeinit { handle }
+            | Op::PwmSetDuty { handle, .. }
+            | Op::PwmSetFrequency { handle, .. }
+            | Op::PwmEnable { handle }
+            | Op::PwmDisable { handle }
+            | Op::PwmGetMaxDuty { handle, .. } => Some(*handle),
+        }
+    }
+
+    /// Check whether an operation matches this (expected) operation
+    fn matches(&self, actual: &Op, mode: ReplayMode) -> bool {
+        match mode {
+            ReplayMode::Strict => self.inputs() == actual.inputs(),
+            ReplayMode::Lenient => {
+                core::mem::discriminant(self) == core::mem::discriminant(actual)
+                    && self.handle() == actual.handle()
+            }
+        }
+    }
+
+    /// Fetch a copy of the operation with returned values cleared, for comparison
+    fn inputs(&self) -> Op {
+        let mut op = self.clone();
+
+        match &mut op {
+            Op::GpioGet { state, .. } => *state = State::Low,
+            Op::GpioWaitEvent { event, edge, .. } => {
+                *event = 0;
+                *edge = Edge::None;
+            }
+            Op::I2cRead { data_in, .. }
+            | Op::I2cWriteRead { data_in, .. }
+            | Op::SpiRead { data_in, .. }
+            | Op::SpiTransfer { data_in, .. } => data_in.iter_mut().for_each(|v| *v = 0),
+            // UART reads may return fewer bytes than requested
+            Op::UartRead { data_in, .. } => data_in.clear(),
+            Op::UartWrite { count, .. } => *count = None,
+            Op::AdcRead { value, .. } => *value = 0,
+            Op::AdcReadMulti { data_in, .. } => data_in.iter_mut().for_each(|v| *v = 0),
+            Op::PwmGetMaxDuty { duty, .. } => *duty = 0,
+            _ => (),
+        }
+
+        op
+    }
+}
+
+/// [MockEngine] errors, reported by [MockEngine::done]
+#[derive(Clone, PartialEq, Debug)]
+pub enum MockError {
+    /// A call did not match the expected operation
+    Mismatch {
+        /// Index of the expected operation
+        index: usize,
+        /// Expected operation, `None` if all operations were already consumed
+        expected: Option<Box<MockOp>>,
+        /// Operation received
+        actual: Box<Op>,
+    },
+    /// Expected operations were not called
+    Incomplete {
+        /// Index of the first operation not called
+        index: usize,
+        /// Remaining operations
+        remaining: Vec<MockOp>,
+    },
+}
+
+impl core::fmt::Display for MockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MockError::Mismatch { index, expected: Some(e), actual } => {
+                write!(f, "op {} mismatch, expected {:?} got {:?}", index, e.op, actual)
+            }
+            MockError::Mismatch { index, expected: None, actual } => {
+                write!(f, "op {} unexpected, got {:?} after all expectations", index, actual)
+            }
+            MockError::Incomplete { index, remaining } => {
+                write!(f, "op {} not called, {} operation(s) remaining", index, remaining.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for MockError {}
+
+/// Mock [Engine] checking calls against [Expectations], see [crate::mock]
+#[derive(Clone, PartialEq, Debug)]
+pub struct MockEngine {
+    ops: Vec<MockOp>,
+    index: usize,
+    err: Option<MockError>,
+    mode: ReplayMode,
+}
+
+impl MockEngine {
+    /// Create a new [MockEngine] with the provided expected operations
+    pub fn new(ops: Vec<MockOp>) -> Self {
+        Self::with_mode(ops, ReplayMode::Strict)
+    }
+
+    /// Create a new [MockEngine] with the provided operation matching mode
+    pub(crate) fn with_mode(ops: Vec<MockOp>, mode: ReplayMode) -> Self {
+        Self { ops, index: 0, err: None, mode }
+    }
+
+    /// Load a [MockEngine] from a TOML expectation file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, anyhow::Error> {
+        let e = Expectations::load(path)?;
+        Ok(Self::new(e.ops))
+    }
+
+    /// Fetch the index of the next expected operation
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Check that all expected operations were called with no mismatches
+    pub fn done(&self) -> Result<(), MockError> {
+        if let Some(e) = &self.err {
+            return Err(e.clone());
+        }
+
+        if self.index < self.ops.len() {
+            return Err(MockError::Incomplete {
+                index: self.index,
+                remaining: self.ops[self.index..].to_vec(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check an operation against the next expectation, returning the expected operation
+    fn expect(&mut self, actual: Op) -> Result<Op, Error> {
+        if self.err.is_some() {
+            return Err(Error::Unexpected);
+        }
+
+        let expected = self.ops.get(self.index).cloned();
+
+        match expected {
+            Some(e) if e.op.matches(&actual, self.mode) => {
+                log::debug!("Mock op {}: {:?}", self.index, e);
+                self.index += 1;
+                res_to_result(e.res).map(|_| e.op)
+            }
+            _ => {
+                let err = MockError::Mismatch {
+                    index: self.index,
+                    expected: expected.map(Box::new),
+                    actual: Box::new(actual),
+                };
+                log::error!("Mock {}", err);
+                self.err = Some(err);
+                Err(Error::Unexpected)
+            }
+        }
+    }
+
+    /// Check an `*_init` operation, returning the expected handle
+    fn expect_init(&mut self, actual: Op) -> Result<i32, Error> {
+        self.expect(actual)?;
+        res_to_result(self.ops[self.index - 1].res)
+    }
+}
+
+fn default_pull() -> Pull {
+    Pull::None
+}
+
+fn default_drive() -> Drive {
+    Drive::PushPull
+}
+
+fn default_strength() -> DriveStrength {
+    DriveStrength::Default
+}
+
+/// Copy scripted data into a caller buffer
+fn copy_in<T: Copy>(buff: &mut [T], data: &[T]) {
+    let n = buff.len().min(data.len());
+    buff[..n].copy_from_slice(&data[..n]);
+}
+
+impl Engine for MockEngine {
+    type Gpio = MockGpio<Self>;
+    type I2c = MockI2c<Self>;
+    type Spi = MockSpi<Self>;
+    type Uart = MockUart<Self>;
+    type Adc = MockAdc<Self>;
+    type Pwm = MockPwm<Self>;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        Some(MockGpio::from_mut(self))
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        Some(MockI2c::from_mut(self))
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        Some(MockSpi::from_mut(self))
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        Some(MockUart::from_mut(self))
+    }
+
+    fn adc(&mut self) -> Option<&mut Self::Adc> {
+        Some(MockAdc::from_mut(self))
+    }
+
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> {
+        Some(MockPwm::from_mut(self))
+    }
+}
+
+engine_wrapper!(
+    /// [Gpio] view of a [MockEngine]
+    pub MockGpio
+);
+engine_wrapper!(
+    /// [I2c] view of a [MockEngine]
+    pub MockI2c
+);
+engine_wrapper!(
+    /// [Spi] view of a [MockEngine]
+    pub MockSpi
+);
+engine_wrapper!(
+    /// [Uart] view of a [MockEngine]
+    pub MockUart
+);
+engine_wrapper!(
+    /// [Adc] view of a [MockEngine]
+    pub MockAdc
+);
+engine_wrapper!(
+    /// [Pwm] view of a [MockEngine]
+    pub MockPwm
+);
+
+impl Gpio for MockGpio<MockEngine> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        self.0.expect_init(Op::gpio_init(port, pin, cfg))
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::GpioDeinit { handle }).map(|_| ())
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        self.0.expect(Op::GpioSet { handle, state: state.into() }).map(|_| ())
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        match self.0.expect(Op::GpioGet { handle, state: State::Low })? {
+            Op::GpioGet { state, .. } => Ok(state.into()),
+            _ => Err(Error::Unexpected),
+        }
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        self.0.expect(Op::GpioSetInterrupt { handle, edge }).map(|_| ())
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        let op = Op::GpioWaitEvent {
+            handles: handles.to_vec(),
+            timeout_us,
+            event: 0,
+            edge: Edge::None,
+        };
+        match self.0.expect(op)? {
+            Op::GpioWaitEvent { event, edge, .. } => Ok(GpioEvent { handle: event, edge }),
+            _ => Err(Error::Unexpected),
+        }
+    }
+}
+
+impl I2c for MockI2c<MockEngine> {
+    fn init(&mut self, port: u32, baud: u32, sda: i32, scl: i32) -> Result<i32, Error> {
+        self.0.expect_init(Op::I2cInit { port, baud, sda, scl })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::I2cDeinit { handle }).map(|_| ())
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        self.0.expect(Op::I2cWrite { handle, addr, data_out: data.to_vec() }).map(|_| ())
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        let data_in = vec![0; buff.len()];
+        if let Op::I2cRead { data_in, .. } = self.0.expect(Op::I2cRead { handle, addr, data_in })? {
+            copy_in(buff, &data_in);
+        }
+        Ok(())
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        let op = Op::I2cWriteRead {
+            handle,
+            addr,
+            data_out: data.to_vec(),
+            data_in: vec![0; buff.len()],
+        };
+        if let Op::I2cWriteRead { data_in, .. } = self.0.expect(op)? {
+            copy_in(buff, &data_in);
+        }
+        Ok(())
+    }
+
+    /// Transactions are checked as their component `i2c_write` and `i2c_read` operations
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        for op in ops.iter_mut() {
+            match op {
+                I2cOp::Write(data) => self.write(handle, addr, data)?,
+                I2cOp::Read(buff) => self.read(handle, addr, buff)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Spi for MockSpi<MockEngine> {
+    fn init(
+        &mut self,
+        port: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        self.0.expect_init(Op::SpiInit { port, baud, mosi, miso, sck, cs })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::SpiDeinit { handle }).map(|_| ())
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let data_in = vec![0; data.len()];
+        if let Op::SpiRead { data_in, .. } = self.0.expect(Op::SpiRead { handle, data_in })? {
+            copy_in(data, &data_in);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        self.0.expect(Op::SpiWrite { handle, data_out: data.to_vec() }).map(|_| ())
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        let op = Op::SpiTransfer { handle, data_out: write.to_vec(), data_in: vec![0; read.len()] };
+        if let Op::SpiTransfer { data_in, .. } = self.0.expect(op)? {
+            copy_in(read, &data_in);
+        }
+        Ok(())
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let op = Op::SpiTransfer { handle, data_out: data.to_vec(), data_in: vec![0; data.len()] };
+        if let Op::SpiTransfer { data_in, .. } = self.0.expect(op)? {
+            copy_in(data, &data_in);
+        }
+        Ok(())
+    }
+}
+
+impl Uart for MockUart<MockEngine> {
+    fn init(&mut self, port: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        self.0.expect_init(Op::UartInit { port, baud, tx, rx })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::UartDeinit { handle }).map(|_| ())
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        self.0.expect(Op::uart_configure(handle, cfg)).map(|_| ())
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        let op = Op::UartWrite {
+            handle,
+            flags: flags.0,
+            timeout_ms,
+            data_out: data.to_vec(),
+            count: None,
+        };
+        match self.0.expect(op)? {
+            Op::UartWrite { count: Some(n), .. } => Ok((n as usize).min(data.len())),
+            _ => Ok(data.len()),
+        }
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        let op = Op::UartRead { handle, flags: flags.0, timeout_ms, data_in: vec![0; buff.len()] };
+        match self.0.expect(op)? {
+            Op::UartRead { data_in, .. } => {
+                copy_in(buff, &data_in);
+                Ok(data_in.len().min(buff.len()))
+            }
+            _ => Err(Error::Unexpected),
+        }
+    }
+}
+
+impl Adc for MockAdc<MockEngine> {
+    fn init(
+        &mut self,
+        port: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error> {
+        self.0.expect_init(Op::AdcInit { port, channel, pin, resolution, reference })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::AdcDeinit { handle }).map(|_| ())
+    }
+
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        match self.0.expect(Op::AdcRead { handle, value: 0 })? {
+            Op::AdcRead { value, .. } => Ok(value),
+            _ => Err(Error::Unexpected),
+        }
+    }
+
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error> {
+        let op = Op::AdcReadMulti { handle, rate_hz, data_in: vec![0; buff.len()] };
+        if let Op::AdcReadMulti { data_in, .. } = self.0.expect(op)? {
+            copy_in(buff, &data_in);
+        }
+        Ok(())
+    }
+}
+
+impl Pwm for MockPwm<MockEngine> {
+    fn init(&mut self, port: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        self.0.expect_init(Op::PwmInit { port, channel, pin, freq_hz })
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::PwmDeinit { handle }).map(|_| ())
+    }
+
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        self.0.expect(Op::PwmSetDuty { handle, duty }).map(|_| ())
+    }
+
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        self.0.expect(Op::PwmSetFrequency { handle, freq_hz }).map(|_| ())
+    }
+
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::PwmEnable { handle }).map(|_| ())
+    }
+
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        self.0.expect(Op::PwmDisable { handle }).map(|_| ())
+    }
+
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        match self.0.expect(Op::PwmGetMaxDuty { handle, duty: 0 })? {
+            Op::PwmGetMaxDuty { duty, .. } => Ok(duty),
+            _ => Err(Error::Unexpected),
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! Recording [Engine] wrapper, capturing traffic in the [Expectations] format
+
+use std::path::Path;
+
+use embedded_hal::digital::PinState;
+
+use super::{error_to_res, Expectations, MockOp, Op};
+use crate::*;
+
+/// [Engine] wrapper forwarding calls to an inner engine and recording each operation
+/// with arguments, data and results, for replay using a [MockEngine](super::MockEngine).
+///
+/// I2C transactions and SPI exec calls are forwarded as a whole and recorded as their
+/// component operations, on failure only the first operation is recorded with the error.
+pub struct RecordingEngine<E: Engine> {
+    inner: E,
+    ops: Vec<MockOp>,
+}
+
+impl<E: Engine> RecordingEngine<E> {
+    /// Create a new [RecordingEngine] wrapping the provided engine
+    pub fn new(inner: E) -> Self {
+        Self { inner, ops: vec![] }
+    }
+
+    /// Fetch the wrapped engine
+    pub fn inner(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Fetch recorded operations
+    pub fn ops(&self) -> &[MockOp] {
+        &self.ops
+    }
+
+    /// Take recorded operations as [Expectations], clearing the log
+    pub fn take(&mut self) -> Expectations {
+        Expectations { ops: core::mem::take(&mut self.ops) }
+    }
+
+    /// Save recorded operations to a TOML file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), anyhow::Error> {
+        Expectations { ops: self.ops.clone() }.save(path)
+    }
+
+    fn record<T>(&mut self, op: Op, r: &Result<T, Error>) {
+        let res = match r {
+            Ok(_) => 0,
+            Err(e) => error_to_res(e),
+        };
+        self.ops.push(MockOp { op, res });
+    }
+
+    fn record_ops(&mut self, ops: Vec<Op>, r: &Result<(), Error>) {
+        match r {
+            Ok(_) => self.ops.extend(ops.into_iter().map(|op| MockOp { op, res: 0 })),
+            Err(e) => {
+                if let Some(op) = ops.into_iter().next() {
+                    self.ops.push(MockOp { op, res: error_to_res(e) });
+                }
+            }
+        }
+    }
+
+    fn record_init(&mut self, op: Op, r: &Result<i32, Error>) {
+        let res = match r {
+            Ok(h) => *h,
+            Err(e) => error_to_res(e),
+        };
+        self.ops.push(MockOp { op, res });
+    }
+}
+
+impl<E: Engine> Engine for RecordingEngine<E> {
+    type Gpio = RecordGpio<Self>;
+    type I2c = RecordI2c<Self>;
+    type Spi = RecordSpi<Self>;
+    type Uart = RecordUart<Self>;
+    type Adc = RecordAdc<Self>;
+    type Pwm = RecordPwm<Self>;
+    type Time = E::Time;
+    type Device = E::Device;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        self.inner.gpio()?;
+        Some(RecordGpio::from_mut(self))
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        self.inner.i2c()?;
+        Some(RecordI2c::from_mut(self))
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        self.inner.spi()?;
+        Some(RecordSpi::from_mut(self))
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        self.inner.uart()?;
+        Some(RecordUart::from_mut(self))
+    }
+
+    fn adc(&mut self) -> Option<&mut Self::Adc> {
+        self.inner.adc()?;
+        Some(RecordAdc::from_mut(self))
+    }
+
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> {
+        self.inner.pwm()?;
+        Some(RecordPwm::from_mut(self))
+    }
+
+    fn time(&mut self) -> Option<&mut Self::Time> {
+        self.inner.time()
+    }
+
+    fn device(&mut self) -> Option<&mut Self::Device> {
+        self.inner.device()
+    }
+}
+
+engine_wrapper!(
+    /// [Gpio] view of a [RecordingEngine]
+    pub RecordGpio
+);
+engine_wrapper!(
+    /// [I2c] view of a [RecordingEngine]
+    pub RecordI2c
+);
+engine_wrapper!(
+    /// [Spi] view of a [RecordingEngine]
+    pub RecordSpi
+);
+engine_wrapper!(
+    /// [Uart] view of a [RecordingEngine]
+    pub RecordUart
+);
+engine_wrapper!(
+    /// [Adc] view of a [RecordingEngine]
+    pub RecordAdc
+);
+engine_wrapper!(
+    /// [Pwm] view of a [RecordingEngine]
+    pub RecordPwm
+);
+
+impl<E: Engine> RecordGpio<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Gpio, Error> {
+        self.0.inner.gpio().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Gpio for RecordGpio<RecordingEngine<E>> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, pin, cfg));
+        self.0.record_init(Op::gpio_init(port, pin, cfg), &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::GpioDeinit { handle }, &r);
+        r
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.set(handle, state));
+        self.0.record(Op::GpioSet { handle, state: state.into() }, &r);
+        r
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        let r = self.drv().and_then(|d| d.get(handle));
+        let state = r.as_ref().map(|s| (*s).into()).unwrap_or_default();
+        self.0.record(Op::GpioGet { handle, state }, &r);
+        r
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.set_interrupt(handle, edge));
+        self.0.record(Op::GpioSetInterrupt { handle, edge }, &r);
+        r
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        let r = self.drv().and_then(|d| d.wait_event(handles, timeout_us));
+        let (event, edge) = match &r {
+            Ok(e) => (e.handle, e.edge),
+            Err(_) => (0, Edge::None),
+        };
+        let op = Op::GpioWaitEvent { handles: handles.to_vec(), timeout_us, event, edge };
+        self.0.record(op, &r);
+        r
+    }
+}
+
+impl<E: Engine> RecordI2c<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::I2c, Error> {
+        self.0.inner.i2c().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> I2c for RecordI2c<RecordingEngine<E>> {
+    fn init(&mut self, port: u32, baud: u32, sda: i32, scl: i32) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, baud, sda, scl));
+        self.0.record_init(Op::I2cInit { port, baud, sda, scl }, &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::I2cDeinit { handle }, &r);
+        r
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.write(handle, addr, data));
+        self.0.record(Op::I2cWrite { handle, addr, data_out: data.to_vec() }, &r);
+        r
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.read(handle, addr, buff));
+        self.0.record(Op::I2cRead { handle, addr, data_in: buff.to_vec() }, &r);
+        r
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.write_read(handle, addr, data, buff));
+        let op = Op::I2cWriteRead {
+            handle,
+            addr,
+            data_out: data.to_vec(),
+            data_in: buff.to_vec(),
+        };
+        self.0.record(op, &r);
+        r
+    }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.transaction(handle, addr, ops));
+        let ops = ops
+            .iter()
+            .map(|o| match o {
+                I2cOp::Write(d) => Op::I2cWrite { handle, addr, data_out: d.to_vec() },
+                I2cOp::Read(b) => Op::I2cRead { handle, addr, data_in: b.to_vec() },
+            })
+            .collect();
+        self.0.record_ops(ops, &r);
+        r
+    }
+}
+
+impl<E: Engine> RecordSpi<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Spi, Error> {
+        self.0.inner.spi().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Spi for RecordSpi<RecordingEngine<E>> {
+    fn init(
+        &mut self,
+        port: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, baud, mosi, miso, sck, cs));
+        self.0.record_init(Op::SpiInit { port, baud, mosi, miso, sck, cs }, &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::SpiDeinit { handle }, &r);
+        r
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.read(handle, data));
+        self.0.record(Op::SpiRead { handle, data_in: data.to_vec() }, &r);
+        r
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.write(handle, data));
+        self.0.record(Op::SpiWrite { handle, data_out: data.to_vec() }, &r);
+        r
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.transfer(handle, read, write));
+        let op = Op::SpiTransfer { handle, data_out: write.to_vec(), data_in: read.to_vec() };
+        self.0.record(op, &r);
+        r
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        let data_out = data.to_vec();
+        let r = self.drv().and_then(|d| d.transfer_inplace(handle, data));
+        self.0.record(Op::SpiTransfer { handle, data_out, data_in: data.to_vec() }, &r);
+        r
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        // Transfers are performed in place, so capture outgoing data first
+        let data_out: Vec<_> = ops
+            .iter()
+            .map(|o| match o {
+                SpiOp::Transfer(d) => d.to_vec(),
+                _ => vec![],
+            })
+            .collect();
+
+        let r = self.drv().and_then(|d| d.exec(handle, ops));
+        let ops = ops
+            .iter()
+            .zip(data_out)
+            .map(|(o, data_out)| match o {
+                SpiOp::Read(b) => Op::SpiRead { handle, data_in: b.to_vec() },
+                SpiOp::Write(d) => Op::SpiWrite { handle, data_out: d.to_vec() },
+                SpiOp::Transfer(d) => Op::SpiTransfer { handle, data_out, data_in: d.to_vec() },
+            })
+            .collect();
+        self.0.record_ops(ops, &r);
+        r
+    }
+}
+
+impl<E: Engine> RecordUart<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Uart, Error> {
+        self.0.inner.uart().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Uart for RecordUart<RecordingEngine<E>> {
+    fn init(&mut self, port: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, baud, tx, rx));
+        self.0.record_init(Op::UartInit { port, baud, tx, rx }, &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::UartDeinit { handle }, &r);
+        r
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.configure(handle, cfg));
+        self.0.record(Op::uart_configure(handle, cfg), &r);
+        r
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        // Clamp the count returned by the driver to the data provided
+        let r = self
+            .drv()
+            .and_then(|d| d.write(handle, flags, timeout_ms, data))
+            .map(|n| n.min(data.len()));
+        let count = r.as_ref().ok().map(|n| *n as u32);
+        let op = Op::UartWrite {
+            handle,
+            flags: flags.0,
+            timeout_ms,
+            data_out: data.to_vec(),
+            count,
+        };
+        self.0.record(op, &r);
+        r
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        let r = self
+            .drv()
+            .and_then(|d| d.read(handle, flags, timeout_ms, buff))
+            .map(|n| n.min(buff.len()));
+        let n = *r.as_ref().unwrap_or(&0);
+        let op = Op::UartRead { handle, flags: flags.0, timeout_ms, data_in: buff[..n].to_vec() };
+        self.0.record(op, &r);
+        r
+    }
+}
+
+impl<E: Engine> RecordAdc<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Adc, Error> {
+        self.0.inner.adc().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Adc for RecordAdc<RecordingEngine<E>> {
+    fn init(
+        &mut self,
+        port: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, channel, pin, resolution, reference));
+        self.0.record_init(Op::AdcInit { port, channel, pin, resolution, reference }, &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::AdcDeinit { handle }, &r);
+        r
+    }
+
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        let r = self.drv().and_then(|d| d.read(handle));
+        let value = *r.as_ref().unwrap_or(&0);
+        self.0.record(Op::AdcRead { handle, value }, &r);
+        r
+    }
+
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.read_multi(handle, rate_hz, buff));
+        self.0.record(Op::AdcReadMulti { handle, rate_hz, data_in: buff.to_vec() }, &r);
+        r
+    }
+}
+
+impl<E: Engine> RecordPwm<RecordingEngine<E>> {
+    fn drv(&mut self) -> Result<&mut E::Pwm, Error> {
+        self.0.inner.pwm().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Pwm for RecordPwm<RecordingEngine<E>> {
+    fn init(&mut self, port: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        let r = self.drv().and_then(|d| d.init(port, channel, pin, freq_hz));
+        self.0.record_init(Op::PwmInit { port, channel, pin, freq_hz }, &r);
+        r
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.deinit(handle));
+        self.0.record(Op::PwmDeinit { handle }, &r);
+        r
+    }
+
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.set_duty(handle, duty));
+        self.0.record(Op::PwmSetDuty { handle, duty }, &r);
+        r
+    }
+
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.set_frequency(handle, freq_hz));
+        self.0.record(Op::PwmSetFrequency { handle, freq_hz }, &r);
+        r
+    }
+
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.enable(handle));
+        self.0.record(Op::PwmEnable { handle }, &r);
+        r
+    }
+
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        let r = self.drv().and_then(|d| d.disable(handle));
+        self.0.record(Op::PwmDisable { handle }, &r);
+        r
+    }
+
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        let r = self.drv().and_then(|d| d.get_max_duty(handle));
+        let duty = *r.as_ref().unwrap_or(&0);
+        self.0.record(Op::PwmGetMaxDuty { handle, duty }, &r);
+        r
+    }
+}
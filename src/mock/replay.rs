@@ -0,0 +1,83 @@
+//! Replay [Engine], answering calls from a captured operation log
+
+use std::path::Path;
+
+use super::{Expectations, MockEngine, MockError, MockOp};
+use super::{MockAdc, MockGpio, MockI2c, MockPwm, MockSpi, MockUart};
+use crate::*;
+
+/// Operation matching mode for replay
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ReplayMode {
+    /// Operations must match the log exactly, including arguments and written data
+    #[default]
+    Strict,
+    /// Operations must match the kind and handle only
+    Lenient,
+}
+
+/// [Engine] replaying a log captured with a [RecordingEngine](super::RecordingEngine).
+///
+/// Calls are answered in order with the recorded read data and result codes.
+/// On divergence [Error::Unexpected] is returned for this and all subsequent calls,
+/// with the mismatch reported by [ReplayEngine::done].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ReplayEngine {
+    mock: MockEngine,
+}
+
+impl ReplayEngine {
+    /// Create a new [ReplayEngine] from recorded operations
+    pub fn new(ops: Vec<MockOp>, mode: ReplayMode) -> Self {
+        Self { mock: MockEngine::with_mode(ops, mode) }
+    }
+
+    /// Load a [ReplayEngine] from a TOML operation log
+    pub fn load<P: AsRef<Path>>(path: P, mode: ReplayMode) -> Result<Self, anyhow::Error> {
+        let e = Expectations::load(path)?;
+        Ok(Self::new(e.ops, mode))
+    }
+
+    /// Fetch the index of the next operation to be replayed
+    pub fn index(&self) -> usize {
+        self.mock.index()
+    }
+
+    /// Check that the full log was replayed with no mismatches
+    pub fn done(&self) -> Result<(), MockError> {
+        self.mock.done()
+    }
+}
+
+impl Engine for ReplayEngine {
+    type Gpio = MockGpio<MockEngine>;
+    type I2c = MockI2c<MockEngine>;
+    type Spi = MockSpi<MockEngine>;
+    type Uart = MockUart<MockEngine>;
+    type Adc = MockAdc<MockEngine>;
+    type Pwm = MockPwm<MockEngine>;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        self.mock.gpio()
+    }
+
+    fn i2c(&mut self) -> Option<&mut Self::I2c> {
+        self.mock.i2c()
+    }
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        self.mock.spi()
+    }
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        self.mock.uart()
+    }
+
+    fn adc(&mut self) -> Option<&mut Self::Adc> {
+        self.mock.adc()
+    }
+
+    fn pwm(&mut self) -> Option<&mut Self::Pwm> {
+        self.mock.pwm()
+    }
+}
@@ -0,0 +1,96 @@
+//! Low-level PWM API abstraction.
+//!
+//! Provides a common PWM trait for adaptation between specifications and implementations.
+
+use super::Error;
+
+/// PWM context abstraction.
+///
+/// This hides runtime implementation details to simplify implementing PWM contexts.
+/// Hopefully one day generation is improved so we don't _need_ this any more
+pub trait Pwm {
+    /// Initialise a PWM timer channel at the provided frequency, returning a handle
+    fn init(&mut self, dev: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error>;
+
+    /// Deinitialise a PWM channel by handle
+    fn deinit(&mut self, handle: i32) -> Result<(), Error>;
+
+    /// Set the duty cycle (from 0 to [Pwm::get_max_duty]) by handle
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error>;
+
+    /// Set the PWM frequency by handle, this may change the maximum duty
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error>;
+
+    /// Enable PWM output by handle
+    fn enable(&mut self, handle: i32) -> Result<(), Error>;
+
+    /// Disable PWM output by handle
+    fn disable(&mut self, handle: i32) -> Result<(), Error>;
+
+    /// Fetch the maximum duty cycle by handle
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error>;
+}
+
+/// Blanket Pwm implementation for &mut T where T: Pwm
+impl<T: Pwm> Pwm for &mut T {
+    fn init(&mut self, dev: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        <T as Pwm>::init(self, dev, channel, pin, freq_hz)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Pwm>::deinit(self, handle)
+    }
+
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        <T as Pwm>::set_duty(self, handle, duty)
+    }
+
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        <T as Pwm>::set_frequency(self, handle, freq_hz)
+    }
+
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Pwm>::enable(self, handle)
+    }
+
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Pwm>::disable(self, handle)
+    }
+
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        <T as Pwm>::get_max_duty(self, handle)
+    }
+}
+
+/// Null [Pwm] implementation to simplify engine instantiation
+pub struct NullPwm;
+
+impl Pwm for NullPwm {
+    fn init(&mut self, _dev: u32, _channel: u32, _pin: i32, _freq_hz: u32) -> Result<i32, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn deinit(&mut self, _handle: i32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn set_duty(&mut self, _handle: i32, _duty: u32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn set_frequency(&mut self, _handle: i32, _freq_hz: u32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn enable(&mut self, _handle: i32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn disable(&mut self, _handle: i32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn get_max_duty(&mut self, _handle: i32) -> Result<u32, Error> {
+        Err(Error::Unsupported)
+    }
+}
@@ -4,6 +4,17 @@
 
 use super::Error;
 
+/// SPI transaction operation, see `$op` in `spi.witx`
+#[derive(Debug, PartialEq)]
+pub enum SpiOp<'a> {
+    /// Read into the provided buffer
+    Read(&'a mut [u8]),
+    /// Write out the provided data
+    Write(&'a [u8]),
+    /// Transfer (write and read) the provided buffer in place
+    Transfer(&'a mut [u8]),
+}
+
 /// SPI context abstraction.
 ///
 /// This hides runtime implementation details to simplify implementing I2C contexts.
@@ -28,6 +39,22 @@ pub trait Spi {
     fn transfer<'a>(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error>;
 
     fn transfer_inplace<'a>(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error>;
+
+    /// Execute a list of operations as a single transaction, with CS held for the duration.
+    ///
+    /// The default implementation falls back to per-operation calls, so platforms
+    /// that can hold CS across operations should override this.
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        for op in ops.iter_mut() {
+            match op {
+                SpiOp::Read(buff) => self.read(handle, buff)?,
+                SpiOp::Write(data) => self.write(handle, data)?,
+                SpiOp::Transfer(data) => self.transfer_inplace(handle, data)?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Blanket I2c implementation for references to I2c types
@@ -63,6 +90,10 @@ impl<T: Spi> Spi for &mut T {
     fn transfer_inplace<'a>(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
         <T as Spi>::transfer_inplace(self, handle, data)
     }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        <T as Spi>::exec(self, handle, ops)
+    }
 }
 
 pub struct NullSpi;
@@ -99,4 +130,8 @@ impl Spi for NullSpi {
     fn transfer_inplace<'a>(&mut self, _handle: i32, _data: &mut [u8]) -> Result<(), Error> {
         Err(Error::Unsupported)
     }
+
+    fn exec(&mut self, _handle: i32, _ops: &mut [SpiOp]) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,298 @@
+//! Low-level time / delay API abstraction.
+//!
+//! Provides a common time trait for adaptation between specifications and implementations.
+
+use super::Error;
+
+/// Boxed sleep future, see [Time::sleep_us]
+#[cfg(feature = "async")]
+pub type Sleep = core::pin::Pin<Box<dyn core::future::Future<Output = ()> + Send>>;
+
+/// Time context abstraction.
+///
+/// This hides runtime implementation details to simplify implementing time contexts.
+/// Hopefully one day generation is improved so we don't _need_ this any more
+pub trait Time {
+    /// Fetch the monotonic time in microseconds
+    fn now_us(&mut self) -> Result<u64, Error>;
+
+    /// Block for at least the provided number of microseconds
+    fn delay_us(&mut self, us: u32) -> Result<(), Error>;
+
+    /// Block for at least the provided number of milliseconds
+    fn delay_ms(&mut self, ms: u32) -> Result<(), Error> {
+        for _ in 0..ms / 1000 {
+            self.delay_us(1_000_000)?;
+        }
+
+        self.delay_us((ms % 1000) * 1000)
+    }
+
+    /// Start a one-shot alarm expiring after `timeout_us`, returning a handle
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error>;
+
+    /// Fetch the time remaining for an alarm by handle, 0 once expired
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error>;
+
+    /// Block until an alarm expires by handle
+    fn alarm_wait(&mut self, handle: i32) -> Result<(), Error> {
+        loop {
+            match self.alarm_remaining(handle)? {
+                0 => return Ok(()),
+                r => self.delay_us(r.min(u32::MAX as u64) as u32)?,
+            }
+        }
+    }
+
+    /// Cancel and release an alarm by handle, required for expired alarms
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error>;
+
+    /// Create a future resolving after at least the provided number of microseconds,
+    /// used by async delays and alarm waits.
+    ///
+    /// Returns `None` by default, in which case async waits fall back to polling
+    /// [Time::now_us] and yielding to the executor (busy-waiting).
+    #[cfg(feature = "async")]
+    fn sleep_us(&mut self, _us: u64) -> Option<Sleep> {
+        None
+    }
+}
+
+/// Blanket Time implementation for &mut T where T: Time
+impl<T: Time> Time for &mut T {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        <T as Time>::now_us(self)
+    }
+
+    fn delay_us(&mut self, us: u32) -> Result<(), Error> {
+        <T as Time>::delay_us(self, us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) -> Result<(), Error> {
+        <T as Time>::delay_ms(self, ms)
+    }
+
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error> {
+        <T as Time>::alarm_start(self, timeout_us)
+    }
+
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error> {
+        <T as Time>::alarm_remaining(self, handle)
+    }
+
+    fn alarm_wait(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Time>::alarm_wait(self, handle)
+    }
+
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error> {
+        <T as Time>::alarm_cancel(self, handle)
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep_us(&mut self, us: u64) -> Option<Sleep> {
+        <T as Time>::sleep_us(self, us)
+    }
+}
+
+/// Null [Time] implementation to simplify engine instantiation
+pub struct NullTime;
+
+impl Time for NullTime {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn delay_us(&mut self, _us: u32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn alarm_start(&mut self, _timeout_us: u64) -> Result<i32, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn alarm_remaining(&mut self, _handle: i32) -> Result<u64, Error> {
+        Err(Error::Unsupported)
+    }
+
+    fn alarm_cancel(&mut self, _handle: i32) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+}
+
+/// [Time] implementation using the std monotonic clock, for hosted platforms
+#[cfg(feature = "std")]
+pub struct StdTime {
+    start: std::time::Instant,
+    alarms: std::collections::HashMap<i32, u64>,
+    next_alarm: i32,
+}
+
+#[cfg(feature = "std")]
+impl StdTime {
+    /// Create a new [StdTime] instance, with time measured from creation
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+            alarms: std::collections::HashMap::new(),
+            next_alarm: 1,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Time for StdTime {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        Ok(self.start.elapsed().as_micros() as u64)
+    }
+
+    fn delay_us(&mut self, us: u32) -> Result<(), Error> {
+        std::thread::sleep(std::time::Duration::from_micros(us as u64));
+        Ok(())
+    }
+
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error> {
+        let deadline = self.now_us()?.saturating_add(timeout_us);
+
+        let handle = self.next_alarm;
+        self.next_alarm = self.next_alarm.checked_add(1).ok_or(Error::NoDevice)?;
+        self.alarms.insert(handle, deadline);
+
+        Ok(handle)
+    }
+
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error> {
+        let deadline = *self.alarms.get(&handle).ok_or(Error::InvalidArg)?;
+
+        Ok(deadline.saturating_sub(self.now_us()?))
+    }
+
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error> {
+        self.alarms.remove(&handle).map(|_| ()).ok_or(Error::InvalidArg)
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep_us(&mut self, us: u64) -> Option<Sleep> {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_micros(us);
+        Some(Box::pin(StdSleep { deadline, waker: None }))
+    }
+}
+
+/// Sleep future for [StdTime], woken by a shared timer thread so no executor support is required
+#[cfg(feature = "async")]
+struct StdSleep {
+    deadline: std::time::Instant,
+    waker: Option<timer::SharedWaker>,
+}
+
+#[cfg(feature = "async")]
+impl core::future::Future for StdSleep {
+    type Output = ();
+
+    fn poll(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<()> {
+        if std::time::Instant::now() >= self.deadline {
+            return core::task::Poll::Ready(());
+        }
+
+        match &self.waker {
+            // Update the waker in case the task has moved
+            Some(w) => *w.lock().unwrap() = cx.waker().clone(),
+            // Register with the timer thread on first poll
+            None => {
+                let w = std::sync::Arc::new(std::sync::Mutex::new(cx.waker().clone()));
+                timer::register(self.deadline, w.clone());
+                self.waker = Some(w);
+            }
+        }
+
+        core::task::Poll::Pending
+    }
+}
+
+/// Timer thread shared by all [StdSleep] futures, started on first use
+#[cfg(feature = "async")]
+mod timer {
+    use std::collections::BinaryHeap;
+    use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+    use std::sync::{Arc, Mutex};
+    use std::task::Waker;
+    use std::time::Instant;
+
+    /// Waker shared between a sleep future and the timer thread
+    pub(super) type SharedWaker = Arc<Mutex<Waker>>;
+
+    /// Pending wake, ordered so the earliest deadline is at the top of the heap
+    struct Entry(Instant, SharedWaker);
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Entry {}
+
+    impl PartialOrd for Entry {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Entry {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            other.0.cmp(&self.0)
+        }
+    }
+
+    static TIMER: Mutex<Option<Sender<Entry>>> = Mutex::new(None);
+
+    /// Wake the provided waker once the deadline has passed
+    pub(super) fn register(deadline: Instant, waker: SharedWaker) {
+        let mut timer = TIMER.lock().unwrap();
+        let tx = timer.get_or_insert_with(|| {
+            let (tx, rx) = channel();
+            std::thread::spawn(move || run(rx));
+            tx
+        });
+
+        // The receiver is never dropped, as the sender is held for the life of the process
+        let _ = tx.send(Entry(deadline, waker));
+    }
+
+    fn run(rx: std::sync::mpsc::Receiver<Entry>) {
+        let mut pending = BinaryHeap::new();
+
+        loop {
+            // Wait for new entries until the earliest deadline
+            let r = match pending.peek() {
+                Some(Entry(deadline, _)) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    rx.recv_timeout(timeout)
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match r {
+                Ok(e) => pending.push(e),
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now = Instant::now();
+            while pending.peek().map(|Entry(d, _)| *d <= now).unwrap_or(false) {
+                if let Some(Entry(_, w)) = pending.pop() {
+                    w.lock().unwrap().wake_by_ref();
+                }
+            }
+        }
+    }
+}
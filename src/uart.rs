@@ -4,6 +4,108 @@
 
 use super::Error;
 
+/// UART parity
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+/// UART stop bits
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum StopBits {
+    One,
+    OnePointFive,
+    Two,
+}
+
+/// UART flow control
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FlowControl {
+    None,
+    RtsCts,
+}
+
+/// UART read / write operation flags, see `$uart_flags` in `uart.witx`.
+///
+/// With no flags set operations block until the full buffer has been transferred.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct UartFlags(pub u32);
+
+impl UartFlags {
+    /// Blocking operation
+    pub const NONE: Self = Self(0);
+    /// Return immediately with the data that could be transferred, which may be none
+    pub const NON_BLOCKING: Self = Self(1 << 0);
+    /// Return once the buffer is complete or the provided timeout has elapsed
+    pub const TIMEOUT: Self = Self(1 << 1);
+    /// Return reads once data has been received and the line goes idle
+    pub const READ_UNTIL_IDLE: Self = Self(1 << 2);
+
+    /// Check whether all of the provided flags are set
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for UartFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// UART line configuration, see `$uart_cfg` in `uart.witx`
+#[derive(Clone, PartialEq, Debug)]
+pub struct UartConfig {
+    /// Baud rate
+    pub baud: u32,
+    /// Data bits per character (5 to 9)
+    pub data_bits: u8,
+    /// Parity
+    pub parity: Parity,
+    /// Stop bits
+    pub stop_bits: StopBits,
+    /// Flow control
+    pub flow_control: FlowControl,
+    /// RTS pin for flow control, -1 for platform default
+    pub rts: i32,
+    /// CTS pin for flow control, -1 for platform default
+    pub cts: i32,
+}
+
+impl UartConfig {
+    /// 8N1 configuration without flow control at the provided baud rate
+    pub const fn new(baud: u32) -> Self {
+        Self {
+            baud,
+            data_bits: 8,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            flow_control: FlowControl::None,
+            rts: -1,
+            cts: -1,
+        }
+    }
+}
+
 /// UART context abstraction.
 ///
 /// This hides runtime implementation details to simplify implementing UART contexts.
@@ -13,9 +115,33 @@ pub trait Uart {
 
     fn deinit(&mut self, handle: i32) -> Result<(), Error>;
 
-    fn write(&mut self, handle: i32, flags: u32, data: &[u8]) -> Result<(), Error>;
+    /// Apply a line configuration by handle,
+    /// returning [Error::Unsupported] if any setting cannot be honoured
+    fn configure(&mut self, _handle: i32, _cfg: &UartConfig) -> Result<(), Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Write data by handle, returning the number of bytes written.
+    ///
+    /// `timeout_ms` applies only when [UartFlags::TIMEOUT] is set.
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error>;
 
-    fn read(&mut self, handle: i32, flags: u32, buff: &mut [u8]) -> Result<(), Error>;
+    /// Read data by handle, returning the number of bytes received.
+    ///
+    /// `timeout_ms` applies only when [UartFlags::TIMEOUT] is set.
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error>;
 }
 
 /// Blanket Uart implementation for &mut T where T: Uart
@@ -28,12 +154,28 @@ impl<T: Uart> Uart for &mut T {
         <T as Uart>::deinit(self, handle)
     }
 
-    fn write(&mut self, handle: i32, flags: u32, data: &[u8]) -> Result<(), Error> {
-        <T as Uart>::write(self, handle, flags, data)
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        <T as Uart>::configure(self, handle, cfg)
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        <T as Uart>::write(self, handle, flags, timeout_ms, data)
     }
 
-    fn read(&mut self, handle: i32, flags: u32, buff: &mut [u8]) -> Result<(), Error> {
-        <T as Uart>::read(self, handle, flags, buff)
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        <T as Uart>::read(self, handle, flags, timeout_ms, buff)
     }
 }
 
@@ -49,11 +191,23 @@ impl Uart for NullUart {
         Err(Error::Unsupported)
     }
 
-    fn write(&mut self, _handle: i32, _flags: u32, _data: &[u8]) -> Result<(), Error> {
+    fn write(
+        &mut self,
+        _handle: i32,
+        _flags: UartFlags,
+        _timeout_ms: u32,
+        _data: &[u8],
+    ) -> Result<usize, Error> {
         Err(Error::Unsupported)
     }
 
-    fn read(&mut self, _handle: i32, _flags: u32, _buff: &mut [u8]) -> Result<(), Error> {
+    fn read(
+        &mut self,
+        _handle: i32,
+        _flags: UartFlags,
+        _timeout_ms: u32,
+        _buff: &mut [u8],
+    ) -> Result<usize, Error> {
         Err(Error::Unsupported)
     }
 }
@@ -0,0 +1,73 @@
+//! ADC API adaptation from [Adc] to wiggle generated [super::api::adc::Adc] interface
+
+use core::ops::DerefMut;
+
+use crate::{Error, Adc, AdcReference, wiggle::api::types};
+
+use wiggle::GuestPtr;
+
+/// Wrapper for wiggle-generated ADC api
+impl <D: Adc> super::api::adc::Adc for D {
+    fn init(
+        &mut self,
+        port: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u32,
+        reference: types::AdcReference,
+    ) -> Result<i32, Error> {
+        log::debug!(
+            "Opening ADC port: {} (channel: {} pin: {} resolution: {} reference: {:?})",
+            port,
+            channel,
+            pin,
+            resolution,
+            reference
+        );
+
+        let resolution = u8::try_from(resolution).map_err(|_| Error::InvalidArg)?;
+
+        let reference = match reference {
+            types::AdcReference::Default => AdcReference::Default,
+            types::AdcReference::Internal => AdcReference::Internal,
+            types::AdcReference::External => AdcReference::External,
+            types::AdcReference::Supply => AdcReference::Supply,
+        };
+
+        Adc::init(self, port, channel, pin, resolution, reference)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Closing ADC handle: {}", handle);
+
+        Adc::deinit(self, handle)
+    }
+
+    /// Read a single sample from an ADC channel
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        log::debug!("ADC read handle: {}", handle);
+
+        Adc::read(self, handle)
+    }
+
+    /// Read multiple samples from an ADC channel
+    fn read_multi<'a>(
+        &mut self,
+        handle: i32,
+        rate_hz: u32,
+        buff: &GuestPtr<'a, [u16]>,
+    ) -> Result<(), Error> {
+        let mut b = buff.as_slice_mut()
+            .map_err(|_| Error::InvalidArg)?
+            .ok_or(Error::Unsupported)?;
+
+        log::debug!(
+            "ADC read_multi handle: {} rate_hz: {} samples: {}",
+            handle,
+            rate_hz,
+            b.len()
+        );
+
+        Adc::read_multi(self, handle, rate_hz, b.deref_mut())
+    }
+}
@@ -0,0 +1,52 @@
+//! Device API adaptation from [Device] to wiggle generated [super::api::device::Device] interface
+
+use core::ops::DerefMut;
+
+use crate::{Error, Device, wiggle::api::types};
+
+/// Borrow a guest handle array for filling
+fn handles<'a>(h: &types::HandleArray<'a>) -> Result<wiggle::GuestSliceMut<'a, i32>, Error> {
+    h.ptr.as_array(h.len)
+        .as_slice_mut()
+        .map_err(|_| Error::InvalidArg)?
+        .ok_or(Error::Unsupported)
+}
+
+/// Wrapper for wiggle-generated device api
+impl <D: Device> super::api::device::Device for D {
+    /// Fetch pre-configured SPI handles
+    fn spi(&mut self, spi: &types::HandleArray) -> Result<u32, Error> {
+        let mut h = handles(spi)?;
+
+        log::debug!("Device spi handles: {}", h.len());
+
+        Device::spi(self, h.deref_mut()).map(|n| n as u32)
+    }
+
+    /// Fetch pre-configured I2C handles
+    fn i2c(&mut self, i2c: &types::HandleArray) -> Result<u32, Error> {
+        let mut h = handles(i2c)?;
+
+        log::debug!("Device i2c handles: {}", h.len());
+
+        Device::i2c(self, h.deref_mut()).map(|n| n as u32)
+    }
+
+    /// Fetch pre-configured GPIO input handles
+    fn gpio_in(&mut self, gpio_in: &types::HandleArray) -> Result<u32, Error> {
+        let mut h = handles(gpio_in)?;
+
+        log::debug!("Device gpio_in handles: {}", h.len());
+
+        Device::gpio_in(self, h.deref_mut()).map(|n| n as u32)
+    }
+
+    /// Fetch pre-configured GPIO output handles
+    fn gpio_out(&mut self, gpio_out: &types::HandleArray) -> Result<u32, Error> {
+        let mut h = handles(gpio_out)?;
+
+        log::debug!("Device gpio_out handles: {}", h.len());
+
+        Device::gpio_out(self, h.deref_mut()).map(|n| n as u32)
+    }
+}
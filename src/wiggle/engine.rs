@@ -0,0 +1,345 @@
+//! Helpers for linking an [Engine] into wasmtime
+//!
+//! Each module is registered via a transparent wrapper over the engine that fetches
+//! the driver on every call, so modules for which the [Engine] accessor returns `None`
+//! are still linked and return [Error::Unsupported] to the guest.
+
+use embedded_hal::digital::PinState;
+
+use crate::*;
+
+use super::api;
+
+/// Bounds required for linking, including `Send` when the `async` feature is enabled
+#[cfg(feature = "async")]
+pub trait MaybeSend: Send {}
+
+#[cfg(feature = "async")]
+impl<T: Send> MaybeSend for T {}
+
+/// Bounds required for linking, including `Send` when the `async` feature is enabled
+#[cfg(not(feature = "async"))]
+pub trait MaybeSend {}
+
+#[cfg(not(feature = "async"))]
+impl<T> MaybeSend for T {}
+
+/// Add all spec modules to a wasmtime [wasmtime::Linker], dispatching to the [Engine]
+/// returned by `get_cx`.
+///
+/// Modules for which the engine accessor returns `None` are stubbed, returning
+/// [Error::Unsupported] so guests importing them still link.
+pub fn add_engine_to_linker<T, E>(
+    linker: &mut wasmtime::Linker<T>,
+    get_cx: impl Fn(&mut T) -> &mut E + Send + Sync + Copy + 'static,
+) -> anyhow::Result<()>
+where
+    T: MaybeSend,
+    E: Engine + MaybeSend,
+{
+    api::gpio::add_to_linker(linker, move |t| EngineGpio::from_mut(get_cx(t)))?;
+    api::i2c::add_to_linker(linker, move |t| EngineI2c::from_mut(get_cx(t)))?;
+    api::spi::add_to_linker(linker, move |t| EngineSpi::from_mut(get_cx(t)))?;
+    api::uart::add_to_linker(linker, move |t| EngineUart::from_mut(get_cx(t)))?;
+    api::adc::add_to_linker(linker, move |t| EngineAdc::from_mut(get_cx(t)))?;
+    api::pwm::add_to_linker(linker, move |t| EnginePwm::from_mut(get_cx(t)))?;
+    api::time::add_to_linker(linker, move |t| EngineTime::from_mut(get_cx(t)))?;
+    api::device::add_to_linker(linker, move |t| EngineDevice::from_mut(get_cx(t)))?;
+
+    Ok(())
+}
+
+engine_wrapper!(EngineGpio);
+engine_wrapper!(EngineI2c);
+engine_wrapper!(EngineSpi);
+engine_wrapper!(EngineUart);
+engine_wrapper!(EngineAdc);
+engine_wrapper!(EnginePwm);
+engine_wrapper!(EngineTime);
+engine_wrapper!(EngineDevice);
+
+impl<E: Engine> EngineGpio<E> {
+    fn drv(&mut self) -> Result<&mut E::Gpio, Error> {
+        self.0.gpio().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Gpio for EngineGpio<E> {
+    fn init(&mut self, port: i32, pin: i32, cfg: &GpioConfig) -> Result<i32, Error> {
+        self.drv()?.init(port, pin, cfg)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn set(&mut self, handle: i32, state: PinState) -> Result<(), Error> {
+        self.drv()?.set(handle, state)
+    }
+
+    fn get(&mut self, handle: i32) -> Result<PinState, Error> {
+        self.drv()?.get(handle)
+    }
+
+    fn set_interrupt(&mut self, handle: i32, edge: Edge) -> Result<(), Error> {
+        self.drv()?.set_interrupt(handle, edge)
+    }
+
+    fn wait_event(&mut self, handles: &[i32], timeout_us: u32) -> Result<GpioEvent, Error> {
+        self.drv()?.wait_event(handles, timeout_us)
+    }
+}
+
+impl<E: Engine> EngineI2c<E> {
+    fn drv(&mut self) -> Result<&mut E::I2c, Error> {
+        self.0.i2c().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> I2c for EngineI2c<E> {
+    fn init(&mut self, dev: u32, baud: u32, sda: i32, sck: i32) -> Result<i32, Error> {
+        self.drv()?.init(dev, baud, sda, sck)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn write(&mut self, handle: i32, addr: u16, data: &[u8]) -> Result<(), Error> {
+        self.drv()?.write(handle, addr, data)
+    }
+
+    fn read(&mut self, handle: i32, addr: u16, buff: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.read(handle, addr, buff)
+    }
+
+    fn write_read(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        data: &[u8],
+        buff: &mut [u8],
+    ) -> Result<(), Error> {
+        self.drv()?.write_read(handle, addr, data, buff)
+    }
+
+    fn transaction(&mut self, handle: i32, addr: u16, ops: &mut [I2cOp]) -> Result<(), Error> {
+        self.drv()?.transaction(handle, addr, ops)
+    }
+}
+
+impl<E: Engine> EngineSpi<E> {
+    fn drv(&mut self) -> Result<&mut E::Spi, Error> {
+        self.0.spi().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Spi for EngineSpi<E> {
+    fn init(
+        &mut self,
+        dev: u32,
+        baud: u32,
+        mosi: i32,
+        miso: i32,
+        sck: i32,
+        cs: i32,
+    ) -> Result<i32, Error> {
+        self.drv()?.init(dev, baud, mosi, miso, sck, cs)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn read(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.read(handle, data)
+    }
+
+    fn write(&mut self, handle: i32, data: &[u8]) -> Result<(), Error> {
+        self.drv()?.write(handle, data)
+    }
+
+    fn transfer(&mut self, handle: i32, read: &mut [u8], write: &[u8]) -> Result<(), Error> {
+        self.drv()?.transfer(handle, read, write)
+    }
+
+    fn transfer_inplace(&mut self, handle: i32, data: &mut [u8]) -> Result<(), Error> {
+        self.drv()?.transfer_inplace(handle, data)
+    }
+
+    fn exec(&mut self, handle: i32, ops: &mut [SpiOp]) -> Result<(), Error> {
+        self.drv()?.exec(handle, ops)
+    }
+}
+
+impl<E: Engine> EngineUart<E> {
+    fn drv(&mut self) -> Result<&mut E::Uart, Error> {
+        self.0.uart().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Uart for EngineUart<E> {
+    fn init(&mut self, dev: u32, baud: u32, tx: i32, rx: i32) -> Result<i32, Error> {
+        self.drv()?.init(dev, baud, tx, rx)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn configure(&mut self, handle: i32, cfg: &UartConfig) -> Result<(), Error> {
+        self.drv()?.configure(handle, cfg)
+    }
+
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        data: &[u8],
+    ) -> Result<usize, Error> {
+        self.drv()?.write(handle, flags, timeout_ms, data)
+    }
+
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: UartFlags,
+        timeout_ms: u32,
+        buff: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.drv()?.read(handle, flags, timeout_ms, buff)
+    }
+}
+
+impl<E: Engine> EngineAdc<E> {
+    fn drv(&mut self) -> Result<&mut E::Adc, Error> {
+        self.0.adc().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Adc for EngineAdc<E> {
+    fn init(
+        &mut self,
+        dev: u32,
+        channel: u32,
+        pin: i32,
+        resolution: u8,
+        reference: AdcReference,
+    ) -> Result<i32, Error> {
+        self.drv()?.init(dev, channel, pin, resolution, reference)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn read(&mut self, handle: i32) -> Result<u16, Error> {
+        self.drv()?.read(handle)
+    }
+
+    fn read_multi(&mut self, handle: i32, rate_hz: u32, buff: &mut [u16]) -> Result<(), Error> {
+        self.drv()?.read_multi(handle, rate_hz, buff)
+    }
+}
+
+impl<E: Engine> EnginePwm<E> {
+    fn drv(&mut self) -> Result<&mut E::Pwm, Error> {
+        self.0.pwm().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Pwm for EnginePwm<E> {
+    fn init(&mut self, dev: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        self.drv()?.init(dev, channel, pin, freq_hz)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.deinit(handle)
+    }
+
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        self.drv()?.set_duty(handle, duty)
+    }
+
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        self.drv()?.set_frequency(handle, freq_hz)
+    }
+
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.enable(handle)
+    }
+
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.disable(handle)
+    }
+
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        self.drv()?.get_max_duty(handle)
+    }
+}
+
+impl<E: Engine> EngineTime<E> {
+    fn drv(&mut self) -> Result<&mut E::Time, Error> {
+        self.0.time().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Time for EngineTime<E> {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        self.drv()?.now_us()
+    }
+
+    fn delay_us(&mut self, us: u32) -> Result<(), Error> {
+        self.drv()?.delay_us(us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) -> Result<(), Error> {
+        self.drv()?.delay_ms(ms)
+    }
+
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error> {
+        self.drv()?.alarm_start(timeout_us)
+    }
+
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error> {
+        self.drv()?.alarm_remaining(handle)
+    }
+
+    fn alarm_wait(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.alarm_wait(handle)
+    }
+
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error> {
+        self.drv()?.alarm_cancel(handle)
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep_us(&mut self, us: u64) -> Option<crate::Sleep> {
+        self.drv().ok()?.sleep_us(us)
+    }
+}
+
+impl<E: Engine> EngineDevice<E> {
+    fn drv(&mut self) -> Result<&mut E::Device, Error> {
+        self.0.device().ok_or(Error::Unsupported)
+    }
+}
+
+impl<E: Engine> Device for EngineDevice<E> {
+    fn spi(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        self.drv()?.spi(handles)
+    }
+
+    fn i2c(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        self.drv()?.i2c(handles)
+    }
+
+    fn gpio_in(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        self.drv()?.gpio_in(handles)
+    }
+
+    fn gpio_out(&mut self, handles: &mut [i32]) -> Result<usize, Error> {
+        self.drv()?.gpio_out(handles)
+    }
+}
@@ -1,16 +1,44 @@
 //! GPIO API adaptation from [Gpio] to wiggle generated [super::api::gpio::Gpio] interface
 
-use crate::{Error, Gpio, wiggle::api::types};
+use crate::{Error, Gpio, GpioConfig, Direction, Pull, Drive, DriveStrength, Edge, wiggle::api::types};
 
 use embedded_hal::digital::{PinState};
 
+use wiggle::GuestPtr;
+
 /// Wrapper for wiggle-generated GPIO api
 impl <D: Gpio> super::api::gpio::Gpio for D {
-    /// Initialise the provided GPIO pin in input or output mode
-    fn init(&mut self, port: i32, pin: i32, mode: types::Mode) -> Result<i32, Error> {
-        log::debug!("GPIO init port: {} pin: {} mode: {:?}", port, pin, mode);
+    /// Initialise the provided GPIO pin with the provided configuration
+    fn init(&mut self, port: i32, pin: i32, cfg: &types::GpioCfg) -> Result<i32, Error> {
+        log::debug!("GPIO init port: {} pin: {} cfg: {:?}", port, pin, cfg);
+
+        let cfg = GpioConfig {
+            direction: match cfg.direction {
+                types::Mode::Input => Direction::Input,
+                types::Mode::Output => Direction::Output,
+            },
+            pull: match cfg.pull {
+                types::Pull::None => Pull::None,
+                types::Pull::Up => Pull::Up,
+                types::Pull::Down => Pull::Down,
+            },
+            drive: match cfg.drive {
+                types::Drive::PushPull => Drive::PushPull,
+                types::Drive::OpenDrain => Drive::OpenDrain,
+            },
+            strength: match cfg.strength {
+                types::DriveStrength::Default => DriveStrength::Default,
+                types::DriveStrength::Low => DriveStrength::Low,
+                types::DriveStrength::Medium => DriveStrength::Medium,
+                types::DriveStrength::High => DriveStrength::High,
+            },
+            initial: match cfg.initial {
+                types::Value::High => PinState::High,
+                types::Value::Low => PinState::Low,
+            },
+        };
 
-        Gpio::init(self, port, pin, mode == types::Mode::Output)
+        Gpio::init(self, port, pin, &cfg)
     }
 
     /// Deinitialise the specified GPIO pin
@@ -43,4 +71,36 @@ impl <D: Gpio> super::api::gpio::Gpio for D {
             PinState::Low => Ok(types::Value::Low),
         }
     }
+
+    /// Configure GPIO pin interrupt edges
+    fn set_interrupt(&mut self, dev: i32, edge: types::Edge) -> Result<(), Error> {
+        log::debug!("GPIO set_interrupt handle: {} edge: {:?}", dev, edge);
+
+        let edge = match edge {
+            types::Edge::None => Edge::None,
+            types::Edge::Rising => Edge::Rising,
+            types::Edge::Falling => Edge::Falling,
+            types::Edge::Both => Edge::Both,
+        };
+
+        Gpio::set_interrupt(self, dev, edge)
+    }
+
+    /// Wait for an event on one of the provided GPIO pins
+    fn wait_event<'a>(&mut self, handles: &GuestPtr<'a, [i32]>, timeout_us: u32) -> Result<types::GpioEvent, Error> {
+        let h = handles.to_vec().map_err(|_| Error::InvalidArg)?;
+
+        log::debug!("GPIO wait_event handles: {:?} timeout_us: {}", h, timeout_us);
+
+        let e = Gpio::wait_event(self, &h, timeout_us)?;
+
+        let edge = match e.edge {
+            Edge::None => types::Edge::None,
+            Edge::Rising => types::Edge::Rising,
+            Edge::Falling => types::Edge::Falling,
+            Edge::Both => types::Edge::Both,
+        };
+
+        Ok(types::GpioEvent{ handle: e.handle, edge })
+    }
 }
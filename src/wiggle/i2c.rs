@@ -2,7 +2,15 @@
 
 use core::ops::{Deref, DerefMut};
 
-use crate::{Error, I2c, wiggle::api::types};
+use crate::{Error, I2c, I2cOp, wiggle::api::types};
+
+use wiggle::{GuestPtr, GuestSlice, GuestSliceMut};
+
+/// Guest buffer borrowed for the duration of an I2C transaction operation
+enum OpBuff<'a> {
+    Read(GuestSliceMut<'a, u8>),
+    Write(GuestSlice<'a, u8>),
+}
 
 /// Wrapper for wiggle-generated I2C api
 impl <D: I2c> super::api::i2c::I2c for D {
@@ -72,4 +80,41 @@ impl <D: I2c> super::api::i2c::I2c for D {
 
         I2c::write_read(self, handle, addr, d1.deref(), b1.deref_mut())
     }
+
+    /// Execute a transaction on an I2c device
+    fn transaction<'a>(
+        &mut self,
+        handle: i32,
+        addr: u16,
+        ops: &GuestPtr<'a, [types::I2cOp<'a>]>,
+    ) -> Result<(), Error> {
+        log::debug!(
+            "I2C transaction handle: {} addr: {} ({} ops)",
+            handle,
+            addr,
+            ops.len()
+        );
+
+        // Borrow guest buffers for each operation
+        let mut buffs = Vec::with_capacity(ops.len() as usize);
+        for o in ops.iter() {
+            let o = o.and_then(|o| o.read()).map_err(|_| Error::InvalidArg)?;
+            let d = o.ptr.as_array(o.len);
+
+            let b = match o.kind {
+                types::I2cOpKind::Read => d.as_slice_mut().map(|b| b.map(OpBuff::Read)),
+                types::I2cOpKind::Write => d.as_slice().map(|b| b.map(OpBuff::Write)),
+            };
+
+            buffs.push(b.map_err(|_| Error::InvalidArg)?.ok_or(Error::Unsupported)?);
+        }
+
+        // Map to operations for execution
+        let mut i2c_ops: Vec<_> = buffs.iter_mut().map(|b| match b {
+            OpBuff::Read(b) => I2cOp::Read(b.deref_mut()),
+            OpBuff::Write(b) => I2cOp::Write(&**b),
+        }).collect();
+
+        I2c::transaction(self, handle, addr, &mut i2c_ops)
+    }
 }
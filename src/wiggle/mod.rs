@@ -11,6 +11,15 @@ mod uart;
 mod gpio;
 mod spi;
 mod i2c;
+mod adc;
+mod pwm;
+mod time;
+mod device;
+
+#[cfg(feature = "wasmtime")]
+mod engine;
+#[cfg(feature = "wasmtime")]
+pub use engine::{add_engine_to_linker, MaybeSend};
 
 /// Wiggle generated APIs
 pub mod api {
@@ -19,6 +28,25 @@ pub mod api {
 
     // Load WITX interface specifications
     // https://docs.rs/wiggle/0.28.0/wiggle/macro.from_witx.html
+    #[cfg(not(feature = "async"))]
+    wiggle::from_witx!({
+        witx: [
+            "./witx/common.witx",
+            "./witx/spi.witx",
+            "./witx/i2c.witx",
+            "./witx/uart.witx",
+            "./witx/gpio.witx",
+            "./witx/device.witx",
+            "./witx/adc.witx",
+            "./witx/pwm.witx",
+            "./witx/time.witx",
+        ],
+        errors: { errno => Error },
+    });
+
+    // Async variant, delays and alarm waits yield to the executor
+    // (requires wasmtime async support for the time module)
+    #[cfg(feature = "async")]
     wiggle::from_witx!({
         witx: [
             "./witx/common.witx",
@@ -27,8 +55,14 @@ pub mod api {
             "./witx/uart.witx",
             "./witx/gpio.witx",
             "./witx/device.witx",
+            "./witx/adc.witx",
+            "./witx/pwm.witx",
+            "./witx/time.witx",
         ],
         errors: { errno => Error },
+        async: {
+            time::{delay_us, delay_ms, alarm_wait},
+        },
     });
 
     pub use types::{Errno, UserErrorConversion};
@@ -49,6 +83,16 @@ pub mod api {
                 Error::Failed => Ok(Errno::Failed),
                 Error::NoDevice => Ok(Errno::NoDevice),
                 Error::Unsupported => Ok(Errno::Unsupported),
+                Error::Timeout => Ok(Errno::Timeout),
+                Error::NoAcknowledge => Ok(Errno::NoAcknowledge),
+                Error::ArbitrationLoss => Ok(Errno::ArbitrationLoss),
+                Error::Bus => Ok(Errno::Bus),
+                Error::Overrun => Ok(Errno::Overrun),
+                Error::ModeFault => Ok(Errno::ModeFault),
+                Error::FrameFormat => Ok(Errno::FrameFormat),
+                Error::ChipSelectFault => Ok(Errno::ChipSelectFault),
+                Error::Parity => Ok(Errno::Parity),
+                Error::Noise => Ok(Errno::Noise),
             }
         }
     }
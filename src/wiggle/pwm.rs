@@ -0,0 +1,59 @@
+//! PWM API adaptation from [Pwm] to wiggle generated [super::api::pwm::Pwm] interface
+
+use crate::{Error, Pwm};
+
+/// Wrapper for wiggle-generated PWM api
+impl <D: Pwm> super::api::pwm::Pwm for D {
+    fn init(&mut self, port: u32, channel: u32, pin: i32, freq_hz: u32) -> Result<i32, Error> {
+        log::debug!(
+            "Opening PWM port: {} (channel: {} pin: {} freq_hz: {})",
+            port,
+            channel,
+            pin,
+            freq_hz
+        );
+
+        Pwm::init(self, port, channel, pin, freq_hz)
+    }
+
+    fn deinit(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Closing PWM handle: {}", handle);
+
+        Pwm::deinit(self, handle)
+    }
+
+    /// Set PWM channel duty cycle
+    fn set_duty(&mut self, handle: i32, duty: u32) -> Result<(), Error> {
+        log::debug!("PWM set_duty handle: {} duty: {}", handle, duty);
+
+        Pwm::set_duty(self, handle, duty)
+    }
+
+    /// Set PWM channel frequency
+    fn set_frequency(&mut self, handle: i32, freq_hz: u32) -> Result<(), Error> {
+        log::debug!("PWM set_frequency handle: {} freq_hz: {}", handle, freq_hz);
+
+        Pwm::set_frequency(self, handle, freq_hz)
+    }
+
+    /// Enable PWM channel output
+    fn enable(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("PWM enable handle: {}", handle);
+
+        Pwm::enable(self, handle)
+    }
+
+    /// Disable PWM channel output
+    fn disable(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("PWM disable handle: {}", handle);
+
+        Pwm::disable(self, handle)
+    }
+
+    /// Fetch PWM channel maximum duty cycle
+    fn get_max_duty(&mut self, handle: i32) -> Result<u32, Error> {
+        log::debug!("PWM get_max_duty handle: {}", handle);
+
+        Pwm::get_max_duty(self, handle)
+    }
+}
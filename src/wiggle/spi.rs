@@ -1,8 +1,15 @@
 use core::ops::{Deref, DerefMut};
 
-use crate::{Error, Spi, wiggle::api::types};
+use crate::{Error, Spi, SpiOp, wiggle::api::types};
 
-use wiggle::GuestPtr;
+use wiggle::{GuestPtr, GuestSlice, GuestSliceMut};
+
+/// Guest buffer borrowed for the duration of an SPI exec operation
+enum OpBuff<'a> {
+    Read(GuestSliceMut<'a, u8>),
+    Write(GuestSlice<'a, u8>),
+    Transfer(GuestSliceMut<'a, u8>),
+}
 
 impl <D: Spi> super::api::spi::Spi for D {
     fn init(
@@ -72,9 +79,31 @@ impl <D: Spi> super::api::spi::Spi for D {
         Spi::transfer_inplace(self, handle, d1.deref_mut())
     }
 
-    fn exec<'a>(&mut self, _handle: i32, ops: &GuestPtr<'a, [types::Op]>) -> Result<(), Error> {
-        let _num_ops = ops.len();
-        // TODO: idk yet how guest types etc. are going to work here
-        todo!()
+    fn exec<'a>(&mut self, handle: i32, ops: &GuestPtr<'a, [types::Op<'a>]>) -> Result<(), Error> {
+        log::debug!("SPI Exec {} ({} ops)", handle, ops.len());
+
+        // Borrow guest buffers for each operation, these must not overlap
+        let mut buffs = Vec::with_capacity(ops.len() as usize);
+        for o in ops.iter() {
+            let o = o.and_then(|o| o.read()).map_err(|_| Error::InvalidArg)?;
+            let d = o.ptr.as_array(o.len);
+
+            let b = match o.kind {
+                types::OpKind::Read => d.as_slice_mut().map(|b| b.map(OpBuff::Read)),
+                types::OpKind::Write => d.as_slice().map(|b| b.map(OpBuff::Write)),
+                types::OpKind::Transfer => d.as_slice_mut().map(|b| b.map(OpBuff::Transfer)),
+            };
+
+            buffs.push(b.map_err(|_| Error::InvalidArg)?.ok_or(Error::Unsupported)?);
+        }
+
+        // Map to operations for execution
+        let mut spi_ops: Vec<_> = buffs.iter_mut().map(|b| match b {
+            OpBuff::Read(b) => SpiOp::Read(b.deref_mut()),
+            OpBuff::Write(b) => SpiOp::Write(&**b),
+            OpBuff::Transfer(b) => SpiOp::Transfer(b.deref_mut()),
+        }).collect();
+
+        Spi::exec(self, handle, &mut spi_ops)
     }
 }
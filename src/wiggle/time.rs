@@ -0,0 +1,137 @@
+//! Time API adaptation from [Time] to wiggle generated [super::api::time::Time] interface
+//!
+//! With the `async` feature delays and alarm waits await [Time::sleep_us] futures
+//! rather than blocking the thread, falling back to polling [Time::now_us] and yielding
+//! to the executor (busy-waiting) where a platform provides no sleep future.
+
+use crate::{Error, Time};
+
+/// Wrapper for wiggle-generated time api
+#[cfg(not(feature = "async"))]
+impl <D: Time> super::api::time::Time for D {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        Time::now_us(self)
+    }
+
+    fn delay_us(&mut self, us: u32) -> Result<(), Error> {
+        log::trace!("Time delay_us: {}", us);
+
+        Time::delay_us(self, us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) -> Result<(), Error> {
+        log::trace!("Time delay_ms: {}", ms);
+
+        Time::delay_ms(self, ms)
+    }
+
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error> {
+        log::debug!("Time alarm_start timeout_us: {}", timeout_us);
+
+        Time::alarm_start(self, timeout_us)
+    }
+
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error> {
+        Time::alarm_remaining(self, handle)
+    }
+
+    fn alarm_wait(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Time alarm_wait handle: {}", handle);
+
+        Time::alarm_wait(self, handle)
+    }
+
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Time alarm_cancel handle: {}", handle);
+
+        Time::alarm_cancel(self, handle)
+    }
+}
+
+/// Wrapper for wiggle-generated time api, yielding to the executor while waiting
+#[cfg(feature = "async")]
+#[wiggle::async_trait]
+impl <D: Time + Send> super::api::time::Time for D {
+    fn now_us(&mut self) -> Result<u64, Error> {
+        Time::now_us(self)
+    }
+
+    async fn delay_us(&mut self, us: u32) -> Result<(), Error> {
+        log::trace!("Time delay_us: {}", us);
+
+        sleep(self, us as u64).await
+    }
+
+    async fn delay_ms(&mut self, ms: u32) -> Result<(), Error> {
+        log::trace!("Time delay_ms: {}", ms);
+
+        sleep(self, ms as u64 * 1000).await
+    }
+
+    fn alarm_start(&mut self, timeout_us: u64) -> Result<i32, Error> {
+        log::debug!("Time alarm_start timeout_us: {}", timeout_us);
+
+        Time::alarm_start(self, timeout_us)
+    }
+
+    fn alarm_remaining(&mut self, handle: i32) -> Result<u64, Error> {
+        Time::alarm_remaining(self, handle)
+    }
+
+    async fn alarm_wait(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Time alarm_wait handle: {}", handle);
+
+        loop {
+            match Time::alarm_remaining(self, handle)? {
+                0 => return Ok(()),
+                r => sleep(self, r).await?,
+            }
+        }
+    }
+
+    fn alarm_cancel(&mut self, handle: i32) -> Result<(), Error> {
+        log::debug!("Time alarm_cancel handle: {}", handle);
+
+        Time::alarm_cancel(self, handle)
+    }
+}
+
+/// Wait for at least the provided number of microseconds,
+/// using the platform sleep future where available
+#[cfg(feature = "async")]
+async fn sleep<D: Time + Send>(d: &mut D, us: u64) -> Result<(), Error> {
+    if let Some(f) = Time::sleep_us(d, us) {
+        f.await;
+        return Ok(());
+    }
+
+    // Busy-wait fallback
+    let deadline = Time::now_us(d)?.saturating_add(us);
+    while Time::now_us(d)? < deadline {
+        YieldNow(false).await;
+    }
+
+    Ok(())
+}
+
+/// Future that returns pending once, yielding to the executor
+#[cfg(feature = "async")]
+struct YieldNow(bool);
+
+#[cfg(feature = "async")]
+impl core::future::Future for YieldNow {
+    type Output = ();
+
+    fn poll(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<()> {
+        if self.0 {
+            return core::task::Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        core::task::Poll::Pending
+    }
+}
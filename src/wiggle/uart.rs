@@ -1,6 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
-use crate::{Error, Uart, wiggle::api::types};
+use crate::{Error, Uart, UartFlags, UartConfig, Parity, StopBits, FlowControl, wiggle::api::types};
 
 /// Wrapper for wiggle-generated UART api
 impl <D: Uart> super::api::uart::Uart for D {
@@ -22,28 +22,76 @@ impl <D: Uart> super::api::uart::Uart for D {
         Uart::deinit(self, handle)
     }
 
+    /// Configure an Uart device
+    fn configure(&mut self, handle: i32, cfg: &types::UartCfg) -> Result<(), Error> {
+        log::debug!("UART configure handle: {} cfg: {:?}", handle, cfg);
+
+        let cfg = UartConfig {
+            baud: cfg.baud,
+            data_bits: match cfg.data_bits {
+                5..=9 => cfg.data_bits as u8,
+                _ => return Err(Error::InvalidArg),
+            },
+            parity: match cfg.parity {
+                types::Parity::None => Parity::None,
+                types::Parity::Even => Parity::Even,
+                types::Parity::Odd => Parity::Odd,
+            },
+            stop_bits: match cfg.stop_bits {
+                types::StopBits::One => StopBits::One,
+                types::StopBits::OnePointFive => StopBits::OnePointFive,
+                types::StopBits::Two => StopBits::Two,
+            },
+            flow_control: match cfg.flow_control {
+                types::FlowControl::None => FlowControl::None,
+                types::FlowControl::RtsCts => FlowControl::RtsCts,
+            },
+            rts: cfg.rts,
+            cts: cfg.cts,
+        };
+
+        Uart::configure(self, handle, &cfg)
+    }
+
     /// Write to an Uart device
-    fn write(&mut self, handle: i32, flags: i32, data: &types::Rbytes) -> Result<(), Error> {
+    fn write(
+        &mut self,
+        handle: i32,
+        flags: types::UartFlags,
+        timeout_ms: u32,
+        data: &types::Rbytes,
+    ) -> Result<u32, Error> {
         let d = data.ptr.as_array(data.len);
         let d1 = d.as_slice_mut().unwrap().unwrap();
 
         log::debug!(
-            "UART write handle: {} flags: {} data: {:02x?}",
+            "UART write handle: {} flags: {:?} timeout_ms: {} data: {:02x?}",
             handle,
             flags,
+            timeout_ms,
             d1.deref()
         );
 
-        Uart::write(self, handle, flags as u32, d1.deref())
+        let n = Uart::write(self, handle, UartFlags(flags.bits()), timeout_ms, d1.deref())?;
+
+        Ok(n as u32)
     }
 
     /// Read from an Uart device
-    fn read(&mut self, handle: i32, flags: i32, buff: &types::Wbytes) -> Result<(), Error> {
+    fn read(
+        &mut self,
+        handle: i32,
+        flags: types::UartFlags,
+        timeout_ms: u32,
+        buff: &types::Wbytes,
+    ) -> Result<u32, Error> {
         let b = buff.ptr.as_array(buff.len);
         let mut b1 = b.as_slice_mut().unwrap().unwrap();
 
-        log::debug!("UART read handle: {} flags: {}", handle, flags);
+        log::debug!("UART read handle: {} flags: {:?} timeout_ms: {}", handle, flags, timeout_ms);
+
+        let n = Uart::read(self, handle, UartFlags(flags.bits()), timeout_ms, b1.deref_mut())?;
 
-        Uart::read(self, handle, flags as u32, b1.deref_mut())
+        Ok(n as u32)
     }
 }
@@ -0,0 +1,210 @@
+//! Round-trip Rust engines through the C driver interface,
+//! exporting a [MockEngine] with [CDrivers] and calling it via [CEngine]
+
+use embedded_hal::digital::PinState;
+
+use wasm_embedded_spec::bindgen::*;
+use wasm_embedded_spec::mock::{MockEngine, MockOp, Op, State};
+use wasm_embedded_spec::*;
+
+/// Export a scripted engine, returning the drivers and a [CEngine] calling them
+fn export(ops: Vec<MockOp>) -> (CDrivers<MockEngine>, CEngine) {
+    let d = CDrivers::new(Box::new(MockEngine::new(ops)));
+    let c = unsafe {
+        CEngine::new(d.ctx())
+            .with_gpio(d.gpio())
+            .with_i2c(d.i2c())
+            .with_spi(d.spi())
+            .with_uart(d.uart())
+    };
+    (d, c)
+}
+
+#[test]
+fn roundtrip() {
+    let cfg = GpioConfig { pull: Pull::Down, strength: DriveStrength::High, ..GpioConfig::output() };
+    let line = UartConfig { parity: Parity::Odd, stop_bits: StopBits::Two, ..UartConfig::new(9600) };
+    let (mut d, mut c) = export(vec![
+        MockOp::new(Op::gpio_init(0, 5, &cfg), 1),
+        MockOp::new(Op::GpioSet { handle: 1, state: State::High }, 0),
+        MockOp::new(Op::I2cWrite { handle: 2, addr: 0x50, data_out: vec![0x10] }, 0),
+        MockOp::new(Op::I2cRead { handle: 2, addr: 0x50, data_in: vec![0xaa, 0xbb] }, 0),
+        MockOp::new(Op::SpiWrite { handle: 3, data_out: vec![0x9f] }, 0),
+        MockOp::new(Op::SpiTransfer { handle: 3, data_out: vec![1, 2], data_in: vec![3, 4] }, 0),
+        MockOp::new(Op::SpiRead { handle: 3, data_in: vec![5] }, 0),
+        MockOp::new(Op::uart_configure(4, &line), 0),
+        MockOp::new(
+            Op::UartWrite {
+                handle: 4,
+                flags: 1,
+                timeout_ms: 0,
+                data_out: b"abc".to_vec(),
+                count: Some(2),
+            },
+            0,
+        ),
+    ]);
+
+    assert_eq!(c.gpio().unwrap().init(0, 5, &cfg), Ok(1));
+    assert_eq!(c.gpio().unwrap().set(1, PinState::High), Ok(()));
+
+    let mut buff = [0u8; 2];
+    let mut ops = [I2cOp::Write(&[0x10]), I2cOp::Read(&mut buff)];
+    assert_eq!(c.i2c().unwrap().transaction(2, 0x50, &mut ops), Ok(()));
+    assert_eq!(buff, [0xaa, 0xbb]);
+
+    let (mut data, mut buff) = ([1u8, 2], [0u8; 1]);
+    let mut ops = [SpiOp::Write(&[0x9f]), SpiOp::Transfer(&mut data), SpiOp::Read(&mut buff)];
+    assert_eq!(c.spi().unwrap().exec(3, &mut ops), Ok(()));
+    assert_eq!((data, buff), ([3, 4], [5]));
+
+    assert_eq!(c.uart().unwrap().configure(4, &line), Ok(()));
+    assert_eq!(c.uart().unwrap().write(4, UartFlags::NON_BLOCKING, 0, b"abc"), Ok(2));
+
+    d.engine().done().unwrap();
+}
+
+#[test]
+fn errno() {
+    let errors = [
+        Error::InvalidArg,
+        Error::Failed,
+        Error::NoDevice,
+        Error::Unexpected,
+        Error::Unsupported,
+        Error::Timeout,
+        Error::NoAcknowledge,
+        Error::ArbitrationLoss,
+        Error::Bus,
+        Error::Overrun,
+        Error::ModeFault,
+        Error::FrameFormat,
+        Error::ChipSelectFault,
+        Error::Parity,
+        Error::Noise,
+    ];
+    let ops = (1..=errors.len() as i32)
+        .map(|n| MockOp::new(Op::GpioSet { handle: n, state: State::Low }, -n))
+        .collect();
+    let (mut d, mut c) = export(ops);
+
+    for (n, e) in errors.iter().enumerate() {
+        assert_eq!(c.gpio().unwrap().set(n as i32 + 1, PinState::Low), Err(e.clone()));
+    }
+
+    d.engine().done().unwrap();
+}
+
+#[test]
+fn null_functions() {
+    let mut d = CDrivers::new(Box::new(MockEngine::new(vec![
+        MockOp::new(
+            Op::I2cWriteRead { handle: 1, addr: 0x20, data_out: vec![1], data_in: vec![2] },
+            0,
+        ),
+        MockOp::new(Op::SpiTransfer { handle: 2, data_out: vec![3], data_in: vec![4] }, 0),
+    ])));
+
+    let gpio = gpio_drv_t { set: None, ..d.gpio() };
+    let i2c = i2c_drv_t { transaction: None, ..d.i2c() };
+    let spi = spi_drv_t { exec: None, ..d.spi() };
+    let mut c = unsafe { CEngine::new(d.ctx()).with_gpio(gpio).with_i2c(i2c).with_spi(spi) };
+
+    // Missing peripherals and functions are unsupported
+    assert!(c.uart().is_none());
+    assert_eq!(c.gpio().unwrap().set(1, PinState::High), Err(Error::Unsupported));
+
+    // Missing transaction / exec functions fall back to per-operation calls,
+    // with I2C writes followed by reads combined to preserve the repeated start
+    let mut buff = [0u8; 1];
+    let mut ops = [I2cOp::Write(&[1]), I2cOp::Read(&mut buff)];
+    assert_eq!(c.i2c().unwrap().transaction(1, 0x20, &mut ops), Ok(()));
+    assert_eq!(buff, [2]);
+
+    let mut data = [3u8];
+    assert_eq!(c.spi().unwrap().exec(2, &mut [SpiOp::Transfer(&mut data)]), Ok(()));
+    assert_eq!(data, [4]);
+
+    d.engine().done().unwrap();
+}
+
+#[test]
+fn max_ops() {
+    let writes = |n| (0..n).map(|i| MockOp::new(Op::SpiWrite { handle: 1, data_out: vec![i] }, 0));
+    let (mut d, mut c) = export(writes(MAX_OPS as u8).collect());
+
+    let data: Vec<_> = (0..=MAX_OPS as u8).map(|i| [i]).collect();
+
+    // Longer lists are rejected without calling the driver
+    let mut ops: Vec<_> = data.iter().map(|d| SpiOp::Write(d)).collect();
+    assert_eq!(c.spi().unwrap().exec(1, &mut ops), Err(Error::InvalidArg));
+    let mut ops: Vec<_> = data.iter().map(|d| I2cOp::Write(d)).collect();
+    assert_eq!(c.i2c().unwrap().transaction(1, 0x20, &mut ops), Err(Error::InvalidArg));
+
+    let mut ops: Vec<_> = data[..MAX_OPS].iter().map(|d| SpiOp::Write(d)).collect();
+    assert_eq!(c.spi().unwrap().exec(1, &mut ops), Ok(()));
+
+    d.engine().done().unwrap();
+
+    // Unless per-operation calls are used
+    let mut d = CDrivers::new(Box::new(MockEngine::new(writes(MAX_OPS as u8 + 1).collect())));
+    let mut c = unsafe { CEngine::new(d.ctx()).with_spi(spi_drv_t { exec: None, ..d.spi() }) };
+    let mut ops: Vec<_> = data.iter().map(|d| SpiOp::Write(d)).collect();
+    assert_eq!(c.spi().unwrap().exec(1, &mut ops), Ok(()));
+
+    d.engine().done().unwrap();
+}
+
+/// GPIO panicking on use
+struct Panics;
+
+impl Engine for Panics {
+    type Gpio = Self;
+
+    fn gpio(&mut self) -> Option<&mut Self::Gpio> {
+        Some(self)
+    }
+}
+
+impl Gpio for Panics {
+    fn init(&mut self, _port: i32, _pin: i32, _cfg: &GpioConfig) -> Result<i32, Error> {
+        panic!("init")
+    }
+
+    fn deinit(&mut self, _handle: i32) -> Result<(), Error> {
+        panic!("deinit")
+    }
+
+    fn set(&mut self, _handle: i32, _state: PinState) -> Result<(), Error> {
+        panic!("set")
+    }
+
+    fn get(&mut self, _handle: i32) -> Result<PinState, Error> {
+        panic!("get")
+    }
+}
+
+#[test]
+fn invalid_calls() {
+    let d = CDrivers::new(Box::new(Panics));
+    let set = d.gpio().set.unwrap();
+
+    // Panics are caught at the C boundary
+    assert_eq!(unsafe { set(d.ctx(), 1, 1) }, error_to_res(&Error::Failed));
+
+    // Null contexts are rejected
+    assert_eq!(unsafe { set(core::ptr::null(), 1, 1) }, error_to_res(&Error::InvalidArg));
+}
+
+#[test]
+fn invalid_config() {
+    let (mut d, mut c) = export(vec![]);
+
+    // Data bits outside 5..=9 are rejected without calling the engine
+    for data_bits in [0, 4, 10, 200] {
+        let cfg = UartConfig { data_bits, ..UartConfig::new(9600) };
+        assert_eq!(c.uart().unwrap().configure(1, &cfg), Err(Error::InvalidArg));
+    }
+
+    d.engine().done().unwrap();
+}
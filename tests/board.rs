@@ -0,0 +1,157 @@
+//! Limit a scripted engine to the devices described by a board
+
+use wasm_embedded_spec::board::{Board, BoardEngine};
+use wasm_embedded_spec::mock::{MockEngine, MockOp, Op};
+use wasm_embedded_spec::*;
+
+const BOARD: &str = r#"
+name = "test"
+
+[[i2c]]
+name = "sensor"
+port = 0
+sda = 4
+scl = 5
+
+[[spi]]
+name = "flash"
+aliases = [ "spi0" ]
+port = 1
+cs = 17
+
+[[uart]]
+name = "console"
+port = 0
+baud = 9600
+tx = 0
+rx = 1
+
+[[gpio]]
+name = "led0"
+port = 0
+pin = 25
+direction = "output"
+
+[[gpio]]
+name = "led1"
+port = 0
+pin = 26
+direction = "output"
+
+[[gpio]]
+name = "button"
+port = 0
+pin = 2
+direction = "input"
+pull = "up"
+"#;
+
+fn board(ops: Vec<MockOp>) -> BoardEngine<MockEngine> {
+    BoardEngine::new(BOARD.parse().unwrap(), MockEngine::new(ops))
+}
+
+#[test]
+fn defaults() {
+    let b: Board = BOARD.parse().unwrap();
+
+    assert_eq!((b.i2c[0].baud, b.i2c[0].sda), (100_000, 4));
+    assert_eq!((b.spi[0].baud, b.spi[0].mosi, b.spi[0].cs), (1_000_000, -1, 17));
+    assert_eq!(b.uart[0].baud, 9600);
+    assert_eq!((b.gpio[0].pull, b.gpio[2].pull), (Pull::None, Pull::Up));
+}
+
+#[test]
+fn init_defaults() {
+    let mut e = board(vec![
+        MockOp::new(Op::I2cInit { port: 0, baud: 100_000, sda: 4, scl: 5 }, 1),
+        MockOp::new(
+            Op::SpiInit { port: 1, baud: 2_000_000, mosi: -1, miso: -1, sck: -1, cs: 17 },
+            2,
+        ),
+        MockOp::new(Op::UartInit { port: 0, baud: 9600, tx: 0, rx: 1 }, 3),
+    ]);
+
+    // Zero bauds and -1 pins are replaced with the board configuration
+    assert_eq!(e.i2c().unwrap().init(0, 0, -1, -1), Ok(1));
+    assert_eq!(e.spi().unwrap().init(1, 2_000_000, -1, -1, -1, -1), Ok(2));
+    assert_eq!(e.uart().unwrap().init(0, 0, 0, -1), Ok(3));
+
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn init_validation() {
+    let led = GpioConfig::output();
+    let mut e = board(vec![
+        MockOp::new(Op::gpio_init(0, 25, &led), 1),
+        MockOp::new(Op::GpioDeinit { handle: 1 }, 0),
+        MockOp::new(Op::gpio_init(0, 25, &led), 4),
+        MockOp::new(Op::I2cInit { port: 0, baud: 100_000, sda: 4, scl: 5 }, 2),
+    ]);
+
+    // Devices and pins not on the board are rejected
+    assert_eq!(e.gpio().unwrap().init(0, 24, &led), Err(Error::NoDevice));
+    assert_eq!(e.i2c().unwrap().init(1, 0, -1, -1), Err(Error::NoDevice));
+    assert_eq!(e.i2c().unwrap().init(0, 0, 6, -1), Err(Error::NoDevice));
+    assert_eq!(e.spi().unwrap().init(1, 0, -1, -1, -1, 16), Err(Error::NoDevice));
+    assert_eq!(e.uart().unwrap().init(0, 0, -1, 2), Err(Error::NoDevice));
+
+    // Open devices are rejected until closed
+    assert_eq!(e.gpio().unwrap().init(0, 25, &led), Ok(1));
+    assert_eq!(e.gpio().unwrap().init(0, 25, &led), Err(Error::InvalidArg));
+    assert_eq!(e.gpio().unwrap().deinit(1), Ok(()));
+    assert_eq!(e.gpio().unwrap().init(0, 25, &led), Ok(4));
+
+    // Including devices opened by name
+    assert_eq!(e.handle("sensor"), Ok(2));
+    assert_eq!(e.i2c().unwrap().init(0, 0, -1, -1), Err(Error::InvalidArg));
+    assert_eq!(e.handle("led0"), Ok(4));
+    assert_eq!(e.handle("missing"), Err(Error::NoDevice));
+
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn deinit_failure() {
+    let mut e = board(vec![
+        MockOp::new(Op::UartInit { port: 0, baud: 9600, tx: 0, rx: 1 }, 3),
+        MockOp::new(Op::UartDeinit { handle: 3 }, -2),
+        MockOp::new(Op::UartDeinit { handle: 3 }, 0),
+        MockOp::new(Op::UartInit { port: 0, baud: 9600, tx: 0, rx: 1 }, 5),
+    ]);
+
+    // Devices remain open if the inner deinit fails
+    assert_eq!(e.uart().unwrap().init(0, 0, -1, -1), Ok(3));
+    assert_eq!(e.uart().unwrap().deinit(3), Err(Error::Failed));
+    assert_eq!(e.uart().unwrap().init(0, 0, -1, -1), Err(Error::InvalidArg));
+    assert_eq!(e.handle("console"), Ok(3));
+
+    assert_eq!(e.uart().unwrap().deinit(3), Ok(()));
+    assert_eq!(e.uart().unwrap().init(0, 0, -1, -1), Ok(5));
+
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn enumeration() {
+    let led = GpioConfig::output();
+    let mut e = board(vec![
+        MockOp::new(Op::gpio_init(0, 25, &led), 1),
+        MockOp::new(Op::gpio_init(0, 26, &led), 2),
+    ]);
+
+    // Devices past the end of the buffer are counted but not opened
+    let mut handles = [0; 1];
+    assert_eq!(e.device().unwrap().gpio_out(&mut handles), Ok(2));
+    assert_eq!(handles, [1]);
+
+    assert_eq!(e.device().unwrap().gpio_out(&mut [0; 0]), Ok(2));
+
+    // Open devices return existing handles
+    let mut handles = [0; 2];
+    assert_eq!(e.device().unwrap().gpio_out(&mut handles), Ok(2));
+    assert_eq!(handles, [1, 2]);
+    assert_eq!(e.gpio().unwrap().init(0, 26, &led), Err(Error::InvalidArg));
+
+    e.inner().done().unwrap();
+}
@@ -0,0 +1,102 @@
+;; Guest exercising the host bindings, shared by the runtime test harnesses
+(module
+  (import "gpio" "init" (func $gpio_init (param i32 i32 i32 i32) (result i32)))
+  (import "gpio" "set" (func $gpio_set (param i32 i32) (result i32)))
+  (import "gpio" "get" (func $gpio_get (param i32 i32) (result i32)))
+  (import "i2c" "init" (func $i2c_init (param i32 i32 i32 i32 i32) (result i32)))
+  (import "i2c" "write_read" (func $i2c_write_read (param i32 i32 i32 i32) (result i32)))
+  (import "i2c" "transaction" (func $i2c_transaction (param i32 i32 i32 i32) (result i32)))
+  (import "spi" "write" (func $spi_write (param i32 i32) (result i32)))
+  (import "spi" "transfer_inplace" (func $spi_transfer_inplace (param i32 i32) (result i32)))
+  (import "uart" "configure" (func $uart_configure (param i32 i32) (result i32)))
+  (import "uart" "write" (func $uart_write (param i32 i32 i32 i32 i32) (result i32)))
+  (import "pwm" "enable" (func $pwm_enable (param i32) (result i32)))
+
+  (memory (export "memory") 1)
+
+  (data (i32.const 0x200) "\01\02\03\04")
+  (data (i32.const 0x210) "hello")
+
+  ;; Write a `{ptr, len}` buffer record
+  (func $bytes (param $at i32) (param $ptr i32) (param $len i32)
+    (i32.store (local.get $at) (local.get $ptr))
+    (i32.store offset=4 (local.get $at) (local.get $len)))
+
+  ;; Write an `{kind, ptr, len}` operation record
+  (func $op (param $at i32) (param $kind i32) (param $ptr i32) (param $len i32)
+    (i32.store (local.get $at) (local.get $kind))
+    (i32.store offset=4 (local.get $at) (local.get $ptr))
+    (i32.store offset=8 (local.get $at) (local.get $len)))
+
+  (func (export "gpio") (result i32)
+    (local $e i32)
+    ;; Output, pull up, push-pull, default strength, initially high
+    (i32.store (i32.const 0x100) (i32.const 1))
+    (i32.store (i32.const 0x104) (i32.const 1))
+    (i32.store (i32.const 0x108) (i32.const 0))
+    (i32.store (i32.const 0x10c) (i32.const 0))
+    (i32.store (i32.const 0x110) (i32.const 1))
+
+    (local.set $e (call $gpio_init (i32.const 0) (i32.const 5) (i32.const 0x100) (i32.const 0x80)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (local.set $e (call $gpio_set (i32.load (i32.const 0x80)) (i32.const 0)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (local.set $e (call $gpio_get (i32.load (i32.const 0x80)) (i32.const 0x84)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (i32.load (i32.const 0x84)))
+
+  (func (export "gpio_error") (result i32)
+    (call $gpio_set (i32.const 7) (i32.const 1)))
+
+  (func (export "i2c") (result i32)
+    (local $e i32)
+    (local $h i32)
+    (local.set $e (call $i2c_init (i32.const 1) (i32.const 100000) (i32.const -1) (i32.const -1) (i32.const 0x80)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (local.set $h (i32.load (i32.const 0x80)))
+
+    (call $bytes (i32.const 0x300) (i32.const 0x200) (i32.const 2))
+    (call $bytes (i32.const 0x308) (i32.const 0x220) (i32.const 3))
+    (local.set $e (call $i2c_write_read (local.get $h) (i32.const 0x50) (i32.const 0x300) (i32.const 0x308)))
+    (if (local.get $e) (then (return (local.get $e))))
+
+    (call $op (i32.const 0x400) (i32.const 1) (i32.const 0x200) (i32.const 2))
+    (call $op (i32.const 0x40c) (i32.const 0) (i32.const 0x230) (i32.const 2))
+    (local.set $e (call $i2c_transaction (local.get $h) (i32.const 0x51) (i32.const 0x400) (i32.const 2)))
+    (if (local.get $e) (then (return (local.get $e))))
+
+    (i32.add
+      (i32.add (i32.load8_u (i32.const 0x220)) (i32.load8_u (i32.const 0x222)))
+      (i32.load8_u (i32.const 0x231))))
+
+  (func (export "i2c_too_many_ops") (result i32)
+    (call $i2c_transaction (i32.const 2) (i32.const 0x50) (i32.const 0x400) (i32.const 17)))
+
+  (func (export "spi") (result i32)
+    (local $e i32)
+    (call $bytes (i32.const 0x300) (i32.const 0x200) (i32.const 4))
+    (local.set $e (call $spi_write (i32.const 3) (i32.const 0x300)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (local.set $e (call $spi_transfer_inplace (i32.const 3) (i32.const 0x300)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (i32.load (i32.const 0x200)))
+
+  (func (export "spi_out_of_bounds") (result i32)
+    (call $bytes (i32.const 0x300) (i32.const 0xfff0) (i32.const 0x100))
+    (call $spi_write (i32.const 3) (i32.const 0x300)))
+
+  (func (export "spi_bad_ref") (result i32)
+    (call $spi_write (i32.const 3) (i32.const 0xfffc)))
+
+  (func (export "uart") (result i32)
+    (local $e i32)
+    (call $bytes (i32.const 0x310) (i32.const 0x210) (i32.const 5))
+    (local.set $e (call $uart_write (i32.const 4) (i32.const 0) (i32.const 10) (i32.const 0x310) (i32.const 0x80)))
+    (if (local.get $e) (then (return (local.get $e))))
+    (i32.add
+      (i32.mul (i32.load (i32.const 0x80)) (i32.const 100))
+      (call $uart_configure (i32.const 4) (i32.const 0x100))))
+
+  (func (export "pwm") (result i32)
+    (call $pwm_enable (i32.const 0)))
+)
@@ -0,0 +1,88 @@
+// Guest link tests shared by the wasm3 and WAMR bindings, included by each runtime's
+// `tests/link.rs` with `run` and `Run` from its harness in scope
+
+const GUEST: &str = include_str!("guest.wat");
+
+fn guest(func: &str) -> Run {
+    let wasm = wat::parse_str(GUEST).expect("Invalid guest");
+    run(&wasm, func)
+}
+
+#[test]
+fn gpio() {
+    let r = guest("gpio");
+
+    assert_eq!(r.result, Ok(1));
+    assert_eq!(r.log, ["gpio.init 0 5 direction=1 pull=1 initial=1", "gpio.set 1 0", "gpio.get 1"]);
+}
+
+#[test]
+fn driver_errors() {
+    let r = guest("gpio_error");
+
+    // `no_device`
+    assert_eq!(r.result, Ok(3));
+    assert_eq!(r.log, ["gpio.set 7 1"]);
+}
+
+#[test]
+fn i2c() {
+    let r = guest("i2c");
+
+    assert_eq!(r.result, Ok(0xA0 + 0xA2 + 0x55));
+    assert_eq!(
+        r.log,
+        [
+            "i2c.init 1 100000 -1 -1",
+            "i2c.write_read 2 0x50 0102 3",
+            "i2c.transaction 2 0x51 2",
+            "  write 0102",
+            "  read 2",
+        ]
+    );
+}
+
+#[test]
+fn too_many_ops() {
+    let r = guest("i2c_too_many_ops");
+
+    // `invalid_arg`, without calling the driver
+    assert_eq!(r.result, Ok(1));
+    assert!(r.log.is_empty());
+}
+
+#[test]
+fn spi() {
+    let r = guest("spi");
+
+    // Transferred bytes are inverted in place
+    assert_eq!(r.result, Ok(0xfbfcfdfe));
+    assert_eq!(r.log, ["spi.write 3 01020304", "spi.transfer_inplace 3 01020304"]);
+}
+
+#[test]
+fn out_of_bounds_traps() {
+    for f in ["spi_out_of_bounds", "spi_bad_ref"] {
+        let r = guest(f);
+
+        assert!(matches!(&r.result, Err(e) if e.contains("out of bounds")), "{}: {:?}", f, r);
+        assert!(r.log.is_empty());
+    }
+}
+
+#[test]
+fn uart() {
+    let r = guest("uart");
+
+    // 5 bytes written, `configure` is `unsupported` by the mock
+    assert_eq!(r.result, Ok(505));
+    assert_eq!(r.log, ["uart.write 4 0 10 hello"]);
+}
+
+#[test]
+fn unsupported() {
+    let r = guest("pwm");
+
+    assert_eq!(r.result, Ok(5));
+    assert!(r.log.is_empty());
+}
@@ -0,0 +1,180 @@
+/// Mock C drivers shared by the runtime host binding test harnesses,
+/// recording driver calls to a log.
+///
+/// Include after the driver headers (`wasm_embedded/gpio.h` etc.)
+
+#ifndef WASME_TESTS_MOCK_DRIVERS_H
+#define WASME_TESTS_MOCK_DRIVERS_H
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <string.h>
+
+/// Mock driver context, recording driver calls
+typedef struct {
+    char *log;
+    uint32_t log_len;
+    uint32_t index;
+} mock_t;
+
+/// Append a line to the mock log
+static void mock_log(const mock_t *ctx, const char *fmt, ...)
+{
+    mock_t *m = (mock_t *)ctx;
+    if (m->index >= m->log_len) {
+        return;
+    }
+
+    va_list args;
+    va_start(args, fmt);
+    int n = vsnprintf(m->log + m->index, m->log_len - m->index, fmt, args);
+    va_end(args);
+
+    if (n > 0) {
+        m->index += (uint32_t)n;
+    }
+    if (m->index >= m->log_len) {
+        m->index = m->log_len - 1;
+    }
+    if (m->index < m->log_len - 1) {
+        m->log[m->index++] = '\n';
+        m->log[m->index] = '\0';
+    }
+}
+
+/// Format bytes as hex for logging
+static const char *hex(const uint8_t *data, uint32_t len)
+{
+    static char buff[128];
+    uint32_t i = 0;
+
+    for (i = 0; i < len && i < sizeof(buff) / 2 - 1; i++) {
+        snprintf(buff + i * 2, 3, "%02x", data[i]);
+    }
+    buff[i * 2] = '\0';
+
+    return buff;
+}
+
+static int32_t gpio_init(const void *ctx, int32_t port, int32_t pin, const gpio_cfg_t *cfg)
+{
+    mock_log(ctx, "gpio.init %d %d direction=%u pull=%u initial=%u",
+             port, pin, cfg->direction, cfg->pull, cfg->initial);
+    return 1;
+}
+
+static int32_t gpio_set(const void *ctx, int32_t dev, uint32_t value)
+{
+    mock_log(ctx, "gpio.set %d %u", dev, value);
+    return dev == 1 ? 0 : -WASME_ERRNO_NO_DEVICE;
+}
+
+static int32_t gpio_get(const void *ctx, int32_t dev, uint32_t *value)
+{
+    mock_log(ctx, "gpio.get %d", dev);
+    *value = GPIO_VALUE_HIGH;
+    return 0;
+}
+
+static int32_t i2c_init(const void *ctx, uint32_t port, uint32_t baud, int32_t sda, int32_t scl)
+{
+    mock_log(ctx, "i2c.init %u %u %d %d", port, baud, sda, scl);
+    return 2;
+}
+
+static int32_t i2c_write_read(const void *ctx, int32_t handle, uint16_t addr,
+                              uint8_t *data, uint32_t data_len, uint8_t *buff, uint32_t buff_len)
+{
+    mock_log(ctx, "i2c.write_read %d 0x%02x %s %u", handle, addr, hex(data, data_len), buff_len);
+    for (uint32_t i = 0; i < buff_len; i++) {
+        buff[i] = (uint8_t)(0xA0 + i);
+    }
+    return 0;
+}
+
+static int32_t i2c_transaction(const void *ctx, int32_t handle, uint16_t addr,
+                               i2c_op_t *ops, uint32_t ops_len)
+{
+    mock_log(ctx, "i2c.transaction %d 0x%02x %u", handle, addr, ops_len);
+    for (uint32_t i = 0; i < ops_len; i++) {
+        switch (ops[i].kind) {
+        case I2C_OP_KIND_WRITE:
+            mock_log(ctx, "  write %s", hex(ops[i].ptr, ops[i].len));
+            break;
+        case I2C_OP_KIND_READ:
+            mock_log(ctx, "  read %u", ops[i].len);
+            memset(ops[i].ptr, 0x55, ops[i].len);
+            break;
+        default:
+            return -WASME_ERRNO_INVALID_ARG;
+        }
+    }
+    return 0;
+}
+
+static int32_t spi_init(const void *ctx, uint32_t port, uint32_t baud,
+                        int32_t mosi, int32_t miso, int32_t sck, int32_t cs)
+{
+    mock_log(ctx, "spi.init %u %u %d %d %d %d", port, baud, mosi, miso, sck, cs);
+    return 3;
+}
+
+static int32_t spi_write(const void *ctx, int32_t handle, uint8_t *data, uint32_t data_len)
+{
+    mock_log(ctx, "spi.write %d %s", handle, hex(data, data_len));
+    return 0;
+}
+
+static int32_t spi_transfer_inplace(const void *ctx, int32_t handle, uint8_t *data, uint32_t data_len)
+{
+    mock_log(ctx, "spi.transfer_inplace %d %s", handle, hex(data, data_len));
+    for (uint32_t i = 0; i < data_len; i++) {
+        data[i] = (uint8_t)~data[i];
+    }
+    return 0;
+}
+
+static int32_t uart_init(const void *ctx, uint32_t port, uint32_t baud, int32_t tx, int32_t rx)
+{
+    mock_log(ctx, "uart.init %u %u %d %d", port, baud, tx, rx);
+    return 4;
+}
+
+static int32_t uart_write(const void *ctx, int32_t handle, uint32_t flags, uint32_t timeout_ms,
+                          uint8_t *data, uint32_t data_len)
+{
+    mock_log(ctx, "uart.write %d %u %u %.*s", handle, flags, timeout_ms, (int)data_len, data);
+    return (int32_t)data_len;
+}
+
+static const gpio_drv_t mock_gpio = {
+    .init = gpio_init,
+    .set = gpio_set,
+    .get = gpio_get,
+};
+
+static const i2c_drv_t mock_i2c = {
+    .init = i2c_init,
+    .write_read = i2c_write_read,
+    .transaction = i2c_transaction,
+};
+
+static const spi_drv_t mock_spi = {
+    .init = spi_init,
+    .write = spi_write,
+    .transfer_inplace = spi_transfer_inplace,
+};
+
+// `configure` is not provided to check unsupported functions
+static const uart_drv_t mock_uart = {
+    .init = uart_init,
+    .write = uart_write,
+};
+
+// Empty drivers, all functions are unsupported
+static const adc_drv_t mock_adc;
+static const pwm_drv_t mock_pwm;
+static const time_drv_t mock_time;
+static const device_drv_t mock_device;
+
+#endif
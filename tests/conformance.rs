@@ -0,0 +1,67 @@
+//! Run guests against the shared fixtures with the conformance runner
+
+use wasm_embedded_spec::conformance::{run, run_file, ConformanceError};
+use wasm_embedded_spec::mock::{Expectations, MockError};
+
+/// Guest following `tests/gpio.toml`, with `wrong` setting the output low rather than high
+const GUEST: &str = r#"
+(module
+  (import "gpio" "init" (func $init (param i32 i32 i32 i32) (result i32)))
+  (import "gpio" "set" (func $set (param i32 i32) (result i32)))
+  (import "gpio" "get" (func $get (param i32 i32) (result i32)))
+
+  (memory (export "memory") 1)
+
+  ;; Open port 2 pin 3 as an output and set it to `state`, then read port 2 pin 4
+  (func $run (param $state i32)
+    ;; Output, no pull, push-pull, default strength, initially low
+    (i32.store (i32.const 0x100) (i32.const 1))
+    (drop (call $init (i32.const 2) (i32.const 3) (i32.const 0x100) (i32.const 0x80)))
+    (drop (call $set (i32.load (i32.const 0x80)) (local.get $state)))
+
+    ;; Input
+    (i32.store (i32.const 0x100) (i32.const 0))
+    (drop (call $init (i32.const 2) (i32.const 4) (i32.const 0x100) (i32.const 0x80)))
+    (drop (call $get (i32.load (i32.const 0x80)) (i32.const 0x84))))
+
+  (func (export "_start")
+    (call $run (i32.const 1)))
+
+  (func (export "wrong")
+    (call $run (i32.const 0)))
+)
+"#;
+
+fn fixture() -> String {
+    format!("{}/tests/gpio.toml", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn run_guest() {
+    let expectations = Expectations::load(fixture()).unwrap();
+
+    run(GUEST.as_bytes(), expectations, None).unwrap();
+}
+
+#[test]
+fn run_guest_file() {
+    let wasm = std::env::temp_dir().join(format!("conformance-{}.wat", std::process::id()));
+    std::fs::write(&wasm, GUEST).unwrap();
+
+    let pass = run_file(&wasm, fixture(), None);
+    let fail = run_file(&wasm, fixture(), Some("wrong"));
+    let missing = run_file(&wasm, fixture(), Some("missing"));
+    std::fs::remove_file(&wasm).unwrap();
+
+    pass.unwrap();
+
+    // The first divergence from the fixture is reported
+    match fail {
+        Err(ConformanceError::Mismatch(MockError::Mismatch { index: 1, expected, .. })) => {
+            assert!(expected.is_some())
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    assert!(matches!(missing, Err(ConformanceError::Setup(_))));
+}
@@ -0,0 +1,222 @@
+//! Inject faults into calls to a scripted engine
+
+use embedded_hal::digital::PinState;
+
+use wasm_embedded_spec::fault::{Call, Fault, FaultInjectingEngine, Rule};
+use wasm_embedded_spec::mock::{MockEngine, MockOp, Op, State};
+use wasm_embedded_spec::*;
+
+fn spi_write(handle: i32, data: &[u8]) -> MockOp {
+    MockOp::new(Op::SpiWrite { handle, data_out: data.to_vec() }, 0)
+}
+
+fn i2c_write(handle: i32, addr: u16, data: &[u8]) -> MockOp {
+    MockOp::new(Op::I2cWrite { handle, addr, data_out: data.to_vec() }, 0)
+}
+
+fn uart_write(handle: i32, data: &[u8]) -> MockOp {
+    let data_out = data.to_vec();
+    MockOp::new(Op::UartWrite { handle, flags: 0, timeout_ms: 0, data_out, count: None }, 0)
+}
+
+#[test]
+fn nth() {
+    let ops = vec![spi_write(1, &[1]), spi_write(1, &[3])];
+    let rule = Rule::new(Fault::Error(Error::Bus)).call(Call::SpiWrite).nth(2);
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0).with_rule(rule);
+
+    let spi = e.spi().unwrap();
+    assert_eq!(spi.write(1, &[1]), Ok(()));
+    assert_eq!(spi.write(1, &[2]), Err(Error::Bus));
+    assert_eq!(spi.write(1, &[3]), Ok(()));
+
+    assert_eq!(e.injected(), 1);
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn from_nth() {
+    let ops = vec![MockOp::new(Op::GpioSet { handle: 1, state: State::High }, 0)];
+    let rule = Rule::new(Fault::Error(Error::Failed)).call(Call::GpioSet).from_nth(2);
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0).with_rule(rule);
+
+    let gpio = e.gpio().unwrap();
+    assert_eq!(gpio.set(1, PinState::High), Ok(()));
+    assert_eq!(gpio.set(1, PinState::Low), Err(Error::Failed));
+    assert_eq!(gpio.set(1, PinState::High), Err(Error::Failed));
+
+    // Other calls are not matched
+    assert_eq!(gpio.get(1), Err(Error::Unexpected));
+
+    assert_eq!(e.injected(), 2);
+}
+
+#[test]
+fn events_and_configuration() {
+    let ops = vec![
+        MockOp::new(Op::GpioSetInterrupt { handle: 1, edge: Edge::Rising }, 0),
+        MockOp::new(
+            Op::GpioWaitEvent { handles: vec![1], timeout_us: 100, event: 1, edge: Edge::Rising },
+            0,
+        ),
+        MockOp::new(Op::uart_configure(3, &UartConfig::new(9600)), 0),
+    ];
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0)
+        .with_rule(Rule::new(Fault::Error(Error::Timeout)).call(Call::GpioWaitEvent).nth(1))
+        .with_rule(Rule::new(Fault::Error(Error::Failed)).call(Call::GpioSetInterrupt).nth(1))
+        .with_rule(Rule::new(Fault::Error(Error::Unsupported)).call(Call::UartConfigure).nth(1));
+
+    let gpio = e.gpio().unwrap();
+    assert_eq!(gpio.set_interrupt(1, Edge::Rising), Err(Error::Failed));
+    assert_eq!(gpio.set_interrupt(1, Edge::Rising), Ok(()));
+
+    // Event waits time out without calling the inner engine
+    assert_eq!(gpio.wait_event(&[1], 100), Err(Error::Timeout));
+    let event = GpioEvent { handle: 1, edge: Edge::Rising };
+    assert_eq!(gpio.wait_event(&[1], 100), Ok(event));
+
+    let uart = e.uart().unwrap();
+    assert_eq!(uart.configure(3, &UartConfig::new(9600)), Err(Error::Unsupported));
+    assert_eq!(uart.configure(3, &UartConfig::new(9600)), Ok(()));
+
+    assert_eq!(e.injected(), 3);
+    e.inner().done().unwrap();
+}
+
+/// SPI accepting all writes
+struct Sink;
+
+impl Engine for Sink {
+    type Spi = Self;
+
+    fn spi(&mut self) -> Option<&mut Self::Spi> {
+        Some(self)
+    }
+}
+
+impl Spi for Sink {
+    fn init(&mut self, _: u32, _: u32, _: i32, _: i32, _: i32, _: i32) -> Result<i32, Error> {
+        Ok(1)
+    }
+
+    fn deinit(&mut self, _handle: i32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn read(&mut self, _handle: i32, _data: &mut [u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write(&mut self, _handle: i32, _data: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn transfer(&mut self, _handle: i32, _read: &mut [u8], _write: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn transfer_inplace(&mut self, _handle: i32, _data: &mut [u8]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Write 1000 times with the provided failure probability, returning failed writes
+fn faults(seed: u64, p: f64) -> Vec<usize> {
+    let rule = Rule::new(Fault::Error(Error::Failed)).probability(p);
+    let mut e = FaultInjectingEngine::new(Sink, seed).with_rule(rule);
+
+    let f: Vec<_> = (0..1000).filter(|_| e.spi().unwrap().write(1, &[0]).is_err()).collect();
+    assert_eq!(e.injected(), f.len());
+    f
+}
+
+#[test]
+fn probability() {
+    assert!(faults(1, 0.0).is_empty());
+    assert_eq!(faults(1, 1.0).len(), 1000);
+
+    // Faults are reproducible for a given seed
+    let a = faults(1234, 0.25);
+    assert_eq!(a, faults(1234, 0.25));
+    assert_ne!(a, faults(4321, 0.25));
+    assert!((200..300).contains(&a.len()), "{} faults", a.len());
+}
+
+#[test]
+fn mirrors_inner_peripherals() {
+    let mut e = FaultInjectingEngine::new(Sink, 0);
+
+    assert!(e.spi().is_some());
+    assert!(e.gpio().is_none());
+    assert!(e.i2c().is_none());
+    assert!(e.uart().is_none());
+    assert!(e.adc().is_none());
+    assert!(e.pwm().is_none());
+}
+
+#[test]
+fn handle_addr_filters() {
+    let ops = vec![i2c_write(1, 0x51, &[2]), i2c_write(2, 0x50, &[3]), i2c_write(1, 0x50, &[4])];
+    let rule = Rule::new(Fault::Error(Error::NoAcknowledge)).handle(1).addr(0x50).nth(1);
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0).with_rule(rule);
+
+    let i2c = e.i2c().unwrap();
+    assert_eq!(i2c.write(1, 0x50, &[1]), Err(Error::NoAcknowledge));
+    assert_eq!(i2c.write(1, 0x51, &[2]), Ok(()));
+    assert_eq!(i2c.write(2, 0x50, &[3]), Ok(()));
+    assert_eq!(i2c.write(1, 0x50, &[4]), Ok(()));
+
+    assert_eq!(e.injected(), 1);
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn transactions() {
+    let ops = vec![
+        i2c_write(1, 0x50, &[0x10]),
+        MockOp::new(Op::I2cRead { handle: 1, addr: 0x50, data_in: vec![0xaa] }, 0),
+        spi_write(2, &[0x9f]),
+    ];
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0)
+        .with_rule(Rule::new(Fault::Error(Error::Bus)).call(Call::I2cRead).nth(1))
+        .with_rule(Rule::new(Fault::Error(Error::Overrun)).call(Call::SpiTransfer));
+
+    // Transactions fail on any component, without calling the inner engine
+    let mut buff = [0u8; 1];
+    let mut ops = [I2cOp::Write(&[0x10]), I2cOp::Read(&mut buff)];
+    assert_eq!(e.i2c().unwrap().transaction(1, 0x50, &mut ops), Err(Error::Bus));
+
+    let mut ops = [I2cOp::Write(&[0x10]), I2cOp::Read(&mut buff)];
+    assert_eq!(e.i2c().unwrap().transaction(1, 0x50, &mut ops), Ok(()));
+    assert_eq!(buff, [0xaa]);
+
+    let mut data = [0u8; 2];
+    let mut ops = [SpiOp::Write(&[0x9f]), SpiOp::Transfer(&mut data)];
+    assert_eq!(e.spi().unwrap().exec(2, &mut ops), Err(Error::Overrun));
+
+    assert_eq!(e.spi().unwrap().exec(2, &mut [SpiOp::Write(&[0x9f])]), Ok(()));
+
+    assert_eq!(e.injected(), 2);
+    e.inner().done().unwrap();
+}
+
+#[test]
+fn short() {
+    let ops = vec![
+        MockOp::new(Op::GpioSet { handle: 1, state: State::High }, 0),
+        uart_write(3, b"a"),
+        uart_write(3, b"bcd"),
+    ];
+    let rule = Rule::new(Fault::Short(1)).nth(1);
+    let mut e = FaultInjectingEngine::new(MockEngine::new(ops), 0).with_rule(rule);
+
+    // Non-UART calls are not counted
+    assert_eq!(e.gpio().unwrap().set(1, PinState::High), Ok(()));
+
+    let uart = e.uart().unwrap();
+    assert_eq!(uart.write(3, UartFlags::NONE, 0, b"abc"), Ok(1));
+    assert_eq!(uart.write(3, UartFlags::NONE, 0, b"bcd"), Ok(3));
+
+    assert_eq!(e.injected(), 1);
+    e.inner().done().unwrap();
+}
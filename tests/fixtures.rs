@@ -0,0 +1,150 @@
+//! Load the shared `tests/*.toml` fixtures and replay them through a [MockEngine]
+
+use embedded_hal::digital::PinState;
+
+use wasm_embedded_spec::mock::{Expectations, MockEngine, Op};
+use wasm_embedded_spec::*;
+
+fn load(name: &str) -> Expectations {
+    let path = format!("{}/tests/{}.toml", env!("CARGO_MANIFEST_DIR"), name);
+    Expectations::load(&path).unwrap_or_else(|e| panic!("failed to load {}: {:?}", path, e))
+}
+
+#[test]
+fn parse() {
+    let fixtures = [
+        ("gpio", 4),
+        ("i2c", 4),
+        ("i2c_transaction", 4),
+        ("spi", 6),
+        ("spi_transaction", 5),
+        ("uart", 3),
+        ("uart_idle", 4),
+        ("adc", 4),
+        ("pwm", 7),
+    ];
+
+    for (name, n) in fixtures {
+        let e = load(name);
+        assert_eq!(e.ops.len(), n, "{} operations", name);
+
+        // Fixtures survive a round trip through the fixture format
+        assert_eq!(e.to_string().parse::<Expectations>().unwrap(), e, "{} round trip", name);
+    }
+}
+
+#[test]
+fn replay_gpio() {
+    let mut m = MockEngine::new(load("gpio").ops);
+
+    let gpio = m.gpio().unwrap();
+    assert_eq!(gpio.init(2, 3, &GpioConfig::output()), Ok(1));
+    assert_eq!(gpio.set(1, PinState::High), Ok(()));
+    assert_eq!(gpio.init(2, 4, &GpioConfig::input()), Ok(2));
+    assert_eq!(gpio.get(2), Ok(PinState::Low));
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_i2c() {
+    let mut m = MockEngine::new(load("i2c").ops);
+
+    let i2c = m.i2c().unwrap();
+    assert_eq!(i2c.init(0, 4_000_000, -1, -1), Ok(1));
+    assert_eq!(i2c.write(1, 0x0a, &[0xaa, 0xbb, 0xcc]), Ok(()));
+
+    let mut buff = [0u8; 4];
+    assert_eq!(i2c.read(1, 0x0a, &mut buff), Ok(()));
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    let mut buff = [0u8; 4];
+    assert_eq!(i2c.write_read(1, 0x0a, &[0xaa, 0xbb, 0xcc], &mut buff), Ok(()));
+    assert_eq!(buff, [0x22, 0x33, 0x44, 0x55]);
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_spi() {
+    let mut m = MockEngine::new(load("spi").ops);
+
+    let spi = m.spi().unwrap();
+    assert_eq!(spi.init(0, 4_000_000, -1, -1, -1, -1), Ok(2));
+    assert_eq!(spi.write(2, &[0xaa, 0xbb, 0xcc]), Ok(()));
+
+    let mut buff = [0u8; 5];
+    assert_eq!(spi.read(2, &mut buff), Ok(()));
+    assert_eq!(buff, [0xab; 5]);
+
+    let mut buff = [0u8; 4];
+    assert_eq!(spi.transfer(2, &mut buff, &[0xaa, 0xbb, 0xcc, 0xdd]), Ok(()));
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    let mut buff = [0xaa, 0xbb, 0xcc, 0xdd];
+    assert_eq!(spi.transfer_inplace(2, &mut buff), Ok(()));
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    assert_eq!(spi.deinit(2), Ok(()));
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_uart() {
+    let mut m = MockEngine::new(load("uart").ops);
+
+    let uart = m.uart().unwrap();
+    assert_eq!(uart.init(0, 4_000_000, -1, -1), Ok(1));
+    assert_eq!(uart.write(1, UartFlags::NONE, 0, &[0xaa, 0xbb, 0xcc]), Ok(3));
+
+    let mut buff = [0u8; 4];
+    assert_eq!(uart.read(1, UartFlags::NONE, 0, &mut buff), Ok(4));
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_adc() {
+    let mut m = MockEngine::new(load("adc").ops);
+
+    let adc = m.adc().unwrap();
+    assert_eq!(adc.init(0, 1, -1, 12, AdcReference::Default), Ok(1));
+    assert_eq!(adc.read(1), Ok(2048));
+
+    let mut buff = [0u16; 4];
+    assert_eq!(adc.read_multi(1, 1000, &mut buff), Ok(()));
+    assert_eq!(buff, [100, 200, 300, 400]);
+    assert_eq!(adc.deinit(1), Ok(()));
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_pwm() {
+    let mut m = MockEngine::new(load("pwm").ops);
+
+    let pwm = m.pwm().unwrap();
+    assert_eq!(pwm.init(1, 2, -1, 1000), Ok(1));
+    let max = pwm.get_max_duty(1).unwrap();
+    assert_eq!(pwm.set_duty(1, max / 2), Ok(()));
+    assert_eq!(pwm.enable(1), Ok(()));
+    assert_eq!(pwm.set_frequency(1, 2000), Ok(()));
+    assert_eq!(pwm.disable(1), Ok(()));
+    assert_eq!(pwm.deinit(1), Ok(()));
+
+    m.done().unwrap();
+}
+
+#[test]
+fn replay_mismatch() {
+    let mut m = MockEngine::new(load("gpio").ops);
+
+    // Calls are checked against the fixture
+    assert_eq!(m.gpio().unwrap().init(2, 4, &GpioConfig::output()), Err(Error::Unexpected));
+    assert!(m.done().is_err());
+
+    let expected = Op::gpio_init(2, 3, &GpioConfig::output());
+    assert_eq!(load("gpio").ops[0].op, expected);
+}
@@ -0,0 +1,318 @@
+//! Adapt embedded-hal and embedded-io drivers to the engine traits
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::digital::PinState;
+
+use wasm_embedded_spec::hal::{HalGpio, HalI2c, HalSpiBus, HalSpiDevice, HalUart};
+use wasm_embedded_spec::*;
+
+/// Shared log of driver calls
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[test]
+fn serial_errors() {
+    use embedded_hal_nb::serial::ErrorKind;
+
+    assert_eq!(Error::from(ErrorKind::Overrun), Error::Overrun);
+    assert_eq!(Error::from(ErrorKind::FrameFormat), Error::FrameFormat);
+    assert_eq!(Error::from(ErrorKind::Parity), Error::Parity);
+    assert_eq!(Error::from(ErrorKind::Noise), Error::Noise);
+    assert_eq!(Error::from(ErrorKind::Other), Error::Failed);
+}
+
+/// Digital line, shared between an input and an output pin
+#[derive(Clone, Default)]
+struct Line(Rc<Cell<bool>>);
+
+impl embedded_hal::digital::ErrorType for Line {
+    type Error = Infallible;
+}
+
+impl embedded_hal::digital::InputPin for Line {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0.get())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.0.get())
+    }
+}
+
+impl embedded_hal::digital::OutputPin for Line {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set(true);
+        Ok(())
+    }
+}
+
+#[test]
+fn gpio() {
+    let (a, b) = (Line::default(), Line::default());
+    let mut g = HalGpio::new().with_output(0, 1, a.clone()).with_input(0, 2, b.clone());
+
+    // Pins are opened once, in the direction they were provided
+    let initial = GpioConfig { initial: PinState::High, ..GpioConfig::output() };
+    let out = g.init(0, 1, &initial).unwrap();
+    assert!(a.0.get());
+    assert_eq!(g.init(0, 1, &initial), Err(Error::InvalidArg));
+    assert_eq!(g.init(0, 2, &GpioConfig::output()), Err(Error::Unsupported));
+    assert_eq!(g.init(1, 1, &GpioConfig::output()), Err(Error::NoDevice));
+    let inp = g.init(0, 2, &GpioConfig::input()).unwrap();
+
+    assert_eq!(g.set(out, PinState::Low), Ok(()));
+    assert!(!a.0.get());
+    assert_eq!(g.get(out), Ok(PinState::Low));
+
+    b.0.set(true);
+    assert_eq!(g.get(inp), Ok(PinState::High));
+    assert_eq!(g.set(inp, PinState::Low), Err(Error::Unsupported));
+
+    // Closed pins may be reopened
+    assert_eq!(g.deinit(out), Ok(()));
+    assert_eq!(g.set(out, PinState::High), Err(Error::InvalidArg));
+    assert_eq!(g.deinit(out), Err(Error::InvalidArg));
+    assert_eq!(g.init(0, 1, &GpioConfig::output()), Ok(out));
+}
+
+/// I2C bus and SPI device / bus logging operations, reads return `id`
+struct Bus {
+    id: u8,
+    log: Log,
+}
+
+impl Bus {
+    fn new(id: u8, log: &Log) -> Self {
+        Self { id, log: log.clone() }
+    }
+
+    fn push(&self, s: String) {
+        self.log.borrow_mut().push(s);
+    }
+}
+
+impl embedded_hal::i2c::ErrorType for Bus {
+    type Error = embedded_hal::i2c::ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for Bus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+
+        if address == 0x10 {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        for o in operations {
+            match o {
+                Operation::Write(d) => self.push(format!("{} {:#x} write {:?}", self.id, address, d)),
+                Operation::Read(b) => {
+                    b.fill(self.id);
+                    self.push(format!("{} {:#x} read {}", self.id, address, b.len()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl embedded_hal::spi::ErrorType for Bus {
+    type Error = embedded_hal::spi::ErrorKind;
+}
+
+impl embedded_hal::spi::SpiDevice for Bus {
+    fn transaction(
+        &mut self,
+        operations: &mut [embedded_hal::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        use embedded_hal::spi::{Operation, SpiBus};
+
+        self.push(format!("{} select", self.id));
+        for o in operations {
+            match o {
+                Operation::Read(b) => SpiBus::read(self, b)?,
+                Operation::Write(d) => SpiBus::write(self, d)?,
+                Operation::Transfer(r, w) => SpiBus::transfer(self, r, w)?,
+                Operation::TransferInPlace(d) => SpiBus::transfer_in_place(self, d)?,
+                Operation::DelayNs(_) => (),
+            }
+        }
+        self.push(format!("{} deselect", self.id));
+        Ok(())
+    }
+}
+
+impl embedded_hal::spi::SpiBus for Bus {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        words.fill(self.id);
+        self.push(format!("{} read {}", self.id, words.len()));
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.push(format!("{} write {:?}", self.id, words));
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        self.push(format!("{} transfer {:?}", self.id, write));
+        read.fill(self.id);
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.push(format!("{} transfer {:?}", self.id, words));
+        words.fill(self.id);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.push(format!("{} flush", self.id));
+        Ok(())
+    }
+}
+
+#[test]
+fn i2c() {
+    let log = Log::default();
+    let mut i = HalI2c::new().with_bus(0, Bus::new(1, &log));
+
+    let h = i.init(0, 100_000, -1, -1).unwrap();
+    assert_eq!(i.init(0, 100_000, -1, -1), Err(Error::InvalidArg));
+    assert_eq!(i.init(1, 100_000, -1, -1), Err(Error::NoDevice));
+
+    let mut buff = [0u8; 2];
+    assert_eq!(i.write_read(h, 0x50, &[0x10], &mut buff), Ok(()));
+    assert_eq!(buff, [1, 1]);
+    let mut ops = [I2cOp::Write(&[0x20]), I2cOp::Read(&mut buff)];
+    assert_eq!(i.transaction(h, 0x50, &mut ops), Ok(()));
+
+    // 10-bit addresses are rejected and driver errors mapped
+    assert_eq!(i.write(h, 0x150, &[1]), Err(Error::InvalidArg));
+    assert_eq!(i.write(h, 0x10, &[1]), Err(Error::NoAcknowledge));
+
+    assert_eq!(
+        *log.borrow(),
+        ["1 0x50 write [16]", "1 0x50 read 2", "1 0x50 write [32]", "1 0x50 read 2"]
+    );
+}
+
+#[test]
+fn spi_device() {
+    let log = Log::default();
+    let mut s = HalSpiDevice::new()
+        .with_device(0, 4, Bus::new(1, &log))
+        .with_device(0, 5, Bus::new(2, &log));
+
+    // A chip select of -1 opens the first closed device on the port
+    let a = s.init(0, 1_000_000, -1, -1, -1, -1).unwrap();
+    let b = s.init(0, 1_000_000, -1, -1, -1, -1).unwrap();
+    assert_ne!(a, b);
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, -1), Err(Error::InvalidArg));
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, 4), Err(Error::InvalidArg));
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, 6), Err(Error::NoDevice));
+
+    let mut data = [0x9f, 0];
+    let mut ops = [SpiOp::Write(&[0x03]), SpiOp::Transfer(&mut data)];
+    assert_eq!(s.exec(b, &mut ops), Ok(()));
+    assert_eq!(data, [2, 2]);
+
+    assert_eq!(s.deinit(a), Ok(()));
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, 4), Ok(a));
+
+    assert_eq!(
+        *log.borrow(),
+        ["2 select", "2 write [3]", "2 transfer [159, 0]", "2 deselect"]
+    );
+}
+
+#[test]
+fn spi_bus() {
+    let log = Log::default();
+    let mut s = HalSpiBus::new().with_bus(0, Bus::new(1, &log));
+
+    // Chip select is left to the caller
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, 4), Err(Error::Unsupported));
+    let h = s.init(0, 1_000_000, -1, -1, -1, -1).unwrap();
+    assert_eq!(s.init(0, 1_000_000, -1, -1, -1, -1), Err(Error::InvalidArg));
+
+    let mut buff = [0u8; 1];
+    assert_eq!(s.write(h, &[0x9f]), Ok(()));
+    assert_eq!(s.exec(h, &mut [SpiOp::Write(&[0x03]), SpiOp::Read(&mut buff)]), Ok(()));
+    assert_eq!(buff, [1]);
+
+    assert_eq!(
+        *log.borrow(),
+        ["1 write [159]", "1 flush", "1 write [3]", "1 read 1", "1 flush"]
+    );
+}
+
+/// Serial port, reading from `rx` and writing to `tx`
+#[derive(Default)]
+struct Serial {
+    rx: VecDeque<u8>,
+    tx: Rc<RefCell<Vec<u8>>>,
+}
+
+impl embedded_io::ErrorType for Serial {
+    type Error = embedded_io::ErrorKind;
+}
+
+impl embedded_io::Read for Serial {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.rx.len());
+        for (b, d) in buf.iter_mut().zip(self.rx.drain(..n)) {
+            *b = d;
+        }
+        Ok(n)
+    }
+}
+
+impl embedded_io::Write for Serial {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.tx.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn uart() {
+    let serial = Serial { rx: b"abcdef".iter().copied().collect(), ..Default::default() };
+    let tx = serial.tx.clone();
+    let mut u = HalUart::new().with_serial(0, serial);
+
+    let h = u.init(0, 115_200, -1, -1).unwrap();
+    assert_eq!(u.init(0, 115_200, -1, -1), Err(Error::InvalidArg));
+    assert_eq!(u.init(1, 115_200, -1, -1), Err(Error::NoDevice));
+
+    assert_eq!(u.write(h, UartFlags::NONE, 0, b"hello"), Ok(5));
+    assert_eq!(*tx.borrow(), b"hello");
+
+    // Non-blocking and timeout operations are unsupported
+    assert_eq!(u.write(h, UartFlags::NON_BLOCKING, 0, b"x"), Err(Error::Unsupported));
+    assert_eq!(u.read(h, UartFlags::TIMEOUT, 10, &mut [0u8; 1]), Err(Error::Unsupported));
+
+    // Blocking reads fill the buffer, reads until idle return available data
+    let mut buff = [0u8; 4];
+    assert_eq!(u.read(h, UartFlags::NONE, 0, &mut buff[..2]), Ok(2));
+    assert_eq!(u.read(h, UartFlags::READ_UNTIL_IDLE, 0, &mut buff), Ok(4));
+    assert_eq!(&buff, b"cdef");
+
+    // Blocking reads reaching the end of the stream fail
+    assert_eq!(u.read(h, UartFlags::NONE, 0, &mut buff), Err(Error::Failed));
+}
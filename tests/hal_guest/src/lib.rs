@@ -0,0 +1,124 @@
+//! Guest exercising `wasm-embedded-hal` against the shared `tests/*.toml` fixtures,
+//! with each export run through the `wasm-embedded-conformance` runner.
+//!
+//! Failed checks trap so the runner reports the guest as failed.
+
+#![no_std]
+
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::i2c::{self, I2c as _};
+use embedded_hal::spi::{self, SpiBus, SpiDevice};
+use embedded_io::{Read, Write};
+
+use wasm_embedded_hal::{I2c, Input, Output, PinState, Pull, Spi, Uart, UartFlags};
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::arch::wasm32::unreachable()
+}
+
+/// Follows `tests/gpio.toml`, pins are not dropped as the fixture does not deinit them
+#[no_mangle]
+pub extern "C" fn gpio() {
+    let mut out = Output::new(2, 3, PinState::Low).unwrap();
+    out.set_high().unwrap();
+
+    let mut inp = Input::new(2, 4, Pull::None).unwrap();
+    assert!(inp.is_low().unwrap());
+
+    core::mem::forget(out);
+    core::mem::forget(inp);
+}
+
+/// Follows `tests/i2c.toml`, the bus is not dropped as the fixture does not deinit it
+#[no_mangle]
+pub extern "C" fn i2c() {
+    let mut i2c = I2c::new(0, 4_000_000, -1, -1).unwrap();
+    i2c.write(0x0a, &[0xaa, 0xbb, 0xcc]).unwrap();
+
+    let mut buff = [0u8; 4];
+    i2c.read(0x0a, &mut buff).unwrap();
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    i2c.write_read(0x0a, &[0xaa, 0xbb, 0xcc], &mut buff).unwrap();
+    assert_eq!(buff, [0x22, 0x33, 0x44, 0x55]);
+
+    core::mem::forget(i2c);
+}
+
+/// Follows `tests/i2c_transaction.toml`, with each operation packed into `i2c.transaction`
+#[no_mangle]
+pub extern "C" fn i2c_transaction() {
+    let mut i2c = I2c::new(1, 100_000, -1, -1).unwrap();
+    let mut buff = [0u8; 2];
+
+    i2c.transaction(0x48, &mut [i2c::Operation::Write(&[0x01]), i2c::Operation::Read(&mut buff)])
+        .unwrap();
+    assert_eq!(buff, [0x12, 0x34]);
+}
+
+/// Follows `tests/spi.toml` using [SpiBus]
+#[no_mangle]
+pub extern "C" fn spi() {
+    let mut spi = Spi::new(0, 4_000_000, -1, -1, -1, -1).unwrap();
+    SpiBus::write(&mut spi, &[0xaa, 0xbb, 0xcc]).unwrap();
+
+    let mut buff = [0u8; 5];
+    SpiBus::read(&mut spi, &mut buff).unwrap();
+    assert_eq!(buff, [0xab; 5]);
+
+    let mut buff = [0u8; 4];
+    SpiBus::transfer(&mut spi, &mut buff, &[0xaa, 0xbb, 0xcc, 0xdd]).unwrap();
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+
+    let mut buff = [0xaa, 0xbb, 0xcc, 0xdd];
+    SpiBus::transfer_in_place(&mut spi, &mut buff).unwrap();
+    assert_eq!(buff, [0x11, 0x22, 0x33, 0x44]);
+}
+
+/// Follows `tests/spi_transaction.toml` using [SpiDevice],
+/// with each operation packed into `spi.exec`
+#[no_mangle]
+pub extern "C" fn spi_transaction() {
+    let mut spi = Spi::new(1, 1_000_000, -1, -1, -1, 5).unwrap();
+    let mut id = [0u8; 3];
+    let mut status = [0x05, 0x00];
+
+    spi.transaction(&mut [
+        spi::Operation::Write(&[0x9f]),
+        spi::Operation::Read(&mut id),
+        spi::Operation::TransferInPlace(&mut status),
+    ])
+    .unwrap();
+    assert_eq!(id, [0xef, 0x40, 0x18]);
+    assert_eq!(status, [0xff, 0x02]);
+}
+
+/// Follows `tests/uart.toml` with blocking reads filling the buffer
+#[no_mangle]
+pub extern "C" fn uart() {
+    let mut uart = Uart::new(0, 4_000_000, -1, -1).unwrap();
+    uart.write_all(&[0xaa, 0xbb, 0xcc]).unwrap();
+
+    let mut buff = [0u8; 4];
+    let n = uart.read_with(UartFlags::BLOCKING, 0, &mut buff).unwrap();
+    assert_eq!(&buff[..n], &[0x11, 0x22, 0x33, 0x44]);
+
+    core::mem::forget(uart);
+}
+
+/// Follows `tests/uart_idle.toml`, [Read] returning what was received before the line went idle
+#[no_mangle]
+pub extern "C" fn uart_idle() {
+    let mut uart = Uart::new(0, 115_200, -1, -1).unwrap();
+    let mut buff = [0u8; 4];
+
+    let n = uart.read(&mut buff).unwrap();
+    assert_eq!(&buff[..n], &[0x11, 0x22]);
+
+    let n = uart.read(&mut buff).unwrap();
+    assert_eq!(&buff[..n], &[0x33]);
+
+    // Empty reads return without calling the runtime
+    assert_eq!(uart.read(&mut []).unwrap(), 0);
+}
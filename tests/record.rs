@@ -0,0 +1,191 @@
+//! Record traffic against a scripted engine, serialise the log and replay it
+
+use embedded_hal::digital::PinState;
+
+use wasm_embedded_spec::mock::{
+    Expectations, MockEngine, MockOp, Op, RecordingEngine, ReplayEngine, ReplayMode, State,
+};
+use wasm_embedded_spec::*;
+
+/// Scripted hardware operations for [guest], also the expected recording
+fn script() -> Vec<MockOp> {
+    let cfg = GpioConfig {
+        pull: Pull::Up,
+        strength: DriveStrength::High,
+        initial: PinState::High,
+        ..GpioConfig::output()
+    };
+    let line = UartConfig { parity: Parity::Even, ..UartConfig::new(9600) };
+
+    vec![
+        MockOp::new(Op::gpio_init(2, 3, &cfg), 1),
+        MockOp::new(Op::GpioSetInterrupt { handle: 1, edge: Edge::Rising }, 0),
+        MockOp::new(
+            Op::GpioWaitEvent { handles: vec![1], timeout_us: 1000, event: 1, edge: Edge::Rising },
+            0,
+        ),
+        MockOp::new(Op::I2cInit { port: 0, baud: 100_000, sda: -1, scl: -1 }, 2),
+        MockOp::new(Op::I2cWrite { handle: 2, addr: 0x50, data_out: vec![0x01] }, 0),
+        MockOp::new(Op::I2cRead { handle: 2, addr: 0x50, data_in: vec![0xaa, 0xbb] }, 0),
+        MockOp::new(
+            Op::SpiInit { port: 0, baud: 1_000_000, mosi: -1, miso: -1, sck: -1, cs: -1 },
+            3,
+        ),
+        MockOp::new(Op::SpiWrite { handle: 3, data_out: vec![0x9f] }, 0),
+        MockOp::new(
+            Op::SpiTransfer { handle: 3, data_out: vec![0, 0], data_in: vec![0x12, 0x34] },
+            0,
+        ),
+        MockOp::new(Op::UartInit { port: 1, baud: 9600, tx: -1, rx: -1 }, 4),
+        MockOp::new(Op::uart_configure(4, &line), 0),
+        MockOp::new(
+            Op::UartWrite {
+                handle: 4,
+                flags: 0,
+                timeout_ms: 0,
+                data_out: b"hello".to_vec(),
+                count: Some(2),
+            },
+            0,
+        ),
+        MockOp::new(
+            Op::UartRead { handle: 4, flags: 6, timeout_ms: 50, data_in: b"ok".to_vec() },
+            0,
+        ),
+        MockOp::new(Op::GpioSet { handle: 1, state: State::Low }, -3),
+    ]
+}
+
+/// Exercise the engine, returning a description of all results
+fn guest<E: Engine>(e: &mut E) -> Vec<String> {
+    let mut r = vec![];
+
+    let gpio = e.gpio().unwrap();
+    let cfg = GpioConfig {
+        pull: Pull::Up,
+        strength: DriveStrength::High,
+        initial: PinState::High,
+        ..GpioConfig::output()
+    };
+    r.push(format!("{:?}", gpio.init(2, 3, &cfg)));
+    r.push(format!("{:?}", gpio.set_interrupt(1, Edge::Rising)));
+    r.push(format!("{:?}", gpio.wait_event(&[1], 1000)));
+
+    let i2c = e.i2c().unwrap();
+    r.push(format!("{:?}", i2c.init(0, 100_000, -1, -1)));
+    let mut buff = [0u8; 2];
+    let mut ops = [I2cOp::Write(&[0x01]), I2cOp::Read(&mut buff)];
+    r.push(format!("{:?}", i2c.transaction(2, 0x50, &mut ops)));
+    r.push(format!("{:02x?}", buff));
+
+    let spi = e.spi().unwrap();
+    r.push(format!("{:?}", spi.init(0, 1_000_000, -1, -1, -1, -1)));
+    let mut data = [0u8; 2];
+    let mut ops = [SpiOp::Write(&[0x9f]), SpiOp::Transfer(&mut data)];
+    r.push(format!("{:?}", spi.exec(3, &mut ops)));
+    r.push(format!("{:02x?}", data));
+
+    let uart = e.uart().unwrap();
+    r.push(format!("{:?}", uart.init(1, 9600, -1, -1)));
+    let line = UartConfig { parity: Parity::Even, ..UartConfig::new(9600) };
+    r.push(format!("{:?}", uart.configure(4, &line)));
+    r.push(format!("{:?}", uart.write(4, UartFlags::NONE, 0, b"hello")));
+    let mut buff = [0u8; 8];
+    let flags = UartFlags::READ_UNTIL_IDLE | UartFlags::TIMEOUT;
+    r.push(format!("{:?}", uart.read(4, flags, 50, &mut buff)));
+    r.push(format!("{:02x?}", buff));
+
+    r.push(format!("{:?}", e.gpio().unwrap().set(1, PinState::Low)));
+
+    r
+}
+
+#[test]
+fn record_replay() {
+    // Record against the scripted hardware
+    let mut rec = RecordingEngine::new(MockEngine::new(script()));
+    let recorded = guest(&mut rec);
+    rec.inner().done().unwrap();
+
+    assert_eq!(recorded[2], "Ok(GpioEvent { handle: 1, edge: Rising })");
+    assert_eq!(recorded[5], "[aa, bb]");
+    assert_eq!(recorded[8], "[12, 34]");
+    assert_eq!(recorded[11], "Ok(2)");
+    assert_eq!(recorded[13], "[6f, 6b, 00, 00, 00, 00, 00, 00]");
+    assert_eq!(recorded[14], "Err(NoDevice)");
+
+    // Transactions are recorded as their component operations
+    let log = rec.take();
+    assert_eq!(log.ops, script());
+
+    // Serialise and parse the log
+    let log: Expectations = log.to_string().parse().unwrap();
+    assert_eq!(log.ops, script());
+
+    // Replay must reproduce the recorded results
+    let mut replay = ReplayEngine::new(log.ops, ReplayMode::Strict);
+    let replayed = guest(&mut replay);
+    replay.done().unwrap();
+
+    assert_eq!(recorded, replayed);
+}
+
+/// UART reporting more bytes written than provided
+struct Overlong;
+
+impl Engine for Overlong {
+    type Uart = Self;
+
+    fn uart(&mut self) -> Option<&mut Self::Uart> {
+        Some(self)
+    }
+}
+
+impl Uart for Overlong {
+    fn init(&mut self, _dev: u32, _baud: u32, _tx: i32, _rx: i32) -> Result<i32, Error> {
+        Ok(1)
+    }
+
+    fn deinit(&mut self, _handle: i32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write(&mut self, _: i32, _: UartFlags, _: u32, data: &[u8]) -> Result<usize, Error> {
+        Ok(data.len() + 10)
+    }
+
+    fn read(&mut self, _: i32, _: UartFlags, _: u32, buff: &mut [u8]) -> Result<usize, Error> {
+        Ok(buff.len() + 10)
+    }
+}
+
+#[test]
+fn record_clamps_uart_counts() {
+    let mut rec = RecordingEngine::new(Overlong);
+
+    assert_eq!(rec.uart().unwrap().write(1, UartFlags::NONE, 0, b"abc"), Ok(3));
+    assert_eq!(rec.uart().unwrap().read(1, UartFlags::NONE, 0, &mut [0u8; 2]), Ok(2));
+
+    let write = Op::UartWrite {
+        handle: 1,
+        flags: 0,
+        timeout_ms: 0,
+        data_out: b"abc".to_vec(),
+        count: Some(3),
+    };
+    let read = Op::UartRead { handle: 1, flags: 0, timeout_ms: 0, data_in: vec![0, 0] };
+    assert_eq!(rec.take().ops, vec![MockOp::new(write, 0), MockOp::new(read, 0)]);
+}
+
+#[test]
+fn record_mirrors_inner_peripherals() {
+    let mut rec = RecordingEngine::new(Overlong);
+
+    assert!(rec.uart().is_some());
+    assert!(rec.gpio().is_none());
+    assert!(rec.i2c().is_none());
+    assert!(rec.spi().is_none());
+    assert!(rec.adc().is_none());
+    assert!(rec.pwm().is_none());
+    assert!(rec.take().ops.is_empty());
+}
@@ -0,0 +1,111 @@
+//! Replay captured operation logs in strict and lenient modes
+
+use wasm_embedded_spec::mock::{MockError, MockOp, Op, ReplayEngine, ReplayMode};
+use wasm_embedded_spec::*;
+
+const LOG: &str = r#"
+ops = [
+  { kind = "spi_write", handle = 2, data_out = [0xaa, 0xbb], res = 0 },
+  { kind = "spi_read", handle = 2, data_in = [0x11, 0x22], res = 0 },
+  { kind = "uart_write", handle = 3, flags = 2, timeout_ms = 100, data_out = [0x61, 0x62, 0x63], count = 1, res = 0 },
+  { kind = "uart_configure", handle = 3, baud = 9600, data_bits = 8, parity = "even", stop_bits = "one", flow_control = "none", rts = -1, cts = -1, res = -5 },
+]
+"#;
+
+fn replay(mode: ReplayMode) -> ReplayEngine {
+    let log: mock::Expectations = LOG.parse().unwrap();
+    ReplayEngine::new(log.ops, mode)
+}
+
+#[test]
+fn strict_match() {
+    let mut r = replay(ReplayMode::Strict);
+
+    assert_eq!(r.spi().unwrap().write(2, &[0xaa, 0xbb]), Ok(()));
+
+    let mut buff = [0u8; 2];
+    assert_eq!(r.spi().unwrap().read(2, &mut buff), Ok(()));
+    assert_eq!(buff, [0x11, 0x22]);
+
+    // Recorded count and result codes are replayed
+    assert_eq!(r.uart().unwrap().write(3, UartFlags::TIMEOUT, 100, b"abc"), Ok(1));
+    let cfg = UartConfig { parity: Parity::Even, ..UartConfig::new(9600) };
+    assert_eq!(r.uart().unwrap().configure(3, &cfg), Err(Error::Unsupported));
+
+    assert_eq!(r.index(), 4);
+    assert_eq!(r.done(), Ok(()));
+}
+
+#[test]
+fn strict_mismatch() {
+    let mut r = replay(ReplayMode::Strict);
+
+    // Written data differs from the log
+    assert_eq!(r.spi().unwrap().write(2, &[0xaa, 0xcc]), Err(Error::Unexpected));
+
+    // Subsequent calls fail, even when matching
+    assert_eq!(r.spi().unwrap().read(2, &mut [0u8; 2]), Err(Error::Unexpected));
+
+    let expected = MockOp { op: Op::SpiWrite { handle: 2, data_out: vec![0xaa, 0xbb] }, res: 0 };
+    assert_eq!(
+        r.done(),
+        Err(MockError::Mismatch {
+            index: 0,
+            expected: Some(Box::new(expected)),
+            actual: Box::new(Op::SpiWrite { handle: 2, data_out: vec![0xaa, 0xcc] }),
+        })
+    );
+}
+
+#[test]
+fn strict_timeout_mismatch() {
+    let mut r = replay(ReplayMode::Strict);
+
+    assert_eq!(r.spi().unwrap().write(2, &[0xaa, 0xbb]), Ok(()));
+    assert_eq!(r.spi().unwrap().read(2, &mut [0u8; 2]), Ok(()));
+
+    // UART timeouts are compared with the log
+    let uart = r.uart().unwrap();
+    assert_eq!(uart.write(3, UartFlags::TIMEOUT, 10, b"abc"), Err(Error::Unexpected));
+    assert!(matches!(r.done(), Err(MockError::Mismatch { index: 2, .. })));
+}
+
+#[test]
+fn strict_incomplete() {
+    let mut r = replay(ReplayMode::Strict);
+
+    assert_eq!(r.spi().unwrap().write(2, &[0xaa, 0xbb]), Ok(()));
+
+    assert!(matches!(r.done(), Err(MockError::Incomplete { index: 1, .. })));
+}
+
+#[test]
+fn lenient_ignores_payloads() {
+    let mut r = replay(ReplayMode::Lenient);
+
+    // Written data and configuration are not compared
+    assert_eq!(r.spi().unwrap().write(2, &[0x01, 0x02, 0x03]), Ok(()));
+
+    let mut buff = [0u8; 2];
+    assert_eq!(r.spi().unwrap().read(2, &mut buff), Ok(()));
+    assert_eq!(buff, [0x11, 0x22]);
+
+    assert_eq!(r.uart().unwrap().write(3, UartFlags::NON_BLOCKING, 0, b"xyz"), Ok(1));
+    let cfg = UartConfig::new(115_200);
+    assert_eq!(r.uart().unwrap().configure(3, &cfg), Err(Error::Unsupported));
+
+    assert_eq!(r.done(), Ok(()));
+}
+
+#[test]
+fn lenient_mismatch() {
+    let mut r = replay(ReplayMode::Lenient);
+
+    // Handles and operation kinds are still checked
+    assert_eq!(r.spi().unwrap().write(1, &[0xaa, 0xbb]), Err(Error::Unexpected));
+    assert!(matches!(r.done(), Err(MockError::Mismatch { index: 0, .. })));
+
+    let mut r = replay(ReplayMode::Lenient);
+    assert_eq!(r.spi().unwrap().read(2, &mut [0u8; 2]), Err(Error::Unexpected));
+    assert!(matches!(r.done(), Err(MockError::Mismatch { index: 0, .. })));
+}
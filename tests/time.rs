@@ -0,0 +1,96 @@
+//! Async delays await platform sleep futures rather than busy-waiting
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::{Duration, Instant};
+
+use wasm_embedded_spec::wiggle::api::time::Time as _;
+use wasm_embedded_spec::{Error, StdTime};
+
+/// Waker unparking the polling thread
+struct Unpark(std::thread::Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future to completion, returning the output and the number of polls
+fn block_on<F: Future>(f: F) -> (F::Output, usize) {
+    let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut f = pin!(f);
+    let mut polls = 0;
+
+    loop {
+        polls += 1;
+        if let Poll::Ready(v) = f.as_mut().poll(&mut cx) {
+            return (v, polls);
+        }
+        std::thread::park();
+    }
+}
+
+#[test]
+fn delay_sleeps() {
+    let mut t = StdTime::new();
+
+    let start = Instant::now();
+    let (res, polls) = block_on(t.delay_ms(50));
+
+    assert_eq!(res, Ok(()));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    // Spurious unparks aside, the task is woken once by the timer
+    assert!(polls < 10, "{} polls", polls);
+}
+
+#[test]
+fn alarm_wait_sleeps() {
+    let mut t = StdTime::new();
+
+    let start = Instant::now();
+    let alarm = wasm_embedded_spec::Time::alarm_start(&mut t, 20_000).unwrap();
+    let (res, polls) = block_on(t.alarm_wait(alarm));
+
+    assert_eq!(res, Ok(()));
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert!(polls < 10, "{} polls", polls);
+
+    assert_eq!(block_on(t.alarm_wait(alarm + 1)).0, Err(Error::InvalidArg));
+}
+
+#[test]
+fn concurrent_sleeps() {
+    let mut t = StdTime::new();
+
+    // Sleeps share a single timer thread, each is woken at its own deadline
+    let mut sleeps: Vec<_> = [30_000, 10_000, 20_000]
+        .iter()
+        .map(|us| wasm_embedded_spec::Time::sleep_us(&mut t, *us).unwrap())
+        .map(Some)
+        .collect();
+
+    let start = Instant::now();
+    let mut order = vec![];
+    let (_, polls) = block_on(std::future::poll_fn(|cx| {
+        for (i, s) in sleeps.iter_mut().enumerate() {
+            if let Some(f) = s {
+                if f.as_mut().poll(cx).is_ready() {
+                    order.push(i);
+                    *s = None;
+                }
+            }
+        }
+        match order.len() {
+            3 => Poll::Ready(()),
+            _ => Poll::Pending,
+        }
+    }));
+
+    assert_eq!(order, [1, 2, 0]);
+    assert!(start.elapsed() >= Duration::from_millis(30));
+    assert!(polls < 20, "{} polls", polls);
+}
@@ -0,0 +1,51 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/harness.c");
+    println!("cargo:rerun-if-changed=../common/mock_drivers.h");
+    println!("cargo:rerun-if-env-changed=WAMR_DIR");
+
+    // Locate WAMR sources, see README.md
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let wamr = match env::var("WAMR_DIR") {
+        Ok(d) => PathBuf::from(d),
+        Err(_) => dir.join("wamr"),
+    };
+    let include = wamr.join("core/iwasm/include");
+    if !include.join("wasm_export.h").exists() {
+        panic!(
+            "WAMR sources not found at {}, clone WAMR (WAMR-1.3.2) here or set WAMR_DIR",
+            wamr.display()
+        );
+    }
+
+    // Generated headers exported by wasm-embedded-spec
+    let inc = env::var("DEP_EMBEDDED_WASM_SPEC_INCLUDE").expect("Missing spec include dir");
+
+    // Harness first, as it depends on the runtime library
+    cc::Build::new()
+        .file("src/harness.c")
+        .include(&include)
+        .include(&inc)
+        .include(dir.join("../common"))
+        .warnings(true)
+        .compile("harness");
+
+    // Build the WAMR runtime library, interpreter only
+    let out = cmake::Config::new(wamr.join("product-mini/platforms/linux"))
+        .define("WAMR_BUILD_INTERP", "1")
+        .define("WAMR_BUILD_FAST_INTERP", "1")
+        .define("WAMR_BUILD_AOT", "0")
+        .define("WAMR_BUILD_JIT", "0")
+        .define("WAMR_BUILD_LIBC_BUILTIN", "0")
+        .define("WAMR_BUILD_LIBC_WASI", "0")
+        .build_target("vmlib")
+        .build();
+
+    println!("cargo:rustc-link-search=native={}", out.join("build").display());
+    println!("cargo:rustc-link-lib=static=vmlib");
+    for l in ["pthread", "m", "dl"] {
+        println!("cargo:rustc-link-lib={}", l);
+    }
+}
@@ -0,0 +1,130 @@
+/// WAMR test harness, registering mock drivers via the generated host bindings
+
+#include <stdlib.h>
+
+#include "wasm_export.h"
+
+#include "wasm_embedded/wamr/adc.h"
+#include "wasm_embedded/wamr/device.h"
+#include "wasm_embedded/wamr/gpio.h"
+#include "wasm_embedded/wamr/i2c.h"
+#include "wasm_embedded/wamr/pwm.h"
+#include "wasm_embedded/wamr/spi.h"
+#include "wasm_embedded/wamr/time.h"
+#include "wasm_embedded/wamr/uart.h"
+
+#include "mock_drivers.h"
+
+/// Error buffer, valid until the next run
+static char harness_error[256];
+
+/// Run `func` from the provided guest, writing the function result to `result` and
+/// the mock driver log to `log`, returns NULL on success or a WAMR error.
+///
+/// The WAMR runtime is global, so runs must not be concurrent.
+const char *harness_run(const uint8_t *wasm, uint32_t wasm_len, const char *func,
+                        uint32_t *result, char *log, uint32_t log_len)
+{
+    mock_t mock = { .log = log, .log_len = log_len, .index = 0 };
+    if (log_len > 0) {
+        log[0] = '\0';
+    }
+
+    wasme_wamr_binding_t gpio_binding = { &mock_gpio, &mock };
+    wasme_wamr_binding_t i2c_binding = { &mock_i2c, &mock };
+    wasme_wamr_binding_t spi_binding = { &mock_spi, &mock };
+    wasme_wamr_binding_t uart_binding = { &mock_uart, &mock };
+    wasme_wamr_binding_t adc_binding = { &mock_adc, &mock };
+    wasme_wamr_binding_t pwm_binding = { &mock_pwm, &mock };
+    wasme_wamr_binding_t time_binding = { &mock_time, &mock };
+    wasme_wamr_binding_t device_binding = { &mock_device, &mock };
+
+    // Symbol tables are registered until the runtime is destroyed
+    NativeSymbol gpio_symbols[WASME_WAMR_GPIO_SYMBOLS];
+    NativeSymbol i2c_symbols[WASME_WAMR_I2C_SYMBOLS];
+    NativeSymbol spi_symbols[WASME_WAMR_SPI_SYMBOLS];
+    NativeSymbol uart_symbols[WASME_WAMR_UART_SYMBOLS];
+    NativeSymbol adc_symbols[WASME_WAMR_ADC_SYMBOLS];
+    NativeSymbol pwm_symbols[WASME_WAMR_PWM_SYMBOLS];
+    NativeSymbol time_symbols[WASME_WAMR_TIME_SYMBOLS];
+    NativeSymbol device_symbols[WASME_WAMR_DEVICE_SYMBOLS];
+
+    const char *res = NULL;
+    uint8_t *buff = NULL;
+    wasm_module_t module = NULL;
+    wasm_module_inst_t inst = NULL;
+    wasm_exec_env_t exec_env = NULL;
+    wasm_function_inst_t f = NULL;
+    uint32_t argv[1] = { 0 };
+
+    RuntimeInitArgs init;
+    memset(&init, 0, sizeof(init));
+    init.mem_alloc_type = Alloc_With_System_Allocator;
+    if (!wasm_runtime_full_init(&init)) {
+        return "failed to initialise runtime";
+    }
+
+    if (!wasme_wamr_register_gpio(gpio_symbols, &gpio_binding)
+        || !wasme_wamr_register_i2c(i2c_symbols, &i2c_binding)
+        || !wasme_wamr_register_spi(spi_symbols, &spi_binding)
+        || !wasme_wamr_register_uart(uart_symbols, &uart_binding)
+        || !wasme_wamr_register_adc(adc_symbols, &adc_binding)
+        || !wasme_wamr_register_pwm(pwm_symbols, &pwm_binding)
+        || !wasme_wamr_register_time(time_symbols, &time_binding)
+        || !wasme_wamr_register_device(device_symbols, &device_binding)) {
+        res = "failed to register native symbols";
+        goto done;
+    }
+
+    // WAMR may modify the module buffer while loading
+    if ((buff = malloc(wasm_len)) == NULL) {
+        res = "failed to allocate module buffer";
+        goto done;
+    }
+    memcpy(buff, wasm, wasm_len);
+
+    module = wasm_runtime_load(buff, wasm_len, harness_error, sizeof(harness_error));
+    if (module == NULL) {
+        res = harness_error;
+        goto done;
+    }
+
+    inst = wasm_runtime_instantiate(module, 64 * 1024, 0, harness_error, sizeof(harness_error));
+    if (inst == NULL) {
+        res = harness_error;
+        goto done;
+    }
+
+    if ((f = wasm_runtime_lookup_function(inst, func, NULL)) == NULL) {
+        res = "function lookup failed";
+        goto done;
+    }
+
+    if ((exec_env = wasm_runtime_create_exec_env(inst, 64 * 1024)) == NULL) {
+        res = "failed to create execution environment";
+        goto done;
+    }
+
+    if (!wasm_runtime_call_wasm(exec_env, f, 0, argv)) {
+        snprintf(harness_error, sizeof(harness_error), "%s", wasm_runtime_get_exception(inst));
+        res = harness_error;
+        goto done;
+    }
+
+    *result = argv[0];
+
+done:
+    if (exec_env != NULL) {
+        wasm_runtime_destroy_exec_env(exec_env);
+    }
+    if (inst != NULL) {
+        wasm_runtime_deinstantiate(inst);
+    }
+    if (module != NULL) {
+        wasm_runtime_unload(module);
+    }
+    free(buff);
+    wasm_runtime_destroy();
+
+    return res;
+}
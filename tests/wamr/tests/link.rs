@@ -0,0 +1,7 @@
+//! Run a guest on WAMR with the mock drivers and check arguments and results
+//! are translated by the generated host bindings
+
+use wasm_embedded_wamr_tests::{run, Run};
+
+// Tests are shared between runtimes so the bindings are checked identically
+include!("../../common/link_tests.rs");
@@ -0,0 +1,47 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/harness.c");
+    println!("cargo:rerun-if-changed=../common/mock_drivers.h");
+    println!("cargo:rerun-if-env-changed=WASM3_DIR");
+
+    // Locate wasm3 sources, see README.md
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let wasm3 = match env::var("WASM3_DIR") {
+        Ok(d) => PathBuf::from(d),
+        Err(_) => dir.join("wasm3"),
+    };
+    let source = wasm3.join("source");
+    if !source.join("wasm3.h").exists() {
+        panic!(
+            "wasm3 sources not found at {}, clone wasm3 (v0.5.0) here or set WASM3_DIR",
+            wasm3.display()
+        );
+    }
+
+    // Generated headers exported by wasm-embedded-spec
+    let inc = env::var("DEP_EMBEDDED_WASM_SPEC_INCLUDE").expect("Missing spec include dir");
+
+    let mut files: Vec<_> = std::fs::read_dir(&source)
+        .expect("Failed to read wasm3 sources")
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|e| e == "c").unwrap_or(false))
+        .collect();
+    files.sort();
+
+    cc::Build::new()
+        .files(files)
+        .include(&source)
+        .warnings(false)
+        .opt_level(2)
+        .compile("wasm3");
+
+    cc::Build::new()
+        .file("src/harness.c")
+        .include(&source)
+        .include(&inc)
+        .include(dir.join("../common"))
+        .warnings(true)
+        .compile("harness");
+}
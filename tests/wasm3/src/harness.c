@@ -0,0 +1,77 @@
+/// wasm3 test harness, linking guests to mock drivers via the generated host bindings
+
+#include "wasm3.h"
+
+#include "wasm_embedded/wasm3/adc.h"
+#include "wasm_embedded/wasm3/device.h"
+#include "wasm_embedded/wasm3/gpio.h"
+#include "wasm_embedded/wasm3/i2c.h"
+#include "wasm_embedded/wasm3/pwm.h"
+#include "wasm_embedded/wasm3/spi.h"
+#include "wasm_embedded/wasm3/time.h"
+#include "wasm_embedded/wasm3/uart.h"
+
+#include "mock_drivers.h"
+
+/// Run `func` from the provided guest, writing the function result to `result` and
+/// the mock driver log to `log`, returns NULL on success or a wasm3 error
+const char *harness_run(const uint8_t *wasm, uint32_t wasm_len, const char *func,
+                        uint32_t *result, char *log, uint32_t log_len)
+{
+    mock_t mock = { .log = log, .log_len = log_len, .index = 0 };
+    if (log_len > 0) {
+        log[0] = '\0';
+    }
+
+    wasme_wasm3_binding_t gpio_binding = { &mock_gpio, &mock };
+    wasme_wasm3_binding_t i2c_binding = { &mock_i2c, &mock };
+    wasme_wasm3_binding_t spi_binding = { &mock_spi, &mock };
+    wasme_wasm3_binding_t uart_binding = { &mock_uart, &mock };
+    wasme_wasm3_binding_t adc_binding = { &mock_adc, &mock };
+    wasme_wasm3_binding_t pwm_binding = { &mock_pwm, &mock };
+    wasme_wasm3_binding_t time_binding = { &mock_time, &mock };
+    wasme_wasm3_binding_t device_binding = { &mock_device, &mock };
+
+    IM3Environment env = m3_NewEnvironment();
+    IM3Runtime runtime = m3_NewRuntime(env, 64 * 1024, NULL);
+    IM3Module module = NULL;
+    IM3Function f = NULL;
+
+    M3Result res = m3_ParseModule(env, &module, wasm, wasm_len);
+    if (res != m3Err_none) {
+        goto done;
+    }
+
+    res = m3_LoadModule(runtime, module);
+    if (res != m3Err_none) {
+        m3_FreeModule(module);
+        goto done;
+    }
+
+    if ((res = wasme_wasm3_link_gpio(module, &gpio_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_i2c(module, &i2c_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_spi(module, &spi_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_uart(module, &uart_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_adc(module, &adc_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_pwm(module, &pwm_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_time(module, &time_binding)) != m3Err_none
+        || (res = wasme_wasm3_link_device(module, &device_binding)) != m3Err_none) {
+        goto done;
+    }
+
+    if ((res = m3_FindFunction(&f, runtime, func)) != m3Err_none) {
+        goto done;
+    }
+
+    if ((res = m3_CallV(f)) != m3Err_none) {
+        goto done;
+    }
+
+    res = m3_GetResultsV(f, result);
+
+done:
+    m3_FreeRuntime(runtime);
+    m3_FreeEnvironment(env);
+
+    return res;
+}
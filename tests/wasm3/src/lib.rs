@@ -0,0 +1,54 @@
+//! wasm3 host binding test harness.
+//!
+//! Runs guests on wasm3 with mock C drivers (see `src/harness.c`) linked using the
+//! generated `inc/wasm_embedded/wasm3` bindings, recording driver calls to a log.
+
+use std::ffi::{c_char, CStr, CString};
+
+extern "C" {
+    fn harness_run(
+        wasm: *const u8,
+        wasm_len: u32,
+        func: *const c_char,
+        result: *mut u32,
+        log: *mut c_char,
+        log_len: u32,
+    ) -> *const c_char;
+}
+
+/// Guest execution outcome
+#[derive(Clone, PartialEq, Debug)]
+pub struct Run {
+    /// Function result, or wasm3 error (including traps)
+    pub result: Result<u32, String>,
+    /// Mock driver calls, one per line
+    pub log: Vec<String>,
+}
+
+/// Run the exported function `func` (returning an `i32`) from the provided guest
+pub fn run(wasm: &[u8], func: &str) -> Run {
+    let func = CString::new(func).unwrap();
+    let mut log = vec![0u8; 4096];
+    let mut value = 0;
+
+    let err = unsafe {
+        harness_run(
+            wasm.as_ptr(),
+            wasm.len() as u32,
+            func.as_ptr(),
+            &mut value,
+            log.as_mut_ptr() as *mut c_char,
+            log.len() as u32,
+        )
+    };
+
+    let result = match err.is_null() {
+        true => Ok(value),
+        false => Err(unsafe { CStr::from_ptr(err) }.to_string_lossy().to_string()),
+    };
+
+    let log = CStr::from_bytes_until_nul(&log).unwrap().to_string_lossy();
+    let log = log.lines().map(|l| l.to_string()).collect();
+
+    Run { result, log }
+}
@@ -0,0 +1,7 @@
+//! Link a guest through wasm3 to the mock drivers and check arguments and results
+//! are translated by the generated host bindings
+
+use wasm_embedded_wasm3_tests::{run, Run};
+
+// Tests are shared between runtimes so the bindings are checked identically
+include!("../../common/link_tests.rs");